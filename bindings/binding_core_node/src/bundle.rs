@@ -3,7 +3,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::{bail, Error};
+use anyhow::Error;
 use napi::{
     bindgen_prelude::{AbortSignal, AsyncTask, Buffer},
     Env, Status, Task,
@@ -16,7 +16,7 @@ use swc_core::{
         resolver::{environment_resolver, paths_resolver},
         Compiler, TransformOutput,
     },
-    bundler::{BundleKind, Bundler, Load, ModuleRecord, Resolve},
+    bundler::{Bundler, Load, ModuleRecord, Resolve},
     common::{collections::AHashMap, Globals, Span, GLOBALS},
     ecma::{
         ast::{
@@ -95,6 +95,7 @@ impl Task for BundleTask {
                                     .cloned(),
                             )
                             .collect(),
+                        code_splitting: self.config.static_items.config.split_chunks(),
                         ..Default::default()
                     },
                     Box::new(Hook),
@@ -106,12 +107,7 @@ impl Task for BundleTask {
 
                 let result = result
                     .into_iter()
-                    .map(|bundle| match bundle.kind {
-                        BundleKind::Named { name } | BundleKind::Lib { name } => {
-                            Ok((name, bundle.module))
-                        }
-                        BundleKind::Dynamic => bail!("unimplemented: dynamic code splitting"),
-                    })
+                    .map(|bundle| Ok((bundle.file_name, bundle.module)))
                     .map(|res| {
                         res.and_then(|(k, m)| {
                             // TODO: Source map
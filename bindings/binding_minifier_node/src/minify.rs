@@ -7,7 +7,8 @@ use napi::{
 };
 use serde::Deserialize;
 use swc_compiler_base::{
-    minify_file_comments, parse_js, IdentCollector, IsModule, SourceMapsConfig, TransformOutput,
+    minify_extra_output, minify_file_comments, parse_js, IdentCollector, IsModule,
+    SourceMapsConfig, TransformOutput,
 };
 use swc_config::config_types::BoolOr;
 use swc_core::{
@@ -20,7 +21,9 @@ use swc_core::{
     ecma::{
         minifier::{
             js::{JsMinifyCommentOption, JsMinifyOptions},
-            option::{MinifyOptions, TopLevelOptions},
+            option::{MangleCache, MinifyOptions, SimpleMangleCache, TopLevelOptions},
+            size::top_level_decls,
+            trace::OptimizationTrace,
         },
         parser::{EsConfig, Syntax},
         transforms::base::{fixer::fixer, hygiene::hygiene, resolver},
@@ -159,6 +162,12 @@ fn do_work(input: MinifyTarget, options: JsMinifyOptions) -> napi::Result<Transf
         )
         .context("failed to parse input file")?;
 
+        let decls = if options.report_size {
+            Some(top_level_decls(&module, &cm))
+        } else {
+            None
+        };
+
         let source_map_names = if source_map.enabled() {
             let mut v = IdentCollector {
                 names: Default::default(),
@@ -176,6 +185,14 @@ fn do_work(input: MinifyTarget, options: JsMinifyOptions) -> napi::Result<Transf
 
         let is_mangler_enabled = min_opts.mangle.is_some();
 
+        let name_cache = options
+            .name_cache
+            .clone()
+            .map(|v| Arc::new(SimpleMangleCache::new(v)));
+        let optimization_trace = options
+            .optimization_trace
+            .then(|| Arc::new(OptimizationTrace::default()));
+
         let module = (|| {
             let module = module.fold_with(&mut resolver(unresolved_mark, top_level_mark, false));
 
@@ -188,6 +205,8 @@ fn do_work(input: MinifyTarget, options: JsMinifyOptions) -> napi::Result<Transf
                 &swc_core::ecma::minifier::option::ExtraOptions {
                     unresolved_mark,
                     top_level_mark,
+                    mangle_name_cache: name_cache.clone().map(|v| v as Arc<dyn MangleCache>),
+                    optimization_trace: optimization_trace.clone(),
                 },
             );
 
@@ -206,7 +225,29 @@ fn do_work(input: MinifyTarget, options: JsMinifyOptions) -> napi::Result<Transf
             .unwrap_or(BoolOr::Data(JsMinifyCommentOption::PreserveSomeComments));
         minify_file_comments(&comments, preserve_comments);
 
-        swc_compiler_base::print(
+        let codegen_config = swc_core::ecma::codegen::Config::default()
+            .with_target(target)
+            .with_minify(true)
+            .with_ascii_only(options.format.ascii_only)
+            .with_emit_assert_for_import_attributes(
+                options.format.emit_assert_for_import_attributes,
+            );
+
+        // Sizes are measured before printing, which takes the comments.
+        let extra = minify_extra_output(
+            cm.clone(),
+            handler,
+            &module,
+            &fm.name,
+            &options,
+            &comments,
+            codegen_config,
+            decls.as_deref(),
+            name_cache.as_deref(),
+            optimization_trace.as_deref(),
+        )?;
+
+        let mut output = swc_compiler_base::print(
             cm.clone(),
             &module,
             Some(&fm.name.to_string()),
@@ -218,14 +259,11 @@ fn do_work(input: MinifyTarget, options: JsMinifyOptions) -> napi::Result<Transf
             Some(&comments),
             options.emit_source_map_columns,
             &options.format.preamble,
-            swc_core::ecma::codegen::Config::default()
-                .with_target(target)
-                .with_minify(true)
-                .with_ascii_only(options.format.ascii_only)
-                .with_emit_assert_for_import_attributes(
-                    options.format.emit_assert_for_import_attributes,
-                ),
-        )
+            codegen_config,
+        )?;
+        output.output = extra;
+
+        Ok(output)
     })
     .convert_err()
 }
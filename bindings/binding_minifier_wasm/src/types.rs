@@ -52,6 +52,40 @@ export interface JsMinifyOptions {
   outputPath?: string
 
   inlineSourcesContent?: boolean
+
+  /**
+   * Names chosen by the mangler for previous files, in the format of
+   * `nameCache` of terser. Original names are prefixed with `$`. The
+   * updated cache is returned as `nameCache` of `output` of the result.
+   */
+  nameCache?: {
+    vars?: {
+      props?: Record<string, string>
+    }
+    props?: {
+      props?: Record<string, string>
+    }
+  }
+
+  /**
+   * Reports raw, gzip and brotli sizes of the output as `output` of the result.
+   */
+  reportSize?: boolean
+
+  /**
+   * Fails if sizes of the output in bytes exceed these.
+   */
+  sizeBudget?: {
+    raw?: number
+    gzip?: number
+    brotli?: number
+  }
+
+  /**
+   * Records why the compressor kept code, as `optimizationTrace` of `output`
+   * of the result.
+   */
+  optimizationTrace?: boolean
 }
 
 /**
@@ -298,6 +332,15 @@ export interface TerserCompressOptions {
   pure_funcs?: string[],
 
 
+  pure_builtins?: boolean,
+
+
+  pure_libraries?: {
+    globals?: string[],
+    modules?: string[],
+  },
+
+
   reduce_funcs?: boolean,
 
 
@@ -681,6 +724,13 @@ export interface JscConfig {
     [from: string]: string[]
   }
 
+  /**
+   * Conditions of the `exports` and `imports` fields of package.json, like
+   * `development`. `import` or `require` is added depending on the kind of
+   * the request, and `default` always matches.
+   */
+  conditions?: string[]
+
   minify?: JsMinifyOptions;
 
   preserveAllComments?: boolean;
@@ -1115,6 +1165,11 @@ export interface Output {
    * Sourcemap (**not** base64 encoded)
    */
   map?: string;
+  /**
+   * Extra data as JSON, e.g. `size` if `reportSize` of minify options is
+   * set.
+   */
+  output?: string;
 }
 
 export interface MatchPattern { }
@@ -59,6 +59,8 @@ pub fn get_minified_with_opts(
             &swc_ecma_minifier::option::ExtraOptions {
                 unresolved_mark: m.unresolved_mark,
                 top_level_mark: m.top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module()
@@ -46,6 +46,7 @@ pub struct PassBuilder<'a, 'b, P: swc_ecma_visit::Fold> {
     inject_helpers: bool,
     minify: Option<JsMinifyOptions>,
     regenerator: regenerator::Config,
+    conditions: Vec<String>,
 }
 
 impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
@@ -73,6 +74,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             inject_helpers: true,
             minify: None,
             regenerator: Default::default(),
+            conditions: Default::default(),
         }
     }
 
@@ -96,6 +98,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             inject_helpers: self.inject_helpers,
             minify: self.minify,
             regenerator: self.regenerator,
+            conditions: self.conditions,
         }
     }
 
@@ -154,6 +157,13 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
         self
     }
 
+    /// Conditions used to resolve the `exports` and `imports` fields of
+    /// package.json. Empty means the defaults of the resolver.
+    pub fn conditions(mut self, conditions: Vec<String>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
     /// # Arguments
     /// ## module
     ///  - Use `None` if you want swc to emit import statements.
@@ -349,6 +359,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
                 comments,
                 base_url,
                 paths,
+                self.conditions,
                 base,
                 self.unresolved_mark,
                 module,
@@ -438,6 +449,8 @@ impl VisitMut for MinifierPass<'_> {
                     &swc_ecma_minifier::option::ExtraOptions {
                         unresolved_mark,
                         top_level_mark,
+                        mangle_name_cache: None,
+                        optimization_trace: None,
                     },
                 )
                 .expect_module()
@@ -498,6 +511,8 @@ impl VisitMut for MinifierPass<'_> {
                     &swc_ecma_minifier::option::ExtraOptions {
                         unresolved_mark,
                         top_level_mark,
+                        mangle_name_cache: None,
+                        optimization_trace: None,
                     },
                 )
                 .expect_script()
@@ -35,8 +35,12 @@ use swc_ecma_lints::{
     config::LintConfig,
     rules::{lint_to_fold, LintParams},
 };
-use swc_ecma_loader::resolvers::{
-    lru::CachingResolver, node::NodeModulesResolver, tsc::TsConfigResolver,
+#[cfg(feature = "pnp")]
+use swc_ecma_loader::resolvers::pnp::PnpManifest;
+use swc_ecma_loader::{
+    resolve::Resolve,
+    resolvers::{lru::CachingResolver, node::NodeModulesResolver, tsc::TsConfigResolver},
+    TargetEnv,
 };
 pub use swc_ecma_minifier::js::*;
 use swc_ecma_minifier::option::terser::TerserTopLevelOptions;
@@ -46,7 +50,10 @@ use swc_ecma_parser::{parse_file_as_expr, Syntax, TsConfig};
 use swc_ecma_transforms::{
     feature::FeatureFlag,
     hygiene, modules,
-    modules::{path::NodeImportResolver, rewriter::import_rewriter, EsModuleConfig},
+    modules::{
+        import_analysis::interop_diagnostics, path::NodeImportResolver, rewriter::import_rewriter,
+        EsModuleConfig,
+    },
     optimization::{const_modules, json_parse, simplifier},
     pass::{noop, Optional},
     proposals::{
@@ -252,6 +259,7 @@ impl Options {
             keep_class_names,
             base_url,
             paths,
+            conditions,
             minify: mut js_minify,
             experimental,
             lints,
@@ -302,6 +310,10 @@ impl Options {
             syntax.typescript(),
         ));
 
+        if let Some(minify) = &mut js_minify {
+            minify.load_pure_libraries(&self.cwd)?;
+        }
+
         if program.is_module() {
             js_minify = js_minify.map(|c| {
                 let compress = c
@@ -558,6 +570,7 @@ impl Options {
         .fixer(!self.disable_fixer)
         .preset_env(cfg.env)
         .regenerator(regenerator)
+        .conditions(conditions)
         .finalize(
             base_url,
             paths.into_iter().collect(),
@@ -680,6 +693,7 @@ impl Options {
                     unresolved_ctxt,
                     es_version,
                     source_map: cm.clone(),
+                    comments: comments.map(|v| v as _),
                 })),
                 // Decorators may use type information
                 Optional::new(
@@ -1149,6 +1163,10 @@ pub struct JscConfig {
     #[serde(default)]
     pub paths: Paths,
 
+    /// Conditions of the `exports` and `imports` fields of package.json.
+    #[serde(default)]
+    pub conditions: Vec<String>,
+
     #[serde(default)]
     pub minify: Option<JsMinifyOptions>,
 
@@ -1283,12 +1301,17 @@ impl ModuleConfig {
         comments: Option<&'cmt dyn Comments>,
         base_url: PathBuf,
         paths: CompiledPaths,
+        conditions: Vec<String>,
         base: &FileName,
         unresolved_mark: Mark,
         config: Option<ModuleConfig>,
         available_features: FeatureFlag,
     ) -> Box<dyn swc_ecma_visit::Fold + 'cmt> {
-        let skip_resolver = base_url.as_os_str().is_empty() && paths.is_empty();
+        let fully_specified = matches!(
+            &config,
+            Some(ModuleConfig::Es6(c) | ModuleConfig::NodeNext(c)) if c.fully_specified
+        );
+        let skip_resolver = base_url.as_os_str().is_empty() && paths.is_empty() && !fully_specified;
 
         let base = match base {
             FileName::Real(v) if !skip_resolver => {
@@ -1302,7 +1325,7 @@ impl ModuleConfig {
                 if skip_resolver {
                     Box::new(noop())
                 } else {
-                    let resolver = build_resolver(base_url, paths, false);
+                    let resolver = build_resolver(base_url, paths, conditions, false, false);
 
                     Box::new(import_rewriter(base, resolver))
                 }
@@ -1311,28 +1334,61 @@ impl ModuleConfig {
                 if skip_resolver {
                     Box::new(noop())
                 } else {
-                    let resolver = build_resolver(base_url, paths, config.resolve_fully);
+                    let resolver = build_resolver(
+                        base_url,
+                        paths,
+                        conditions,
+                        config.resolve_fully,
+                        config.fully_specified,
+                    );
 
                     Box::new(import_rewriter(base, resolver))
                 }
             }
             Some(ModuleConfig::CommonJs(config)) => {
+                let interop_diagnostics = match &base {
+                    FileName::Real(..) if config.interop_diagnostics => {
+                        let resolver: Box<dyn Resolve> = {
+                            let mut r =
+                                NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+                            if !conditions.is_empty() {
+                                r = r.with_conditions(conditions.clone());
+                            }
+                            if skip_resolver {
+                                Box::new(r)
+                            } else {
+                                Box::new(TsConfigResolver::new(r, base_url.clone(), paths.clone()))
+                            }
+                        };
+
+                        Either::Left(interop_diagnostics(resolver, base.clone()))
+                    }
+                    _ => Either::Right(noop()),
+                };
+
                 if skip_resolver {
-                    Box::new(modules::common_js::common_js(
-                        unresolved_mark,
-                        config,
-                        available_features,
-                        comments,
+                    Box::new(chain!(
+                        interop_diagnostics,
+                        modules::common_js::common_js(
+                            unresolved_mark,
+                            config,
+                            available_features,
+                            comments,
+                        )
                     ))
                 } else {
-                    let resolver = build_resolver(base_url, paths, config.resolve_fully);
-                    Box::new(modules::common_js::common_js_with_resolver(
-                        resolver,
-                        base,
-                        unresolved_mark,
-                        config,
-                        available_features,
-                        comments,
+                    let resolver =
+                        build_resolver(base_url, paths, conditions, config.resolve_fully, false);
+                    Box::new(chain!(
+                        interop_diagnostics,
+                        modules::common_js::common_js_with_resolver(
+                            resolver,
+                            base,
+                            unresolved_mark,
+                            config,
+                            available_features,
+                            comments,
+                        )
                     ))
                 }
             }
@@ -1346,7 +1402,13 @@ impl ModuleConfig {
                         comments,
                     ))
                 } else {
-                    let resolver = build_resolver(base_url, paths, config.config.resolve_fully);
+                    let resolver = build_resolver(
+                        base_url,
+                        paths,
+                        conditions,
+                        config.config.resolve_fully,
+                        false,
+                    );
 
                     Box::new(modules::umd::umd_with_resolver(
                         cm,
@@ -1368,7 +1430,13 @@ impl ModuleConfig {
                         comments,
                     ))
                 } else {
-                    let resolver = build_resolver(base_url, paths, config.config.resolve_fully);
+                    let resolver = build_resolver(
+                        base_url,
+                        paths,
+                        conditions,
+                        config.config.resolve_fully,
+                        false,
+                    );
 
                     Box::new(modules::amd::amd_with_resolver(
                         resolver,
@@ -1384,7 +1452,8 @@ impl ModuleConfig {
                 if skip_resolver {
                     Box::new(modules::system_js::system_js(unresolved_mark, config))
                 } else {
-                    let resolver = build_resolver(base_url, paths, config.resolve_fully);
+                    let resolver =
+                        build_resolver(base_url, paths, conditions, config.resolve_fully, false);
 
                     Box::new(modules::system_js::system_js_with_resolver(
                         resolver,
@@ -1719,13 +1788,17 @@ fn default_env_name() -> String {
 fn build_resolver(
     mut base_url: PathBuf,
     paths: CompiledPaths,
+    conditions: Vec<String>,
     resolve_fully: bool,
+    fully_specified: bool,
 ) -> Box<SwcImportResolver> {
-    static CACHE: Lazy<DashMap<(PathBuf, CompiledPaths, bool), SwcImportResolver, ARandomState>> =
+    type CacheKey = (PathBuf, CompiledPaths, Vec<String>, bool, bool);
+
+    static CACHE: Lazy<DashMap<CacheKey, SwcImportResolver, ARandomState>> =
         Lazy::new(Default::default);
 
     // On Windows, we need to normalize path as UNC path.
-    if cfg!(target_os = "windows") {
+    if cfg!(target_os = "windows") && !base_url.as_os_str().is_empty() {
         base_url = base_url
             .canonicalize()
             .with_context(|| {
@@ -1738,29 +1811,51 @@ fn build_resolver(
             .unwrap();
     }
 
-    if let Some(cached) = CACHE.get(&(base_url.clone(), paths.clone(), resolve_fully)) {
+    if let Some(cached) = CACHE.get(&(
+        base_url.clone(),
+        paths.clone(),
+        conditions.clone(),
+        resolve_fully,
+        fully_specified,
+    )) {
         return Box::new((*cached).clone());
     }
 
     let r = {
-        let r = TsConfigResolver::new(
-            NodeModulesResolver::without_node_modules(Default::default(), Default::default(), true),
-            base_url.clone(),
-            paths.clone(),
-        );
+        let mut node_resolver =
+            NodeModulesResolver::without_node_modules(Default::default(), Default::default(), true);
+        if !conditions.is_empty() {
+            node_resolver = node_resolver.with_conditions(conditions.clone());
+        }
+        // `base_url` is empty if only `fully_specified` is enabled.
+        #[cfg(feature = "pnp")]
+        match PnpManifest::find(&base_url) {
+            Ok(Some(manifest)) => node_resolver = node_resolver.with_pnp(Arc::new(manifest)),
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!("failed to load the manifest of Yarn Plug'n'Play: {:?}", err)
+            }
+        }
+
+        let r = TsConfigResolver::new(node_resolver, base_url.clone(), paths.clone());
         let r = CachingResolver::new(40, r);
 
         let r = NodeImportResolver::with_config(
             r,
             swc_ecma_transforms::modules::path::Config {
-                base_dir: Some(base_url.clone()),
+                // `base_url` is empty if only `fully_specified` is enabled.
+                base_dir: Some(base_url.clone()).filter(|v| !v.as_os_str().is_empty()),
                 resolve_fully,
+                fully_specified,
             },
         );
         Arc::new(r)
     };
 
-    CACHE.insert((base_url, paths, resolve_fully), r.clone());
+    CACHE.insert(
+        (base_url, paths, conditions, resolve_fully, fully_specified),
+        r.clone(),
+    );
 
     Box::new(r)
 }
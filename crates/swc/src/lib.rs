@@ -136,7 +136,11 @@ use swc_ecma_codegen::{self, Node};
 use swc_ecma_loader::resolvers::{
     lru::CachingResolver, node::NodeModulesResolver, tsc::TsConfigResolver,
 };
-use swc_ecma_minifier::option::{MinifyOptions, TopLevelOptions};
+use swc_ecma_minifier::{
+    option::{MangleCache, MinifyOptions, SimpleMangleCache, TopLevelOptions},
+    size::top_level_decls,
+    trace::OptimizationTrace,
+};
 use swc_ecma_parser::{EsConfig, Syntax};
 use swc_ecma_transforms::{
     fixer,
@@ -480,41 +484,39 @@ impl Compiler {
         }
     }
 
-    #[tracing::instrument(skip_all)]
-    pub fn read_config(&self, opts: &Options, name: &FileName) -> Result<Option<Config>, Error> {
-        static CUR_DIR: Lazy<PathBuf> = Lazy::new(|| {
-            if cfg!(target_arch = "wasm32") {
-                PathBuf::new()
-            } else {
-                ::std::env::current_dir().unwrap()
-            }
-        });
-
-        self.run(|| -> Result<_, Error> {
-            let Options {
-                ref root,
-                root_mode,
-                swcrc,
-                config_file,
-                ..
-            } = opts;
-
-            let root = root.as_ref().unwrap_or(&CUR_DIR);
-
-            let swcrc_path = match config_file {
-                Some(ConfigFile::Str(s)) => Some(PathBuf::from(s.clone())),
-                _ => {
-                    if *swcrc {
-                        if let FileName::Real(ref path) = name {
-                            find_swcrc(path, root, *root_mode)
-                        } else {
-                            None
-                        }
+    /// Returns the path of the config file [Compiler::read_config] loads for
+    /// `name`, if any.
+    pub fn swcrc_path(&self, opts: &Options, name: &FileName) -> Option<PathBuf> {
+        let Options {
+            ref root,
+            root_mode,
+            swcrc,
+            config_file,
+            ..
+        } = opts;
+
+        let root = root.as_ref().unwrap_or(&CUR_DIR);
+
+        match config_file {
+            Some(ConfigFile::Str(s)) => Some(PathBuf::from(s.clone())),
+            _ => {
+                if *swcrc {
+                    if let FileName::Real(ref path) = name {
+                        find_swcrc(path, root, *root_mode)
                     } else {
                         None
                     }
+                } else {
+                    None
                 }
-            };
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub fn read_config(&self, opts: &Options, name: &FileName) -> Result<Option<Config>, Error> {
+        self.run(|| -> Result<_, Error> {
+            let swcrc_path = self.swcrc_path(opts, name);
 
             let config_file = match swcrc_path.as_deref() {
                 Some(s) => Some(load_swcrc(s)?),
@@ -561,6 +563,12 @@ impl Compiler {
                                 })?
                             };
                         }
+
+                        if let Some(minify) = &mut c.jsc.minify {
+                            minify
+                                .load_pure_libraries(dir)
+                                .context("failed to process config file")?;
+                        }
                     }
 
                     return Ok(config);
@@ -759,6 +767,11 @@ impl Compiler {
         self.run(|| {
             let _timer = timer!("Compiler::minify");
 
+            #[cfg(not(feature = "size_report"))]
+            if opts.report_size || opts.size_budget.is_some() {
+                bail!("`reportSize` and `sizeBudget` require the `size_report` feature of swc");
+            }
+
             let target = opts.ecma.clone().into();
 
             let (source_map, orig) = opts
@@ -791,7 +804,12 @@ impl Compiler {
                         Some(true) | None => Some(Default::default()),
                         _ => None,
                     })
-                    .map(|v| v.into_config(self.cm.clone())),
+                    .map(|mut v| {
+                        v.load_pure_libraries(Path::new(""))?;
+
+                        Ok::<_, Error>(v.into_config(self.cm.clone()))
+                    })
+                    .transpose()?,
                 mangle: opts
                     .mangle
                     .clone()
@@ -848,6 +866,14 @@ impl Compiler {
                 )
                 .context("failed to parse input file")?;
 
+            // The breakdown is computed from a separate source map, which points to
+            // the input file even if `orig` is given.
+            let decls = if opts.report_size {
+                Some(top_level_decls(&module, &self.cm))
+            } else {
+                None
+            };
+
             let source_map_names = if source_map.enabled() {
                 let mut v = swc_compiler_base::IdentCollector {
                     names: Default::default(),
@@ -865,6 +891,14 @@ impl Compiler {
 
             let is_mangler_enabled = min_opts.mangle.is_some();
 
+            let name_cache = opts
+                .name_cache
+                .clone()
+                .map(|v| Arc::new(SimpleMangleCache::new(v)));
+            let optimization_trace = opts
+                .optimization_trace
+                .then(|| Arc::new(OptimizationTrace::default()));
+
             let module = self.run_transform(handler, false, || {
                 let module =
                     module.fold_with(&mut resolver(unresolved_mark, top_level_mark, false));
@@ -878,6 +912,8 @@ impl Compiler {
                     &swc_ecma_minifier::option::ExtraOptions {
                         unresolved_mark,
                         top_level_mark,
+                        mangle_name_cache: name_cache.clone().map(|v| v as Arc<dyn MangleCache>),
+                        optimization_trace: optimization_trace.clone(),
                     },
                 );
 
@@ -895,7 +931,29 @@ impl Compiler {
                 .unwrap_or(BoolOr::Data(JsMinifyCommentOption::PreserveSomeComments));
             swc_compiler_base::minify_file_comments(&comments, preserve_comments);
 
-            self.print(
+            let codegen_config = swc_ecma_codegen::Config::default()
+                .with_target(target)
+                .with_minify(true)
+                .with_ascii_only(opts.format.ascii_only)
+                .with_emit_assert_for_import_attributes(
+                    opts.format.emit_assert_for_import_attributes,
+                );
+
+            // Sizes are measured before printing, which takes the comments.
+            let extra = swc_compiler_base::minify_extra_output(
+                self.cm.clone(),
+                handler,
+                &module,
+                &fm.name,
+                opts,
+                &comments,
+                codegen_config,
+                decls.as_deref(),
+                name_cache.as_deref(),
+                optimization_trace.as_deref(),
+            )?;
+
+            let mut output = self.print(
                 &module,
                 Some(&fm.name.to_string()),
                 opts.output_path.clone().map(From::from),
@@ -906,14 +964,11 @@ impl Compiler {
                 Some(&comments),
                 opts.emit_source_map_columns,
                 &opts.format.preamble,
-                swc_ecma_codegen::Config::default()
-                    .with_target(target)
-                    .with_minify(true)
-                    .with_ascii_only(opts.format.ascii_only)
-                    .with_emit_assert_for_import_attributes(
-                        opts.format.emit_assert_for_import_attributes,
-                    ),
-            )
+                codegen_config,
+            )?;
+            output.output = extra;
+
+            Ok(output)
         })
     }
 
@@ -1003,6 +1058,14 @@ impl Compiler {
     }
 }
 
+static CUR_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    if cfg!(target_arch = "wasm32") {
+        PathBuf::new()
+    } else {
+        ::std::env::current_dir().unwrap()
+    }
+});
+
 fn find_swcrc(path: &Path, root: &Path, root_mode: RootMode) -> Option<PathBuf> {
     let mut parent = path.parent();
     while let Some(dir) = parent {
@@ -0,0 +1,32 @@
+function count(): number {
+    return 1;
+}
+
+function maybe(): Promise<number> | undefined {
+    return undefined;
+}
+
+declare function any(): any;
+
+class Counter {
+    value(): string {
+        return "";
+    }
+
+    async read(name: string, values: string[], later: Promise<string>) {
+        await 1;
+        await "literal";
+        await count();
+        await name;
+        await values;
+        await this.value();
+        await (() => {});
+
+        // Unknown or thenable
+        await later;
+        await maybe();
+        await any();
+        await unknown();
+        await Promise.resolve(1);
+    }
+}
@@ -0,0 +1,36 @@
+function simple(a) {
+    if (a) {
+        return 1;
+    }
+    return 2;
+}
+
+function complex(a, b) {
+    if (a && b) {
+        return 1;
+    }
+    return a ? 2 : 3;
+}
+
+const arrow = (a, b = 1) => a || b || 0;
+
+const obj = {
+    method(a) {
+        for (const x of a) {
+            if (x) continue;
+        }
+    },
+};
+
+function outer() {
+    return function (x) {
+        switch (x) {
+            case 1:
+                return 1;
+            case 2:
+                return 2;
+            default:
+                return 3;
+        }
+    };
+}
@@ -0,0 +1,25 @@
+function foo(a) {
+    if (a) {
+        for (const x of a) {
+            while (x) {
+                try {
+                } catch (e) {}
+            }
+        }
+    } else if (!a) {
+        if (a) {
+            if (a) {
+            }
+        }
+    } else {
+        switch (a) {
+        }
+    }
+
+    return () => {
+        if (a) {
+            if (a) {
+            }
+        }
+    };
+}
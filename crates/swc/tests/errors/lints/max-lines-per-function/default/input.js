@@ -0,0 +1,15 @@
+function ok() {
+
+    return 1;
+
+}
+
+function tooLong() {
+    let a = 1;
+    return a;
+}
+
+const arrow = () =>
+    1 +
+    2 +
+    3;
@@ -0,0 +1,16 @@
+function ok(a, b) {}
+
+function tooMany(a, b, c) {}
+
+const arrow = (a, b, c, d) => {};
+
+class Foo {
+    constructor(a, b, c) {}
+
+    method(a, b, c) {}
+}
+
+const obj = {
+    "quoted"(a, b, c) {},
+    prop: function (a, b, c) {},
+};
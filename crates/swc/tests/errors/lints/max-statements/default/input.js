@@ -0,0 +1,22 @@
+function ok() {
+    let a = 1;
+    a++;
+    return a;
+}
+
+function tooMany() {
+    let a = 1;
+    if (a) {
+        a++;
+        a++;
+    }
+    return a;
+}
+
+function outer() {
+    const inner = () => {
+        let a;
+        let b;
+    };
+    return inner;
+}
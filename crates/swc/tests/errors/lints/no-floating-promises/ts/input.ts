@@ -0,0 +1,55 @@
+async function load(): Promise<string> {
+    return "";
+}
+
+function fetchData(): Promise<number> {
+    return Promise.resolve(1);
+}
+
+function sync(): number {
+    return 1;
+}
+
+declare function external(): Promise<void>;
+
+const save = async () => {};
+
+export async function run(callback: () => Promise<void>) {
+    load();
+    fetchData().then(console.log);
+    fetchData().finally(() => {});
+    external();
+    save();
+    callback();
+    Promise.all([load()]);
+    new Promise((resolve) => resolve(1));
+    (async () => {})();
+    callback && load();
+    sync(), load();
+    callback ? load() : sync();
+
+    // Handled
+    await load();
+    void load();
+    load().catch(console.error);
+    fetchData().then(console.log, console.error);
+    const value = load();
+    sync();
+    unknown();
+    return load();
+}
+
+class Service {
+    async refresh(): Promise<void> {}
+
+    #reset = async () => {};
+
+    start() {
+        this.refresh();
+        this.#reset();
+
+        function inner(this: any) {
+            this.refresh();
+        }
+    }
+}
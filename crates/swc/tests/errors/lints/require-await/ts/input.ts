@@ -0,0 +1,39 @@
+async function noAwait() {
+    return 1;
+}
+
+async function withAwait() {
+    await noAwait();
+}
+
+async function withForAwait(values: AsyncIterable<number>) {
+    for await (const value of values) {
+    }
+}
+
+async function nested() {
+    return async () => {
+        await noAwait();
+    };
+}
+
+async function* generator() {
+    yield 1;
+}
+
+const arrow = async () => 1;
+
+const obj = {
+    async method() {},
+    async "quoted"() {
+        await noAwait();
+    },
+};
+
+class Foo {
+    async method() {}
+
+    async #private() {}
+}
+
+declare function declared(): Promise<void>;
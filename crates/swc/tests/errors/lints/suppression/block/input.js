@@ -0,0 +1,14 @@
+/* swc-lint-disable no-console */
+console.log(1);
+debugger;
+/* swc-lint-enable no-console */
+console.log(2);
+
+/* swc-lint-disable */
+console.log(3);
+debugger;
+/* swc-lint-enable */
+console.log(4);
+
+/* swc-lint-disable no-debugger */
+debugger;
@@ -0,0 +1,8 @@
+/* swc-lint-disable no-console */
+
+export function log(message) {
+    console.log(message);
+    debugger;
+}
+
+console.info("done");
@@ -0,0 +1,14 @@
+// swc-lint-disable-next-line no-console
+console.log("suppressed");
+console.log("reported");
+
+// swc-lint-disable-next-line no-debugger -- only `no-debugger` is suppressed
+console.log(1); debugger;
+
+// swc-lint-disable-next-line
+console.log(2); debugger;
+
+function foo() {
+    /* swc-lint-disable-next-line no-console, no-debugger */
+    console.log(3); debugger;
+}
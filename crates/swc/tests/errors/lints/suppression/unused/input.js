@@ -0,0 +1,14 @@
+// swc-lint-disable-next-line no-console
+console.log(1);
+
+// swc-lint-disable-next-line no-console
+const a = 1;
+
+// swc-lint-disable-next-line no-console, no-debugger
+console.log(2);
+
+/* swc-lint-disable */
+/* swc-lint-enable */
+
+// swc-lint-disable-next-line
+debugger;
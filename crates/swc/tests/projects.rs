@@ -3,7 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::Context;
+use anyhow::{Context, Error};
 use rayon::prelude::*;
 use swc::{
     config::{
@@ -12,6 +12,7 @@ use swc::{
     },
     try_with_handler, BoolOrDataConfig, Compiler, TransformOutput,
 };
+use swc_atoms::JsWord;
 use swc_common::{
     chain,
     comments::{Comment, SingleThreadedComments},
@@ -20,7 +21,7 @@ use swc_common::{
     BytePos, FileName, Globals, SourceMap, GLOBALS,
 };
 use swc_ecma_ast::{EsVersion, *};
-use swc_ecma_minifier::option::MangleOptions;
+use swc_ecma_minifier::option::{MangleOptions, NameCache};
 use swc_ecma_parser::{EsConfig, Syntax, TsConfig};
 use swc_ecma_transforms::{
     helpers::{self, Helpers},
@@ -1119,6 +1120,126 @@ fn issue_7513_2() {
     );
 }
 
+fn minify_with_size(opts: JsMinifyOptions) -> Result<TransformOutput, Error> {
+    static INPUT: &str = "export function large(items) {
+    return items.map((item) => item.name + ': ' + item.value).join(', ');
+}
+export const small = 1;";
+
+    let cm = Lrc::<SourceMap>::default();
+    let c = swc::Compiler::new(cm.clone());
+    GLOBALS.set(&Default::default(), || {
+        try_with_handler(cm.clone(), Default::default(), |handler| {
+            let fm = cm.new_source_file(FileName::Real("input.js".into()), INPUT.to_string());
+
+            c.minify(
+                fm,
+                handler,
+                &JsMinifyOptions {
+                    module: true,
+                    compress: BoolOrDataConfig::from_bool(true),
+                    mangle: BoolOrDataConfig::from_bool(true),
+                    ..opts
+                },
+            )
+        })
+    })
+}
+
+#[cfg(feature = "size_report")]
+#[test]
+fn minify_size_report() {
+    let output = minify_with_size(JsMinifyOptions {
+        report_size: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(output.map, None);
+
+    let output: serde_json::Value =
+        serde_json::from_str(output.output.as_deref().unwrap()).unwrap();
+    let size = &output["size"];
+    assert!(size["raw"].as_u64().unwrap() > 0);
+    assert!(size["gzip"].as_u64().unwrap() > 0);
+    assert!(size["brotli"].as_u64().unwrap() > 0);
+
+    let decls = size["declarations"].as_array().unwrap();
+    assert_eq!(decls[0]["name"], "large");
+    assert_eq!(decls[1]["name"], "small");
+    assert!(decls[0]["size"].as_u64() > decls[1]["size"].as_u64());
+}
+
+#[cfg(feature = "size_report")]
+#[test]
+fn minify_size_report_with_source_map() {
+    let output = minify_with_size(JsMinifyOptions {
+        report_size: true,
+        source_map: BoolOrDataConfig::from_bool(true),
+        emit_source_map_columns: false,
+        ..Default::default()
+    })
+    .unwrap();
+
+    // The source map is the one requested by the user.
+    let map = sourcemap::SourceMap::from_slice(output.map.as_deref().unwrap().as_bytes()).unwrap();
+    assert!(map.tokens().all(|token| token.get_dst_col() == 0));
+
+    let report: serde_json::Value =
+        serde_json::from_str(output.output.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        report["size"]["raw"].as_u64().unwrap(),
+        output.code.len() as u64
+    );
+    assert_eq!(report["size"]["declarations"].as_array().unwrap().len(), 2);
+}
+
+#[cfg(feature = "size_report")]
+#[test]
+fn minify_size_budget() {
+    use swc_ecma_minifier::size::SizeBudget;
+
+    let err = minify_with_size(JsMinifyOptions {
+        size_budget: Some(SizeBudget {
+            gzip: Some(10),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+    .unwrap_err();
+
+    assert!(
+        format!("{:?}", err).contains("gzip size of the output"),
+        "{:?}",
+        err
+    );
+
+    minify_with_size(JsMinifyOptions {
+        size_budget: Some(SizeBudget {
+            raw: Some(1000),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+    .unwrap();
+}
+
+#[cfg(not(feature = "size_report"))]
+#[test]
+fn minify_size_report_without_feature() {
+    let err = minify_with_size(JsMinifyOptions {
+        report_size: true,
+        ..Default::default()
+    })
+    .unwrap_err();
+
+    assert!(
+        format!("{:?}", err).contains("require the `size_report` feature"),
+        "{:?}",
+        err
+    );
+}
+
 #[testing::fixture("tests/minify/**/input.js")]
 fn minify(input_js: PathBuf) {
     let input_dir = input_js.parent().unwrap();
@@ -1151,3 +1272,50 @@ fn minify(input_js: PathBuf) {
     })
     .unwrap()
 }
+
+#[test]
+fn minify_name_cache() {
+    fn minify(src: &str, name_cache: NameCache) -> (String, NameCache) {
+        let cm = Lrc::<SourceMap>::default();
+        let c = swc::Compiler::new(cm.clone());
+        let output = GLOBALS
+            .set(&Default::default(), || {
+                try_with_handler(cm.clone(), Default::default(), |handler| {
+                    let fm = cm.new_source_file(FileName::Anon, src.to_string());
+
+                    c.minify(
+                        fm,
+                        handler,
+                        &JsMinifyOptions {
+                            compress: BoolOrDataConfig::from_bool(false),
+                            mangle: BoolOrDataConfig::from_obj(MangleOptions {
+                                top_level: Some(true),
+                                ..Default::default()
+                            }),
+                            name_cache: Some(name_cache),
+                            ..Default::default()
+                        },
+                    )
+                })
+            })
+            .unwrap();
+
+        let extra: serde_json::Value = serde_json::from_str(&output.output.unwrap()).unwrap();
+
+        (
+            output.code,
+            serde_json::from_value(extra["nameCache"].clone()).unwrap(),
+        )
+    }
+
+    let (first, cache) = minify("var config = 1; console.log(config);", Default::default());
+    let config = cache.vars[&JsWord::from("config")].clone();
+    assert_eq!(first, format!("var {c}=1;console.log({c});", c = config));
+
+    // `config` is a global declared by the first file.
+    let (second, cache) = minify("var local = config;", cache);
+    assert_eq!(
+        second,
+        format!("var {}={};", cache.vars[&JsWord::from("local")], config)
+    );
+}
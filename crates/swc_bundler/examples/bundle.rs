@@ -85,7 +85,11 @@ fn do_test(_entry: &Path, entries: HashMap<String, FileName>, inline: bool, mini
                 disable_fixer: minify,
                 disable_hygiene: minify,
                 disable_dce: false,
+                tree_shaking_report: false,
                 module: Default::default(),
+                code_splitting: false,
+                entry_file_names: None,
+                chunk_file_names: None,
             },
             Box::new(Hook),
         );
@@ -132,6 +136,8 @@ fn do_test(_entry: &Path, entries: HashMap<String, FileName>, inline: bool, mini
                             &ExtraOptions {
                                 unresolved_mark: Mark::new(),
                                 top_level_mark: Mark::new(),
+                                mangle_name_cache: None,
+                                optimization_trace: None,
                             },
                         )
                         .expect_module();
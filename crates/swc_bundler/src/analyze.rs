@@ -0,0 +1,391 @@
+//! Attributes bytes of emitted bundles to the modules they come from.
+
+use std::{collections::BTreeMap, fmt::Write as _, io::Write};
+
+use anyhow::{Context, Error};
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+use swc_common::{sync::Lrc, BytePos, FileName, LineCol, SourceFile, SourceMap};
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+
+use crate::Bundle;
+
+/// Sizes of a bundle and the modules merged into it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleAnalysis {
+    /// [Bundle::file_name]
+    pub file_name: String,
+
+    pub size: Size,
+
+    /// Sorted by raw size, in descending order.
+    pub modules: Vec<ModuleSize>,
+
+    /// Sorted by raw size, in descending order.
+    pub packages: Vec<PackageSize>,
+
+    /// Code outside of the tokens of modules, like code injected by the
+    /// bundler.
+    pub unattributed: Size,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Size {
+    pub raw: usize,
+    /// Modules are compressed together, so the gzip size of the bundle is
+    /// distributed to modules and packages by their raw sizes.
+    pub gzip: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleSize {
+    pub file_name: String,
+
+    /// Name of the package in `node_modules` which contains the module.
+    pub package: Option<String>,
+
+    pub size: Size,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageSize {
+    /// [None] for modules not in `node_modules`.
+    pub name: Option<String>,
+
+    pub size: Size,
+}
+
+/// Emits `bundle` and attributes each byte of the output to the module it
+/// comes from, using the source map of the output.
+///
+/// Bytes between two mappings of the same module belong to the module. Other
+/// bytes, like code injected by the bundler between modules, are
+/// unattributed.
+pub fn analyze_bundle(
+    cm: Lrc<SourceMap>,
+    bundle: &Bundle,
+    cfg: swc_ecma_codegen::Config,
+) -> Result<BundleAnalysis, Error> {
+    let mut code = vec![];
+    let mut srcmap = vec![];
+
+    {
+        let mut emitter = Emitter {
+            cfg,
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut code,
+                Some(&mut srcmap),
+            )),
+        };
+
+        emitter
+            .emit_module(&bundle.module)
+            .context("failed to emit module to analyze it")?;
+    }
+
+    let code = String::from_utf8(code).context("emitted code is not utf-8")?;
+    let files = cm.files().clone();
+
+    let mut mappings = {
+        let line_starts = line_starts(&code);
+
+        srcmap
+            .into_iter()
+            .filter_map(|(pos, loc)| {
+                let offset = byte_offset(&code, &line_starts, loc)?;
+                Some((offset, lookup_source_file(&files, pos)))
+            })
+            .collect::<Vec<_>>()
+    };
+    mappings.sort_by_key(|(offset, _)| *offset);
+
+    // Sizes of each module, keyed by the start position of the source file.
+    let mut modules = BTreeMap::<BytePos, (Lrc<SourceFile>, usize)>::new();
+    let mut unattributed = code.len();
+
+    for window in mappings.windows(2) {
+        let ((start, start_fm), (end, end_fm)) = (&window[0], &window[1]);
+        let fm = match (start_fm, end_fm) {
+            (Some(start_fm), Some(end_fm)) if start_fm.start_pos == end_fm.start_pos => start_fm,
+            _ => continue,
+        };
+
+        modules
+            .entry(fm.start_pos)
+            .or_insert_with(|| (fm.clone(), 0))
+            .1 += end - start;
+        unattributed -= end - start;
+    }
+
+    let size = size_of(&code)?;
+    let distribute = |raw: usize| Size {
+        raw,
+        gzip: if size.raw == 0 {
+            0
+        } else {
+            (size.gzip as f64 * raw as f64 / size.raw as f64).round() as usize
+        },
+    };
+
+    let mut packages = BTreeMap::<Option<String>, usize>::new();
+    let mut modules = modules
+        .into_values()
+        .map(|(fm, raw)| {
+            let package = package_name(&fm.name);
+            *packages.entry(package.clone()).or_default() += raw;
+
+            ModuleSize {
+                file_name: fm.name.to_string(),
+                package,
+                size: distribute(raw),
+            }
+        })
+        .collect::<Vec<_>>();
+    modules.sort_by(|a, b| b.size.raw.cmp(&a.size.raw));
+
+    let mut packages = packages
+        .into_iter()
+        .map(|(name, raw)| PackageSize {
+            name,
+            size: distribute(raw),
+        })
+        .collect::<Vec<_>>();
+    packages.sort_by(|a, b| b.size.raw.cmp(&a.size.raw));
+
+    Ok(BundleAnalysis {
+        file_name: bundle.file_name.clone(),
+        size,
+        modules,
+        packages,
+        unattributed: distribute(unattributed),
+    })
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Bundle analysis</title>
+<style>
+body { font-family: sans-serif; margin: 16px; }
+.treemap { display: flex; height: 480px; margin-bottom: 24px; }
+.package { display: flex; flex-direction: column; border: 1px solid #fff; background: #4a90d9; overflow: hidden; }
+.package > .label { color: #fff; font-weight: bold; padding: 2px 4px; }
+.module { border: 1px solid #4a90d9; background: #a9cbf0; overflow: hidden; font-size: 12px; padding: 2px 4px; }
+table { border-collapse: collapse; }
+td, th { padding: 2px 8px; text-align: right; }
+td:first-child, th:first-child { text-align: left; }
+</style>
+</head>
+<body>
+"#;
+
+/// Renders a static html page which shows the bundles as treemaps.
+pub fn render_analysis_html(bundles: &[BundleAnalysis]) -> String {
+    let mut html = String::new();
+
+    html.push_str(HTML_HEAD);
+
+    for bundle in bundles {
+        let _ = writeln!(
+            html,
+            "<h2>{} ({})</h2>",
+            escape(&bundle.file_name),
+            format_size(bundle.size)
+        );
+
+        html.push_str("<div class=\"treemap\">\n");
+        for package in &bundle.packages {
+            let name = package_label(&package.name);
+
+            let _ = writeln!(
+                html,
+                "<div class=\"package\" style=\"flex-grow: {}\" title=\"{} ({})\">\n<div \
+                 class=\"label\">{}</div>",
+                package.size.raw,
+                escape(name),
+                format_size(package.size),
+                escape(name)
+            );
+            for module in bundle
+                .modules
+                .iter()
+                .filter(|module| module.package == package.name)
+            {
+                let _ = writeln!(
+                    html,
+                    "<div class=\"module\" style=\"flex-grow: {}\" title=\"{} ({})\">{}</div>",
+                    module.size.raw,
+                    escape(&module.file_name),
+                    format_size(module.size),
+                    escape(short_name(&module.file_name))
+                );
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</div>\n");
+
+        html.push_str("<table>\n<tr><th>Module</th><th>Raw</th><th>Gzip</th></tr>\n");
+        for module in &bundle.modules {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&module.file_name),
+                module.size.raw,
+                module.size.gzip
+            );
+        }
+        let _ = writeln!(
+            html,
+            "<tr><td>(bundler)</td><td>{}</td><td>{}</td></tr>",
+            bundle.unattributed.raw, bundle.unattributed.gzip
+        );
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn lookup_source_file(files: &[Lrc<SourceFile>], pos: BytePos) -> Option<Lrc<SourceFile>> {
+    if pos.is_dummy() {
+        return None;
+    }
+
+    let idx = files.partition_point(|fm| fm.start_pos <= pos);
+    let fm = files.get(idx.checked_sub(1)?)?;
+
+    if pos <= fm.end_pos {
+        Some(fm.clone())
+    } else {
+        None
+    }
+}
+
+fn line_starts(code: &str) -> Vec<usize> {
+    Some(0)
+        .into_iter()
+        .chain(code.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect()
+}
+
+/// Converts a position of a source map, which uses utf-16 columns, to a byte
+/// offset of `code`.
+fn byte_offset(code: &str, line_starts: &[usize], loc: LineCol) -> Option<usize> {
+    let start = *line_starts.get(loc.line as usize)?;
+    let line = code[start..].split('\n').next().unwrap_or_default();
+
+    let mut col = 0;
+    for (idx, c) in line.char_indices() {
+        if col >= loc.col as usize {
+            return Some(start + idx);
+        }
+        col += c.len_utf16();
+    }
+
+    Some(start + line.len())
+}
+
+fn size_of(code: &str) -> Result<Size, Error> {
+    if code.is_empty() {
+        return Ok(Size::default());
+    }
+
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(code.as_bytes())?;
+    let gzip = encoder.finish()?.len();
+
+    Ok(Size {
+        raw: code.len(),
+        gzip,
+    })
+}
+
+/// Returns the name of the package in the last `node_modules` of the path.
+fn package_name(file_name: &FileName) -> Option<String> {
+    let path = match file_name {
+        FileName::Real(path) => path,
+        _ => return None,
+    };
+
+    let components = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>();
+    let idx = components.iter().rposition(|c| c == "node_modules")?;
+
+    let name = components.get(idx + 1)?;
+    if name.starts_with('@') {
+        Some(format!("{}/{}", name, components.get(idx + 2)?))
+    } else {
+        Some(name.to_string())
+    }
+}
+
+fn package_label(name: &Option<String>) -> &str {
+    name.as_deref().unwrap_or("(own code)")
+}
+
+fn short_name(file_name: &str) -> &str {
+    file_name
+        .rsplit(|c| c == '/' || c == '\\')
+        .next()
+        .unwrap_or(file_name)
+}
+
+fn format_size(size: Size) -> String {
+    format!("{} B, {} B gzipped", size.raw, size.gzip)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn package_names() {
+        assert_eq!(
+            package_name(&FileName::Real(PathBuf::from(
+                "/app/node_modules/react/index.js"
+            ))),
+            Some("react".into())
+        );
+        assert_eq!(
+            package_name(&FileName::Real(PathBuf::from(
+                "/app/node_modules/@scope/pkg/node_modules/@babel/runtime/helpers/a.js"
+            ))),
+            Some("@babel/runtime".into())
+        );
+        assert_eq!(
+            package_name(&FileName::Real(PathBuf::from("/app/src/index.js"))),
+            None
+        );
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let code = "a\nconst s = '가나';\nb";
+        let line_starts = line_starts(code);
+
+        let offset = byte_offset(code, &line_starts, LineCol { line: 1, col: 14 }).unwrap();
+        assert_eq!(&code[offset..], ";\nb");
+
+        let offset = byte_offset(code, &line_starts, LineCol { line: 2, col: 0 }).unwrap();
+        assert_eq!(&code[offset..], "b");
+    }
+}
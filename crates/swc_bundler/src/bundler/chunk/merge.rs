@@ -20,7 +20,7 @@ use crate::{
     },
     dep_graph::ModuleGraph,
     id::{Id, ModuleId},
-    inline::inline,
+    inline::{inline, InlineData},
     load::Load,
     modules::Modules,
     resolve::Resolve,
@@ -58,6 +58,24 @@ impl Ctx {
     }
 }
 
+/// Identifiers renamed by [Bundler::finalize_merging_of_entry].
+pub(super) struct MergedNames {
+    inlined: InlineData,
+    keywords: KeywordRenamer,
+}
+
+impl MergedNames {
+    /// Returns the identifier which refers to `id` after merging.
+    pub fn resolve(&self, id: &Id) -> Ident {
+        let id = self.inlined.resolve(id);
+
+        match self.keywords.get(&id) {
+            Some(renamed) => renamed.clone(),
+            None => id.into_ident(),
+        }
+    }
+}
+
 impl<L, R> Bundler<'_, L, R>
 where
     L: Load,
@@ -421,7 +439,12 @@ where
         }
     }
 
-    fn finalize_merging_of_entry(&self, ctx: &Ctx, id: ModuleId, entry: &mut Modules) {
+    pub(super) fn finalize_merging_of_entry(
+        &self,
+        ctx: &Ctx,
+        id: ModuleId,
+        entry: &mut Modules,
+    ) -> MergedNames {
         tracing::trace!("All modules are merged");
 
         tracing::debug!("Injecting reexports");
@@ -431,7 +454,7 @@ where
 
         tracing::debug!("Inlining injected variables");
 
-        inline(self.injected_ctxt, entry);
+        let inlined = inline(self.injected_ctxt, entry);
 
         entry.sort(id, &ctx.graph, &ctx.cycles, &self.cm);
 
@@ -478,7 +501,8 @@ where
 
         tracing::debug!("Renaming keywords");
 
-        entry.visit_mut_with(&mut KeywordRenamer::default());
+        let mut keywords = KeywordRenamer::default();
+        entry.visit_mut_with(&mut keywords);
 
         // print_hygiene(
         //     "done-clean",
@@ -488,10 +512,17 @@ where
         //         .fold_with(&mut hygiene())
         //         .fold_with(&mut fixer(None)),
         // );
+
+        MergedNames { inlined, keywords }
     }
 
     /// Remove exports with wrong syntax context
-    fn remove_wrong_exports(&self, ctx: &Ctx, info: &TransformedModule, module: &mut Modules) {
+    pub(super) fn remove_wrong_exports(
+        &self,
+        ctx: &Ctx,
+        info: &TransformedModule,
+        module: &mut Modules,
+    ) {
         tracing::debug!("Removing wrong exports");
 
         let item_count = module.iter().count();
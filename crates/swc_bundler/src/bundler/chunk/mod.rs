@@ -5,6 +5,7 @@ use anyhow::{Context, Error};
 use rayon::iter::ParallelIterator;
 use swc_common::collections::AHashMap;
 
+pub(super) use self::plan::chunk_file_name;
 use super::{load::TransformedModule, Bundler};
 use crate::{
     bundler::chunk::merge::Ctx, load::Load, resolve::Resolve, util::IntoParallelIterator, Bundle,
@@ -14,6 +15,7 @@ mod cjs;
 mod computed_key;
 mod merge;
 mod plan;
+mod split;
 
 #[derive(Debug)]
 struct InternalEntry {}
@@ -73,7 +75,8 @@ where
             .map(|id| -> Result<_, Error> {
                 self.run(|| {
                     // TODO: is_entry should be false if it's dep of other entry.
-                    let is_entry = plan.entries.contains_key(id);
+                    let is_entry =
+                        plan.entries.contains_key(id) && !self.is_wrapped_chunk_root(*id);
                     let module = self.get_for_merging(&ctx, *id, is_entry)?;
 
                     Ok((*id, module))
@@ -86,6 +89,10 @@ where
         #[cfg(not(target_arch = "wasm32"))]
         tracing::debug!("Module preparation took {:?}", dur);
 
+        if !plan.chunks.is_empty() {
+            return Ok(self.link_chunks(&ctx, &plan.chunks, all));
+        }
+
         let entries = all
             .iter()
             .filter_map(|(id, module)| {
@@ -115,9 +122,13 @@ where
                         })
                         .clone();
                     Bundle {
+                        file_name: kind.name().to_string(),
                         kind,
                         id,
                         module: module.into(),
+                        tree_shaking: None,
+                        imports: Default::default(),
+                        dynamic_imports: Default::default(),
                     }
                 })
                 .collect()
@@ -141,9 +152,13 @@ where
                         })
                         .clone();
                     Bundle {
+                        file_name: kind.name().to_string(),
                         kind,
                         id,
                         module: module.into(),
+                        tree_shaking: None,
+                        imports: Default::default(),
+                        dynamic_imports: Default::default(),
                     }
                 })
                 .collect()
@@ -1,5 +1,15 @@
+use std::{
+    collections::{hash_map::Entry, VecDeque},
+    path::Path,
+};
+
 use anyhow::{bail, Error};
-use swc_common::collections::AHashMap;
+use indexmap::{IndexMap, IndexSet};
+use petgraph::EdgeDirection::Outgoing;
+use swc_common::{
+    collections::{AHashMap, AHashSet, ARandomState},
+    FileName,
+};
 use swc_graph_analyzer::{DepGraph, GraphAnalyzer};
 
 use crate::{
@@ -22,6 +32,26 @@ pub(super) struct Plan {
 
     /// Id of all modules.
     pub all: Vec<ModuleId>,
+
+    /// Output chunks. This is empty if code splitting is disabled.
+    pub chunks: Vec<ChunkPlan>,
+}
+
+/// A bundle created by code splitting.
+#[derive(Debug)]
+pub(super) struct ChunkPlan {
+    pub kind: BundleKind,
+
+    /// Name of the file the chunk is emitted as, which is used by other chunks
+    /// to import it.
+    pub name: String,
+
+    /// The entry of the chunk. For a common chunk, this is the first module of
+    /// the chunk.
+    pub root: ModuleId,
+
+    /// All modules of the chunk, including `root`.
+    pub modules: IndexSet<ModuleId, ARandomState>,
 }
 
 impl DepGraph for Scope {
@@ -51,24 +81,205 @@ where
         let mut builder = PlanBuilder::default();
         let mut analyzer = GraphAnalyzer::new(&self.scope);
 
+        // Sorted to make names of chunks deterministic.
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut roots = vec![];
+
         for (name, module) in entries {
             if let Some(v) = builder.kinds.insert(module.id, BundleKind::Named { name }) {
                 bail!("Multiple entries with same input path detected: {:?}", v)
             }
 
             analyzer.load(module.id);
+            roots.push(module.id);
         }
+
+        if self.config.code_splitting {
+            self.add_dynamic_entries(&mut builder, &mut analyzer, &mut roots);
+        }
+
         let res = analyzer.into_result();
 
         // dbg!(&builder.cycles);
 
+        let chunks = if self.config.code_splitting {
+            self.assign_chunks(&builder, &roots, &res.graph)
+        } else {
+            Default::default()
+        };
+
         Ok((
             Plan {
                 entries: builder.kinds,
                 all: res.all,
+                chunks,
             },
             res.graph,
             res.cycles,
         ))
     }
+
+    /// Makes targets of `import()` entries of the plan.
+    fn add_dynamic_entries(
+        &self,
+        builder: &mut PlanBuilder,
+        analyzer: &mut GraphAnalyzer<&Scope>,
+        roots: &mut Vec<ModuleId>,
+    ) {
+        let mut names = builder
+            .kinds
+            .values()
+            .filter_map(|kind| match kind {
+                BundleKind::Named { name } => Some(chunk_file_name(name)),
+                _ => None,
+            })
+            .collect::<AHashSet<_>>();
+
+        let mut queue = roots.iter().copied().collect::<VecDeque<_>>();
+        let mut visited = AHashSet::default();
+
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            let m = self.scope.get_module(id).expect("failed to get module");
+
+            queue.extend(
+                m.imports
+                    .specifiers
+                    .iter()
+                    .chain(m.exports.reexports.iter())
+                    .map(|v| v.0.module_id),
+            );
+
+            for src in &m.imports.dynamic {
+                if let Entry::Vacant(e) = builder.kinds.entry(src.module_id) {
+                    let target = self
+                        .scope
+                        .get_module(src.module_id)
+                        .expect("failed to get module");
+                    let name = unique_name(&mut names, &target.fm.name);
+
+                    e.insert(BundleKind::Dynamic { name });
+                    analyzer.load(src.module_id);
+                    roots.push(src.module_id);
+                }
+
+                queue.push_back(src.module_id);
+            }
+        }
+    }
+
+    /// Assigns each module to exactly one chunk.
+    ///
+    /// A module which is statically reachable from only one entry is placed
+    /// in the chunk of the entry. Modules reachable from multiple entries are
+    /// grouped by the set of the entries, and each group becomes a common
+    /// chunk.
+    fn assign_chunks(
+        &self,
+        builder: &PlanBuilder,
+        roots: &[ModuleId],
+        graph: &ModuleGraph,
+    ) -> Vec<ChunkPlan> {
+        let mut chunks = roots
+            .iter()
+            .map(|&root| {
+                let kind = builder.kinds[&root].clone();
+                let name = match &kind {
+                    BundleKind::Named { name } => chunk_file_name(name),
+                    BundleKind::Dynamic { name } | BundleKind::Lib { name } => name.clone(),
+                };
+
+                ChunkPlan {
+                    kind,
+                    name,
+                    root,
+                    modules: IndexSet::from_iter([root]),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut names = chunks
+            .iter()
+            .map(|chunk| chunk.name.clone())
+            .collect::<AHashSet<_>>();
+
+        // Entries which can load a module synchronously.
+        let mut owners = IndexMap::<ModuleId, Vec<usize>, ARandomState>::default();
+        for (idx, &root) in roots.iter().enumerate() {
+            let mut stack = vec![root];
+            let mut visited = AHashSet::default();
+
+            while let Some(id) = stack.pop() {
+                if !visited.insert(id) {
+                    continue;
+                }
+                owners.entry(id).or_default().push(idx);
+                stack.extend(graph.neighbors_directed(id, Outgoing));
+            }
+        }
+
+        let mut common_chunks = AHashMap::<Vec<usize>, usize>::default();
+        for (id, owners) in owners {
+            if builder.kinds.contains_key(&id) {
+                continue;
+            }
+
+            let idx = if let [owner] = &*owners {
+                *owner
+            } else {
+                *common_chunks.entry(owners).or_insert_with(|| {
+                    let module = self.scope.get_module(id).expect("failed to get module");
+                    let name = unique_name(&mut names, &module.fm.name);
+
+                    chunks.push(ChunkPlan {
+                        kind: BundleKind::Lib { name: name.clone() },
+                        name,
+                        root: id,
+                        modules: Default::default(),
+                    });
+                    chunks.len() - 1
+                })
+            };
+
+            chunks[idx].modules.insert(id);
+        }
+
+        chunks
+    }
+}
+
+/// Name of the chunk of a user-provided entry.
+pub(crate) fn chunk_file_name(entry_name: &str) -> String {
+    let stem = Path::new(entry_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| entry_name.to_string());
+
+    format!("{}.js", stem)
+}
+
+/// Creates a name for a chunk of `file`, which is not in `names`.
+fn unique_name(names: &mut AHashSet<String>, file: &FileName) -> String {
+    let stem = match file {
+        FileName::Real(path) => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string()),
+        _ => None,
+    }
+    .unwrap_or_else(|| "chunk".into());
+
+    let mut name = format!("{}.js", stem);
+    let mut i = 1;
+    while names.contains(&name) {
+        name = format!("{}{}.js", stem, i);
+        i += 1;
+    }
+
+    names.insert(name.clone());
+    name
 }
@@ -0,0 +1,447 @@
+use std::{mem::replace, sync::atomic::Ordering};
+
+use indexmap::IndexSet;
+use swc_atoms::JsWord;
+use swc_common::{
+    collections::{AHashMap, AHashSet, ARandomState},
+    SyntaxContext, DUMMY_SP,
+};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{prepend_stmt, quote_ident, ExprFactory};
+use swc_ecma_visit::{
+    noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+use super::{merge::Ctx, plan::ChunkPlan};
+use crate::{id::Id, modules::Modules, Bundle, BundleKind, Bundler, Load, ModuleId, Resolve};
+
+/// A chunk which is being linked.
+struct Linking {
+    module: Modules,
+
+    /// Identifiers declared by other chunks, with the index of the chunk.
+    imports: IndexSet<(Id, usize), ARandomState>,
+
+    /// Identifiers used by other chunks.
+    exports: IndexSet<Id, ARandomState>,
+
+    /// Identifiers of `imports` and `exports` after merging.
+    resolved: AHashMap<Id, Ident>,
+
+    /// Chunks loaded by `import()`.
+    dynamic_imports: IndexSet<usize, ARandomState>,
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Merges modules of each chunk and links the chunks using
+    /// `import`/`export`.
+    ///
+    /// Merging renames identifiers (e.g. while inlining injected variables),
+    /// so identifiers shared between chunks are resolved using the names
+    /// returned by [Bundler::finalize_merging_of_entry].
+    pub(super) fn link_chunks(
+        &self,
+        ctx: &Ctx,
+        chunks: &[ChunkPlan],
+        all: AHashMap<ModuleId, Modules>,
+    ) -> Vec<Bundle> {
+        self.run(|| self.link_chunks_inner(ctx, chunks, all))
+    }
+
+    /// Returns true if `id` is a common js module loaded by `import()`.
+    ///
+    /// Such a module is wrapped like a dependency, and the chunk exports
+    /// `module.exports` as `default`, just like `import cjs from './cjs'`.
+    pub(super) fn is_wrapped_chunk_root(&self, id: ModuleId) -> bool {
+        if !self.config.code_splitting || !self.config.require {
+            return false;
+        }
+
+        let info = self.scope.get_module(id).unwrap();
+        self.scope.is_cjs(id) || !info.is_es6
+    }
+
+    fn link_chunks_inner(
+        &self,
+        ctx: &Ctx,
+        chunks: &[ChunkPlan],
+        mut all: AHashMap<ModuleId, Modules>,
+    ) -> Vec<Bundle> {
+        let mut chunk_of_module = AHashMap::default();
+        let mut chunk_of_ctxt = AHashMap::default();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            for &id in &chunk.modules {
+                let info = self.scope.get_module(id).unwrap();
+                chunk_of_module.insert(id, idx);
+                chunk_of_ctxt.insert(info.local_ctxt(), idx);
+                chunk_of_ctxt.insert(info.export_ctxt(), idx);
+            }
+        }
+
+        let mut linking = chunks
+            .iter()
+            .enumerate()
+            .map(|(idx, chunk)| {
+                let mut module = Modules::empty(self.injected_ctxt);
+                let mut imports = IndexSet::default();
+                let mut dynamic_imports = IndexSet::default();
+
+                for &id in &chunk.modules {
+                    let mut dep = all
+                        .remove(&id)
+                        .unwrap_or_else(|| unreachable!("module {} does not exist in the map", id));
+                    let info = self.scope.get_module(id).unwrap();
+
+                    dynamic_imports.extend(
+                        info.imports
+                            .dynamic
+                            .iter()
+                            .map(|src| chunk_of_module[&src.module_id]),
+                    );
+
+                    dep.visit_mut_with(&mut DynamicImportRewriter {
+                        synthesized_ctxt: self.synthesized_ctxt,
+                        targets: info
+                            .imports
+                            .dynamic
+                            .iter()
+                            .map(|src| {
+                                let name = &chunks[chunk_of_module[&src.module_id]].name;
+                                (src.src.value.clone(), format!("./{}", name))
+                            })
+                            .collect(),
+                    });
+
+                    dep.visit_with(&mut ForeignRefFinder {
+                        chunk: idx,
+                        chunk_of_ctxt: &chunk_of_ctxt,
+                        refs: &mut imports,
+                    });
+
+                    module.add_dep(dep);
+                }
+
+                Linking {
+                    module,
+                    imports,
+                    exports: Default::default(),
+                    resolved: Default::default(),
+                    dynamic_imports,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for idx in 0..linking.len() {
+            let imports = linking[idx].imports.clone();
+            for (id, owner) in imports {
+                linking[owner].exports.insert(id);
+            }
+        }
+
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let linking = &mut linking[idx];
+            let root_info = self.scope.get_module(chunk.root).unwrap();
+
+            for &id in &chunk.modules {
+                let info = self.scope.get_module(id).unwrap();
+                root_info.helpers.extend(&info.helpers);
+                root_info.swc_helpers.extend_from(&info.swc_helpers);
+            }
+
+            if matches!(chunk.kind, BundleKind::Dynamic { .. })
+                && self.is_wrapped_chunk_root(chunk.root)
+            {
+                root_info.helpers.require.store(true, Ordering::SeqCst);
+                linking.module.append(
+                    chunk.root,
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span: DUMMY_SP,
+                        expr: Box::new(
+                            CallExpr {
+                                span: DUMMY_SP,
+                                callee: Ident::new(
+                                    "load".into(),
+                                    DUMMY_SP.with_ctxt(root_info.export_ctxt()),
+                                )
+                                .as_callee(),
+                                args: Default::default(),
+                                type_args: Default::default(),
+                            }
+                            .make_member(quote_ident!("default")),
+                        ),
+                    })),
+                );
+            }
+
+            self.replace_import_specifiers(&root_info, &mut linking.module);
+            let names = self.finalize_merging_of_entry(ctx, chunk.root, &mut linking.module);
+            self.remove_wrong_exports(ctx, &root_info, &mut linking.module);
+
+            linking.resolved = linking
+                .imports
+                .iter()
+                .map(|(id, _)| id)
+                .chain(linking.exports.iter())
+                .map(|id| (id.clone(), names.resolve(id)))
+                .collect();
+
+            // A common chunk exports only what other chunks use.
+            if matches!(chunk.kind, BundleKind::Lib { .. }) {
+                linking.module.retain_mut(|_, item| {
+                    !matches!(
+                        item,
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                            src: None,
+                            ..
+                        }))
+                    )
+                });
+            }
+        }
+
+        let mut modules = linking
+            .iter_mut()
+            .map(|linking| {
+                Module::from(replace(
+                    &mut linking.module,
+                    Modules::empty(self.injected_ctxt),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        // Names exported by each chunk for other chunks.
+        let mut export_names = vec![AHashMap::<Id, JsWord>::default(); chunks.len()];
+        for (idx, linking) in linking.iter().enumerate() {
+            let module = &mut modules[idx];
+            let mut used_names = AHashSet::default();
+            let mut existing = AHashMap::default();
+
+            for item in &module.body {
+                if let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    specifiers,
+                    src: None,
+                    ..
+                })) = item
+                {
+                    for s in specifiers {
+                        if let ExportSpecifier::Named(ExportNamedSpecifier {
+                            orig: ModuleExportName::Ident(orig),
+                            exported,
+                            ..
+                        }) = s
+                        {
+                            let exported = match exported {
+                                Some(ModuleExportName::Ident(exported)) => exported,
+                                Some(ModuleExportName::Str(..)) => continue,
+                                None => orig,
+                            };
+                            used_names.insert(exported.sym.clone());
+                            existing.insert(Id::from(orig), exported.sym.clone());
+                        }
+                    }
+                }
+            }
+
+            let mut specifiers = vec![];
+            for id in &linking.exports {
+                let local = match linking.resolved.get(id) {
+                    Some(v) => v.clone(),
+                    None => continue,
+                };
+                let name = match existing.get(&Id::from(&local)) {
+                    Some(name) => name.clone(),
+                    None => {
+                        let name = unique_export_name(&mut used_names, &local.sym);
+                        existing.insert(Id::from(&local), name.clone());
+                        specifiers.push(ExportSpecifier::Named(ExportNamedSpecifier {
+                            span: DUMMY_SP,
+                            orig: ModuleExportName::Ident(local),
+                            exported: Some(ModuleExportName::Ident(Ident::new(
+                                name.clone(),
+                                DUMMY_SP,
+                            ))),
+                            is_type_only: false,
+                        }));
+                        name
+                    }
+                };
+
+                export_names[idx].insert(id.clone(), name);
+            }
+
+            if !specifiers.is_empty() {
+                module
+                    .body
+                    .push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        NamedExport {
+                            span: DUMMY_SP,
+                            specifiers,
+                            src: None,
+                            type_only: false,
+                            with: None,
+                        },
+                    )));
+            }
+        }
+
+        // Chunks imported by each chunk using `import`.
+        let mut static_imports = vec![vec![]; chunks.len()];
+        for (idx, linking) in linking.iter().enumerate() {
+            let mut imports = AHashMap::<usize, Vec<_>>::default();
+            for (id, owner) in &linking.imports {
+                let (local, name) = match (linking.resolved.get(id), export_names[*owner].get(id)) {
+                    (Some(local), Some(name)) => (local.clone(), name.clone()),
+                    _ => continue,
+                };
+
+                let specifiers = imports.entry(*owner).or_default();
+                if specifiers
+                    .iter()
+                    .any(|s: &ImportSpecifier| matches!(s, ImportSpecifier::Named(s) if s.local.to_id() == local.to_id()))
+                {
+                    continue;
+                }
+                specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: DUMMY_SP,
+                    local,
+                    imported: Some(ModuleExportName::Ident(Ident::new(name, DUMMY_SP))),
+                    is_type_only: false,
+                }));
+            }
+
+            let mut imports = imports.into_iter().collect::<Vec<_>>();
+            imports.sort_by_key(|(owner, _)| *owner);
+
+            static_imports[idx] = imports
+                .iter()
+                .map(|(owner, _)| chunks[*owner].root)
+                .collect();
+
+            for (owner, specifiers) in imports.into_iter().rev() {
+                prepend_stmt(
+                    &mut modules[idx].body,
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers,
+                        src: Box::new(Str {
+                            span: DUMMY_SP.with_ctxt(self.synthesized_ctxt),
+                            value: format!("./{}", chunks[owner].name).into(),
+                            raw: None,
+                        }),
+                        type_only: false,
+                        with: None,
+                    })),
+                );
+            }
+        }
+
+        chunks
+            .iter()
+            .zip(modules)
+            .zip(static_imports)
+            .zip(linking)
+            .map(|(((chunk, module), imports), linking)| Bundle {
+                kind: chunk.kind.clone(),
+                file_name: chunk.kind.name().to_string(),
+                id: chunk.root,
+                module,
+                tree_shaking: None,
+                imports,
+                dynamic_imports: linking
+                    .dynamic_imports
+                    .into_iter()
+                    .map(|idx| chunks[idx].root)
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+fn unique_export_name(used: &mut AHashSet<JsWord>, sym: &JsWord) -> JsWord {
+    let mut name = sym.clone();
+    let mut i = 1;
+    while used.contains(&name) {
+        name = format!("{}{}", sym, i).into();
+        i += 1;
+    }
+
+    used.insert(name.clone());
+    name
+}
+
+/// Finds identifiers declared by modules of other chunks.
+struct ForeignRefFinder<'a> {
+    chunk: usize,
+    chunk_of_ctxt: &'a AHashMap<SyntaxContext, usize>,
+    refs: &'a mut IndexSet<(Id, usize), ARandomState>,
+}
+
+impl Visit for ForeignRefFinder<'_> {
+    noop_visit_type!();
+
+    fn visit_export_named_specifier(&mut self, n: &ExportNamedSpecifier) {
+        n.orig.visit_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        if let Some(&owner) = self.chunk_of_ctxt.get(&n.span.ctxt) {
+            if owner != self.chunk {
+                self.refs.insert((n.into(), owner));
+            }
+        }
+    }
+
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+
+    fn visit_member_prop(&mut self, n: &MemberProp) {
+        if let MemberProp::Computed(n) = n {
+            n.visit_with(self);
+        }
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName) {
+        if let PropName::Computed(n) = n {
+            n.visit_with(self);
+        }
+    }
+
+    fn visit_super_prop(&mut self, n: &SuperProp) {
+        if let SuperProp::Computed(n) = n {
+            n.visit_with(self);
+        }
+    }
+}
+
+/// Replaces the source of `import()` with the path to the chunk.
+struct DynamicImportRewriter {
+    synthesized_ctxt: SyntaxContext,
+    targets: AHashMap<JsWord, String>,
+}
+
+impl VisitMut for DynamicImportRewriter {
+    noop_visit_mut_type!();
+
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        n.visit_mut_children_with(self);
+
+        if !matches!(n.callee, Callee::Import(..)) {
+            return;
+        }
+
+        if let Some(ExprOrSpread { spread: None, expr }) = n.args.first_mut() {
+            if let Expr::Lit(Lit::Str(src)) = &mut **expr {
+                if let Some(path) = self.targets.get(&src.value) {
+                    *src = Str {
+                        span: src.span.with_ctxt(self.synthesized_ctxt),
+                        value: path.clone().into(),
+                        raw: None,
+                    };
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,33 @@
-use std::path::{Path, PathBuf};
+use std::{
+    iter::once,
+    path::{Path, PathBuf},
+};
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use relative_path::RelativePath;
-use swc_common::{collections::AHashMap, util::move_map::MoveMap, FileName, Mark, DUMMY_SP};
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    comments::NoopComments,
+    util::move_map::MoveMap,
+    FileName, Mark, SyntaxContext, DUMMY_SP,
+};
 use swc_ecma_ast::*;
 use swc_ecma_transforms_base::{
     fixer::fixer,
     helpers::{inject_helpers, HELPERS},
     hygiene::hygiene,
 };
+use swc_ecma_transforms_module::{
+    common_js, import_analysis::import_analyzer, umd, util::ImportInterop,
+};
 use swc_ecma_utils::{contains_top_level_await, find_pat_ids, private_ident, ExprFactory};
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
-use crate::{hash::calc_hash, Bundle, BundleKind, Bundler, Load, ModuleType, Resolve};
+use super::chunk::chunk_file_name;
+use crate::{
+    hash::{calc_hash, combine_hashes},
+    Bundle, BundleKind, Bundler, Load, ModuleId, ModuleType, Resolve,
+};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -34,7 +49,9 @@ where
             let mut renamed = AHashMap::default();
 
             for mut bundle in bundles {
-                bundle.module = self.optimize(bundle.module);
+                let (module, tree_shaking) = self.optimize(bundle.id, bundle.module);
+                bundle.module = module;
+                bundle.tree_shaking = tree_shaking;
 
                 if !self.config.disable_hygiene {
                     bundle.module = bundle.module.fold_with(&mut hygiene());
@@ -57,59 +74,70 @@ where
                     let module = bundle.module;
 
                     bundle.module = HELPERS.set(&swc_helpers, || {
+                        let module = if self.is_converted_to_script() {
+                            // Helpers used by the module transforms should be injected before
+                            // the conversion.
+                            module.fold_with(&mut import_analyzer(ImportInterop::Swc, false))
+                        } else {
+                            module
+                        };
+
                         module.fold_with(&mut inject_helpers(unresolved_mark))
                     });
                 }
 
-                match bundle.kind {
-                    BundleKind::Named { .. } => {
-                        // Inject helpers
-                        let helpers = self
-                            .scope
-                            .get_module(bundle.id)
-                            .expect("module should exist at this point")
-                            .helpers;
+                {
+                    // Inject helpers
+                    let helpers = self
+                        .scope
+                        .get_module(bundle.id)
+                        .expect("module should exist at this point")
+                        .helpers;
 
-                        helpers.add_to(&mut bundle.module.body);
+                    helpers.add_to(&mut bundle.module.body);
+                }
 
-                        new.push(bundle);
-                    }
-                    BundleKind::Lib { name } => {
-                        let hash = calc_hash(self.cm.clone(), &bundle.module)?;
-                        let mut new_name = PathBuf::from(name);
-                        let key = new_name.clone();
-                        let file_name = new_name
-                            .file_name()
-                            .map(|path| -> PathBuf {
-                                let path = Path::new(path);
-                                let ext = path.extension();
-                                if let Some(ext) = ext {
-                                    return format!(
-                                        "{}-{}.{}",
-                                        path.file_stem().unwrap().to_string_lossy(),
-                                        hash,
-                                        ext.to_string_lossy()
-                                    )
-                                    .into();
-                                }
-                                format!("{}-{}", path.file_stem().unwrap().to_string_lossy(), hash,)
-                                    .into()
-                            })
-                            .expect("javascript file should have name");
-                        new_name.pop();
-                        new_name = new_name.join(file_name.clone());
-
-                        renamed.insert(key, new_name.to_string_lossy().to_string());
-
-                        new.push(Bundle {
-                            kind: BundleKind::Named {
-                                name: file_name.display().to_string(),
-                            },
-                            ..bundle
-                        })
-                    }
-                    _ => new.push(bundle),
+                bundle.module = self.may_convert_module(bundle.id, bundle.module, unresolved_mark);
+
+                new.push(bundle);
+            }
+
+            let file_names = self.file_names(&new)?;
+            // File names of bundles, by paths used to import them.
+            let mut chunk_names = AHashMap::default();
+            let mut is_renamed = false;
+
+            for (bundle, file_name) in new.iter().zip(&file_names) {
+                if let BundleKind::Lib { name } = &bundle.kind {
+                    renamed.insert(PathBuf::from(name), file_name.clone());
                 }
+
+                let name = reference_name(&bundle.kind);
+                is_renamed |= name != *file_name;
+                chunk_names.insert(format!("./{}", name), file_name.clone());
+            }
+
+            new = new
+                .into_iter()
+                .zip(file_names)
+                .map(|(bundle, file_name)| Bundle {
+                    file_name,
+                    ..bundle
+                })
+                .collect();
+
+            if is_renamed {
+                new = new.move_map(|bundle| {
+                    let module = bundle.module.fold_with(&mut ChunkRenamer {
+                        synthesized_ctxt: self.synthesized_ctxt,
+                        base: RelativePath::new(&bundle.file_name)
+                            .parent()
+                            .unwrap_or_else(|| RelativePath::new("")),
+                        renamed: &chunk_names,
+                    });
+
+                    Bundle { module, ..bundle }
+                });
             }
 
             if new.len() == 1 {
@@ -142,6 +170,167 @@ where
         })
     }
 
+    /// Computes [Bundle::file_name] of each bundle, using the patterns of
+    /// [crate::Config].
+    ///
+    /// The hash of a bundle covers the bundles it references, transitively, so
+    /// the file name changes if a file name of a dependency changes.
+    fn file_names(&self, bundles: &[Bundle]) -> Result<Vec<String>, Error> {
+        let patterns = bundles
+            .iter()
+            .map(|bundle| self.file_name_pattern(&bundle.kind))
+            .collect::<Vec<_>>();
+
+        let mut hashes = vec![];
+        let mut deps = vec![];
+
+        if patterns
+            .iter()
+            .any(|pattern| matches!(pattern, Some(pattern) if pattern.contains("[hash]")))
+        {
+            let index = bundles
+                .iter()
+                .enumerate()
+                .map(|(idx, bundle)| (bundle.id, idx))
+                .collect::<AHashMap<_, _>>();
+
+            for bundle in bundles {
+                hashes.push(calc_hash(self.cm.clone(), &bundle.module)?);
+                deps.push(
+                    bundle
+                        .imports
+                        .iter()
+                        .chain(&bundle.dynamic_imports)
+                        .filter_map(|id| index.get(id).copied())
+                        .collect::<AHashSet<_>>(),
+                );
+            }
+        }
+
+        let mut used = AHashSet::default();
+
+        bundles
+            .iter()
+            .zip(patterns)
+            .enumerate()
+            .map(|(idx, (bundle, pattern))| {
+                let name = bundle.kind.name();
+                let file_name = match pattern {
+                    Some(pattern) => {
+                        let hash = if pattern.contains("[hash]") {
+                            let mut visited = AHashSet::default();
+                            let mut stack = deps[idx].iter().copied().collect::<Vec<_>>();
+                            while let Some(dep) = stack.pop() {
+                                if dep != idx && visited.insert(dep) {
+                                    stack.extend(deps[dep].iter().copied());
+                                }
+                            }
+
+                            if visited.is_empty() {
+                                hashes[idx].clone()
+                            } else {
+                                let mut visited = visited.into_iter().collect::<Vec<_>>();
+                                visited.sort_by(|&a, &b| bundles[a].kind.cmp(&bundles[b].kind));
+
+                                combine_hashes(
+                                    once(&*hashes[idx])
+                                        .chain(visited.into_iter().map(|dep| &*hashes[dep])),
+                                )
+                            }
+                        } else {
+                            Default::default()
+                        };
+
+                        expand_file_name(pattern, name, &hash)
+                    }
+                    None => name.to_string(),
+                };
+
+                if !used.insert(file_name.clone()) {
+                    bail!(
+                        "multiple bundles are emitted to `{}`; use `[name]` in the pattern of \
+                         file names",
+                        file_name
+                    )
+                }
+
+                Ok(file_name)
+            })
+            .collect()
+    }
+
+    fn file_name_pattern(&self, kind: &BundleKind) -> Option<&str> {
+        match kind {
+            BundleKind::Named { .. } => self.config.entry_file_names.as_deref(),
+            BundleKind::Dynamic { .. } => self.config.chunk_file_names.as_deref(),
+            BundleKind::Lib { .. } => Some(
+                self.config
+                    .chunk_file_names
+                    .as_deref()
+                    .unwrap_or("[name]-[hash].[ext]"),
+            ),
+        }
+    }
+
+    /// Returns true if es module syntax in bundles is converted by
+    /// [Self::may_convert_module].
+    fn is_converted_to_script(&self) -> bool {
+        matches!(self.config.module, ModuleType::CommonJs | ModuleType::Umd)
+    }
+
+    /// Converts a bundle to a common js module or an umd module, using the
+    /// module transforms of swc.
+    ///
+    /// This should be called after injecting helpers, so the helpers are
+    /// declared inside of the umd factory.
+    fn may_convert_module(
+        &self,
+        id: ModuleId,
+        mut module: Module,
+        unresolved_mark: Mark,
+    ) -> Module {
+        if !self.is_converted_to_script() {
+            return module;
+        }
+
+        let info = self
+            .scope
+            .get_module(id)
+            .expect("module should exist at this point");
+
+        module = HELPERS.set(&info.swc_helpers, || match self.config.module {
+            ModuleType::CommonJs => module.fold_with(&mut common_js(
+                unresolved_mark,
+                Default::default(),
+                Default::default(),
+                None::<NoopComments>,
+            )),
+            ModuleType::Umd => {
+                // Used to determine the name of the global variable.
+                module.span = info.module.span;
+
+                module.fold_with(&mut umd(
+                    self.cm.clone(),
+                    unresolved_mark,
+                    Default::default(),
+                    Default::default(),
+                    None::<NoopComments>,
+                ))
+            }
+            _ => unreachable!(),
+        });
+
+        if !self.config.disable_hygiene {
+            module = module.fold_with(&mut hygiene());
+        }
+
+        if !self.config.disable_fixer {
+            module = module.fold_with(&mut fixer(None));
+        }
+
+        module
+    }
+
     fn may_wrap_with_iife(&self, module: Module) -> Module {
         if self.config.module != ModuleType::Iife {
             return module;
@@ -361,6 +550,40 @@ where
     }
 }
 
+/// Name used by other bundles to import a bundle created by code splitting.
+fn reference_name(kind: &BundleKind) -> String {
+    match kind {
+        BundleKind::Named { name } => chunk_file_name(name),
+        BundleKind::Dynamic { name } | BundleKind::Lib { name } => name.clone(),
+    }
+}
+
+/// Expands `[name]`, `[ext]` and `[hash]` in `pattern`. The directory of
+/// `name` is preserved.
+fn expand_file_name(pattern: &str, name: &str, hash: &str) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_else(|| name.into());
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy())
+        .unwrap_or_else(|| "js".into());
+
+    let file_name = pattern
+        .replace("[name]", &stem)
+        .replace("[ext]", &ext)
+        .replace("[hash]", hash);
+
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => {
+            dir.join(file_name).to_string_lossy().to_string()
+        }
+        _ => file_name,
+    }
+}
+
 /// Import renamer. This pass changes import path.
 struct Renamer<'a, R>
 where
@@ -420,3 +643,41 @@ where
         import
     }
 }
+
+/// Changes paths to chunks created by code splitting, which are marked with
+/// `synthesized_ctxt`, to be relative to `base`.
+struct ChunkRenamer<'a> {
+    synthesized_ctxt: SyntaxContext,
+    /// Directory of the bundle being renamed.
+    base: &'a RelativePath,
+    /// Maps original paths to file names of bundles.
+    renamed: &'a AHashMap<String, String>,
+}
+
+impl Fold for ChunkRenamer<'_> {
+    noop_fold_type!();
+
+    fn fold_str(&mut self, s: Str) -> Str {
+        if s.span.ctxt != self.synthesized_ctxt {
+            return s;
+        }
+
+        match self.renamed.get(&*s.value) {
+            Some(v) => {
+                let v = self.base.relative(v);
+                let value = v.as_str();
+
+                Str {
+                    value: if value.starts_with('.') {
+                        value.into()
+                    } else {
+                        format!("./{}", value).into()
+                    },
+                    raw: None,
+                    ..s
+                }
+            }
+            None => s,
+        }
+    }
+}
@@ -82,6 +82,8 @@ pub(super) struct RawImports {
     /// }
     /// ```
     pub lazy_imports: Vec<ImportDecl>,
+
+    /// Sources of `import()`. This is empty if code splitting is disabled.
     pub dynamic_imports: Vec<Str>,
 
     /// Contains namespace imports accessed with computed key.
@@ -254,19 +256,20 @@ where
                         self.info.lazy_imports.push(decl);
                     }
 
-                    // TODO: Uncomment this after implementing an option to make swc_bundler
-                    // includes dynamic imports
-                    //
-                    //
-                    // ExprOrSuper::Expr(ref e) => match &**e {
-                    //     Expr::Ident(Ident {
-                    //         sym: "import",
-                    //         ..
-                    //     }) => {
-                    //         self.info.dynamic_imports.push(src.clone());
-                    //     }
-                    //     _ => {}
-                    // },
+                    Callee::Import(..) if self.bundler.config.code_splitting => {
+                        if self.bundler.is_external(&src.value) {
+                            return;
+                        }
+
+                        if !self
+                            .info
+                            .dynamic_imports
+                            .iter()
+                            .any(|prev| prev.value == src.value)
+                        {
+                            self.info.dynamic_imports.push(src.clone());
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -11,6 +11,11 @@ pub struct KeywordRenamer {
 }
 
 impl KeywordRenamer {
+    /// Returns the identifier `id` is renamed to, if it's renamed.
+    pub(crate) fn get(&self, id: &Id) -> Option<&Ident> {
+        self.renamed.get(id)
+    }
+
     /// Returns `Some(new_ident)` if it should be renamed.
     fn renamed(&mut self, id: &Ident) -> Option<Ident> {
         if id.sym == "import" {
@@ -40,6 +40,9 @@ pub(crate) struct TransformedModule {
 
     pub swc_helpers: Lrc<swc_ecma_transforms_base::helpers::Helpers>,
 
+    /// False if the module is marked as side-effect free by package.json.
+    pub has_side_effects: bool,
+
     local_ctxt: SyntaxContext,
     export_ctxt: SyntaxContext,
 }
@@ -209,6 +212,7 @@ where
                     is_es6,
                     helpers: Default::default(),
                     swc_helpers: Lrc::new(data.helpers),
+                    has_side_effects: self.has_side_effects(file_name),
                     local_ctxt: SyntaxContext::empty().apply_mark(local_mark),
                     export_ctxt: SyntaxContext::empty().apply_mark(export_mark),
                 },
@@ -344,6 +348,11 @@ where
                 };
                 files.push((src.clone(), file_name));
 
+                if is_dynamic {
+                    merged.dynamic.push(src);
+                    continue;
+                }
+
                 // TODO: Handle rename
                 let mut specifiers = vec![];
                 for s in decl.specifiers {
@@ -386,6 +395,9 @@ where
 pub(crate) struct Imports {
     /// If imported ids are empty, it is a side-effect import.
     pub specifiers: Vec<(Source, Vec<Specifier>)>,
+
+    /// Modules loaded by `import()`.
+    pub dynamic: Vec<Source>,
 }
 
 /// Clone is relatively cheap
@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
-use anyhow::{Context, Error};
+use anyhow::{bail, Context, Error};
 use swc_atoms::JsWord;
 use swc_common::{
     collections::AHashMap, sync::Lrc, FileName, Globals, Mark, SourceMap, SyntaxContext, GLOBALS,
 };
 use swc_ecma_ast::Module;
 
-use self::scope::Scope;
+pub use self::tree_shaking::{ModuleTreeShaking, TreeShakingReport};
+use self::{scope::Scope, side_effects::SideEffectsCache};
 use crate::{Hook, Load, ModuleId, Resolve};
 
 mod chunk;
@@ -19,8 +20,10 @@ mod keywords;
 mod load;
 mod optimize;
 mod scope;
+mod side_effects;
 #[cfg(test)]
 pub(crate) mod tests;
+mod tree_shaking;
 
 #[derive(Debug, Default)]
 pub struct Config {
@@ -39,19 +42,60 @@ pub struct Config {
     pub disable_fixer: bool,
 
     /// Disable tree-shaking optimization.
+    ///
+    /// If it's false, modules marked as side-effect free by `sideEffects` of
+    /// package.json are removed if none of their exports are used.
     pub disable_dce: bool,
 
+    /// If it's true, [Bundle::tree_shaking] is populated.
+    pub tree_shaking_report: bool,
+
     /// List of modules which should be preserved.
     pub external_modules: Vec<JsWord>,
 
     /// Type of emitted module
     pub module: ModuleType,
+
+    /// If it's true, targets of `import()` are emitted as separate bundles
+    /// ([BundleKind::Dynamic]) and modules shared by multiple bundles are
+    /// hoisted into common bundles ([BundleKind::Lib]).
+    ///
+    /// Bundles import each other using the names of the bundles, so they
+    /// should be emitted to a same directory, as-is.
+    ///
+    /// This requires [ModuleType::Es].
+    pub code_splitting: bool,
+
+    /// Pattern used to compute [Bundle::file_name] of user-provided entries.
+    ///
+    /// `[name]` is replaced with the name of the entry without an extension,
+    /// `[ext]` with the extension of it and `[hash]` with a hash of the
+    /// emitted code. e.g. `[name].[hash].js`
+    ///
+    /// If it's [None], the name of the entry is used as-is.
+    pub entry_file_names: Option<String>,
+
+    /// Pattern used to compute [Bundle::file_name] of bundles created by code
+    /// splitting. See [Config::entry_file_names] for the syntax.
+    ///
+    /// If it's [None], common bundles are named `[name]-[hash].[ext]` and the
+    /// name of a dynamic bundle is used as-is.
+    pub chunk_file_names: Option<String>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModuleType {
     Es,
     Iife,
+    /// Exports are assigned to `module.exports`, and external modules are
+    /// loaded using `require`.
+    CommonJs,
+    /// The bundle is wrapped with an UMD factory, and external modules are
+    /// passed to the factory.
+    ///
+    /// The name of the global variable is derived from the file name of the
+    /// entry.
+    Umd,
 }
 
 impl Default for ModuleType {
@@ -65,18 +109,49 @@ pub enum BundleKind {
     /// User-provided entry
     Named { name: String },
     /// Auto-generated entry (created by import expression)
-    Dynamic,
+    ///
+    /// These are created only if [Config::code_splitting] is enabled. `name`
+    /// is the default file name of the bundle.
+    Dynamic { name: String },
     /// A lazy-loaded shared library
     Lib { name: String },
 }
 
+impl BundleKind {
+    pub fn name(&self) -> &str {
+        match self {
+            BundleKind::Named { name }
+            | BundleKind::Dynamic { name }
+            | BundleKind::Lib { name } => name,
+        }
+    }
+}
+
 /// Built bundle
 #[derive(Debug)]
 pub struct Bundle {
     pub kind: BundleKind,
     pub id: ModuleId,
+    /// Name of the file this bundle should be written to.
+    ///
+    /// Bundles reference each other using this name, so it differs from the
+    /// name of [BundleKind] only if a pattern of [Config] is used or the
+    /// bundle is a common bundle created by code splitting.
+    pub file_name: String,
     /// Merged module.
     pub module: Module,
+    /// Modules retained in the bundle and exports removed by tree shaking.
+    ///
+    /// This is [None] unless [Config::tree_shaking_report] is enabled.
+    pub tree_shaking: Option<TreeShakingReport>,
+    /// Bundles imported by this bundle using `import`, by [Bundle::id].
+    ///
+    /// This is empty unless [Config::code_splitting] is enabled.
+    pub imports: Vec<ModuleId>,
+    /// Bundles loaded by this bundle using `import()`, by [Bundle::id].
+    ///
+    /// This is empty unless [Config::code_splitting] is enabled.
+    pub dynamic_imports: Vec<ModuleId>,
 }
 
 pub struct Bundler<'a, L, R>
@@ -105,6 +180,8 @@ where
 
     scope: Scope,
 
+    side_effects: SideEffectsCache,
+
     hook: Box<dyn 'a + Hook>,
 }
 
@@ -139,6 +216,7 @@ where
                 synthesized_ctxt,
                 injected_ctxt,
                 scope: Default::default(),
+                side_effects: Default::default(),
                 hook,
                 unresolved_mark: Mark::new(),
             }
@@ -156,6 +234,10 @@ where
     /// circular manner. However, it applies only to the provided `entries`, and
     /// dependencies with circular reference is ok.
     pub fn bundle(&mut self, entries: HashMap<String, FileName>) -> Result<Vec<Bundle>, Error> {
+        if self.config.code_splitting && self.config.module != ModuleType::Es {
+            bail!("code splitting requires es modules as output")
+        }
+
         let results = entries
             .into_iter()
             .map(|(name, path)| -> Result<_, Error> {
@@ -180,9 +262,6 @@ where
             })
             .collect::<Vec<_>>();
 
-        // We collect at here to handle dynamic imports
-        // TODO: Handle dynamic imports
-
         let local = {
             let mut output = AHashMap::default();
 
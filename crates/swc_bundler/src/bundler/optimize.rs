@@ -3,7 +3,8 @@ use swc_ecma_ast::*;
 use swc_ecma_transforms_optimization::simplify::{const_propagation::constant_propagation, dce};
 use swc_ecma_visit::FoldWith;
 
-use crate::{Bundler, Load, Resolve};
+use super::tree_shaking::TreeShakingReport;
+use crate::{Bundler, Load, ModuleId, Resolve};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -14,24 +15,48 @@ where
     ///
     /// Note: Context of used_exports is ignored, as the specifiers comes from
     /// other module.
-    pub(super) fn optimize(&self, mut node: Module) -> Module {
+    ///
+    /// `id` is the id of the root module of the bundle.
+    pub(super) fn optimize(
+        &self,
+        id: ModuleId,
+        mut node: Module,
+    ) -> (Module, Option<TreeShakingReport>) {
         self.run(|| {
+            let owners = self.module_owners(id);
+            let declared = if self.config.tree_shaking_report {
+                Some(owners.declared_names(&node))
+            } else {
+                None
+            };
+
             if !self.config.disable_inliner {
                 node = node.fold_with(&mut constant_propagation())
             }
             if !self.config.disable_dce {
-                node = node.fold_with(&mut Repeat::new(dce::dce(
-                    dce::Config {
-                        // TODO(kdy1): Apply mark to wrapped esms and use it at here.
-                        module_mark: None,
-                        top_level: true,
-                        top_retain: Default::default(),
-                        preserve_imports_with_side_effects: true,
-                    },
-                    self.unresolved_mark,
-                )));
+                node = self.dce(node);
+
+                while self.remove_side_effect_free_modules(id, &owners, &mut node) {
+                    node = self.dce(node);
+                }
             }
-            node
+
+            let report = declared.map(|declared| owners.report(declared, &node));
+
+            (node, report)
         })
     }
+
+    fn dce(&self, node: Module) -> Module {
+        node.fold_with(&mut Repeat::new(dce::dce(
+            dce::Config {
+                // TODO(kdy1): Apply mark to wrapped esms and use it at here.
+                module_mark: None,
+                top_level: true,
+                top_retain: Default::default(),
+                preserve_imports_with_side_effects: true,
+            },
+            self.unresolved_mark,
+        )))
+    }
 }
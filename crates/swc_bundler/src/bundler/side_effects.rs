@@ -0,0 +1,164 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+use serde::Deserialize;
+use swc_common::{
+    collections::AHashMap,
+    sync::{Lock, Lrc},
+    FileName,
+};
+
+use crate::{Bundler, Load, Resolve};
+
+/// `sideEffects` of package.json files, cached by directories.
+#[derive(Debug, Default)]
+pub(super) struct SideEffectsCache {
+    /// The nearest package of a directory.
+    packages: Lock<AHashMap<PathBuf, Option<Lrc<Package>>>>,
+}
+
+#[derive(Debug)]
+struct Package {
+    dir: PathBuf,
+    side_effects: SideEffects,
+}
+
+#[derive(Debug)]
+enum SideEffects {
+    All(bool),
+    /// Files matching one of patterns have side effects.
+    Files(Vec<Pattern>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    #[serde(default)]
+    side_effects: Option<RawSideEffects>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawSideEffects {
+    Bool(bool),
+    Files(Vec<String>),
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Returns false if the module is marked as side-effect free by
+    /// `sideEffects` of the nearest package.json.
+    pub(super) fn has_side_effects(&self, file_name: &FileName) -> bool {
+        if self.config.disable_dce {
+            return true;
+        }
+
+        let path = match file_name {
+            FileName::Real(path) => path,
+            _ => return true,
+        };
+
+        let package = match path.parent().and_then(|dir| self.side_effects.package(dir)) {
+            Some(v) => v,
+            None => return true,
+        };
+
+        match &package.side_effects {
+            SideEffects::All(v) => *v,
+            SideEffects::Files(patterns) => {
+                let path = path.strip_prefix(&package.dir).unwrap_or(path);
+                let options = MatchOptions {
+                    require_literal_separator: true,
+                    ..Default::default()
+                };
+
+                patterns
+                    .iter()
+                    .any(|pattern| pattern.matches_path_with(path, options))
+            }
+        }
+    }
+}
+
+impl SideEffectsCache {
+    fn package(&self, dir: &Path) -> Option<Lrc<Package>> {
+        if let Some(v) = self.packages.lock().get(dir) {
+            return v.clone();
+        }
+
+        let pkg_path = dir.join("package.json");
+        let package = if pkg_path.is_file() {
+            read_package(dir, &pkg_path).map(Lrc::new)
+        } else {
+            dir.parent().and_then(|parent| self.package(parent))
+        };
+
+        self.packages
+            .lock()
+            .insert(dir.to_path_buf(), package.clone());
+
+        package
+    }
+}
+
+/// Returns [None] if the package.json cannot be read, so the module is treated
+/// as a module with side effects.
+fn read_package(dir: &Path, pkg_path: &Path) -> Option<Package> {
+    let json = match fs::read_to_string(pkg_path) {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!("failed to read {}: {}", pkg_path.display(), err);
+            return None;
+        }
+    };
+    let pkg: PackageJson = match serde_json::from_str(&json) {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!("failed to parse {}: {}", pkg_path.display(), err);
+            return None;
+        }
+    };
+
+    let side_effects = match pkg.side_effects {
+        None => SideEffects::All(true),
+        Some(RawSideEffects::Bool(v)) => SideEffects::All(v),
+        Some(RawSideEffects::Files(files)) => SideEffects::Files(
+            files
+                .iter()
+                .filter_map(|file| {
+                    // Same as webpack, a pattern without a slash matches files in any
+                    // directory.
+                    let pattern = if file.contains('/') {
+                        Pattern::new(file.strip_prefix("./").unwrap_or(file))
+                    } else {
+                        Pattern::new(&format!("**/{}", file))
+                    };
+
+                    match pattern {
+                        Ok(v) => Some(v),
+                        Err(err) => {
+                            tracing::warn!(
+                                "invalid pattern `{}` in `sideEffects` of {}: {}",
+                                file,
+                                pkg_path.display(),
+                                err
+                            );
+                            None
+                        }
+                    }
+                })
+                .collect(),
+        ),
+    };
+
+    Some(Package {
+        dir: dir.to_path_buf(),
+        side_effects,
+    })
+}
@@ -137,8 +137,12 @@ impl TestBuilder {
                         disable_hygiene: false,
                         disable_fixer: false,
                         disable_dce: false,
+                        tree_shaking_report: false,
                         external_modules: vec![],
                         module: Default::default(),
+                        code_splitting: false,
+                        entry_file_names: None,
+                        chunk_file_names: None,
                     },
                     Box::new(Hook),
                 );
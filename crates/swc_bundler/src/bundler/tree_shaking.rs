@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    BytePos, Span, Spanned,
+};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{collect_decls, find_pat_ids};
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use super::load::{Specifier, TransformedModule};
+use crate::{Bundler, Load, ModuleId, Resolve};
+
+/// Result of tree shaking for a bundle.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct TreeShakingReport {
+    /// Modules merged into the bundle, by their file names.
+    pub modules: BTreeMap<String, ModuleTreeShaking>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleTreeShaking {
+    /// True if any code of the module is in the bundle.
+    pub retained: bool,
+
+    /// False if the module is marked as side-effect free by `sideEffects` of
+    /// package.json.
+    pub has_side_effects: bool,
+
+    /// Exports of the module removed because they are not used.
+    pub removed_exports: Vec<String>,
+}
+
+/// Finds the module which a module item comes from, using spans.
+///
+/// Items injected by the bundler do not belong to any module.
+pub(super) struct ModuleOwners {
+    /// Sorted by the start position of source files.
+    modules: Vec<(BytePos, BytePos, TransformedModule)>,
+}
+
+impl ModuleOwners {
+    fn owner(&self, span: Span) -> Option<&TransformedModule> {
+        if span.is_dummy() {
+            return None;
+        }
+
+        let idx = match self
+            .modules
+            .binary_search_by_key(&span.lo, |(start, ..)| *start)
+        {
+            Ok(idx) => idx,
+            Err(0) => return None,
+            Err(idx) => idx - 1,
+        };
+
+        let (_, end, module) = &self.modules[idx];
+        if span.lo < *end {
+            Some(module)
+        } else {
+            None
+        }
+    }
+
+    fn owner_id(&self, item: &ModuleItem) -> Option<ModuleId> {
+        self.owner(item.span()).map(|m| m.id)
+    }
+
+    /// Names declared at the top level of each module.
+    pub fn declared_names(&self, module: &Module) -> AHashMap<ModuleId, AHashSet<String>> {
+        let mut names = AHashMap::<_, AHashSet<_>>::default();
+
+        for item in &module.body {
+            if let Some(id) = self.owner_id(item) {
+                let entry = names.entry(id).or_default();
+
+                let decl = match item {
+                    ModuleItem::Stmt(Stmt::Decl(decl))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                        decl
+                    }
+                    _ => continue,
+                };
+
+                match decl {
+                    Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+                        entry.insert(ident.sym.to_string());
+                    }
+                    Decl::Var(v) => {
+                        let ids: Vec<Ident> = find_pat_ids(&v.decls);
+                        entry.extend(ids.into_iter().map(|id| id.sym.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        names
+    }
+
+    /// `before` should be the result of [ModuleOwners::declared_names] before
+    /// tree shaking.
+    pub fn report(
+        &self,
+        before: AHashMap<ModuleId, AHashSet<String>>,
+        module: &Module,
+    ) -> TreeShakingReport {
+        let after = self.declared_names(module);
+
+        let modules = before
+            .into_iter()
+            .filter_map(|(id, declared)| {
+                let (_, _, info) = self.modules.iter().find(|(_, _, m)| m.id == id)?;
+                let retained = after.get(&id);
+
+                let mut removed_exports = info
+                    .exports
+                    .items
+                    .iter()
+                    .filter_map(|s| match s {
+                        Specifier::Specific { local, alias } => {
+                            let local = local.sym().to_string();
+                            if !declared.contains(&local)
+                                || retained.map_or(false, |names| names.contains(&local))
+                            {
+                                return None;
+                            }
+
+                            Some(
+                                alias
+                                    .as_ref()
+                                    .map_or(local, |alias| alias.sym().to_string()),
+                            )
+                        }
+                        Specifier::Namespace { .. } => None,
+                    })
+                    .collect::<Vec<_>>();
+                removed_exports.sort();
+                removed_exports.dedup();
+
+                Some((
+                    info.fm.name.to_string(),
+                    ModuleTreeShaking {
+                        retained: retained.is_some(),
+                        has_side_effects: info.has_side_effects,
+                        removed_exports,
+                    },
+                ))
+            })
+            .collect();
+
+        TreeShakingReport { modules }
+    }
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Creates [ModuleOwners] for modules reachable from `root`.
+    pub(super) fn module_owners(&self, root: ModuleId) -> ModuleOwners {
+        let mut modules = vec![];
+        let mut visited = AHashSet::default();
+        let mut queue = vec![root];
+
+        while let Some(id) = queue.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            let info = match self.scope.get_module(id) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            queue.extend(
+                info.imports
+                    .specifiers
+                    .iter()
+                    .map(|(src, _)| src.module_id)
+                    .chain(info.imports.dynamic.iter().map(|src| src.module_id))
+                    .chain(info.exports.reexports.iter().map(|(src, _)| src.module_id)),
+            );
+
+            modules.push((info.fm.start_pos, info.fm.end_pos, info));
+        }
+
+        modules.sort_by_key(|(start, ..)| *start);
+
+        ModuleOwners { modules }
+    }
+
+    /// Removes items of side-effect free modules, if nothing declared by the
+    /// module is used by other modules.
+    ///
+    /// Returns true if anything is removed.
+    pub(super) fn remove_side_effect_free_modules(
+        &self,
+        root: ModuleId,
+        owners: &ModuleOwners,
+        module: &mut Module,
+    ) -> bool {
+        let item_owners = module
+            .body
+            .iter()
+            .map(|item| owners.owner(item.span()))
+            .collect::<Vec<_>>();
+
+        let mut candidates = AHashSet::default();
+        let mut declared_by = AHashMap::default();
+        for (item, owner) in module.body.iter().zip(&item_owners) {
+            let owner = match owner {
+                Some(v) if v.id != root && !v.has_side_effects => v.id,
+                _ => continue,
+            };
+
+            candidates.insert(owner);
+            declared_by.extend(
+                collect_decls::<Id, _>(item)
+                    .into_iter()
+                    .map(|id| (id, owner)),
+            );
+        }
+
+        if candidates.is_empty() {
+            return false;
+        }
+
+        let mut used = AHashSet::default();
+        for (item, owner) in module.body.iter().zip(&item_owners) {
+            item.visit_with(&mut UsageFinder {
+                owner: owner.map(|m| m.id),
+                declared_by: &declared_by,
+                used: &mut used,
+            });
+        }
+
+        let removed = candidates
+            .into_iter()
+            .filter(|id| !used.contains(id))
+            .collect::<AHashSet<_>>();
+        if removed.is_empty() {
+            return false;
+        }
+
+        tracing::debug!("Removing side-effect free modules: {:?}", removed);
+
+        let mut item_owners = item_owners.into_iter();
+        module.body.retain(|_| match item_owners.next().flatten() {
+            Some(owner) => !removed.contains(&owner.id),
+            None => true,
+        });
+
+        true
+    }
+}
+
+/// Finds modules whose declarations are used by other modules.
+struct UsageFinder<'a> {
+    owner: Option<ModuleId>,
+    declared_by: &'a AHashMap<Id, ModuleId>,
+    used: &'a mut AHashSet<ModuleId>,
+}
+
+impl Visit for UsageFinder<'_> {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, i: &Ident) {
+        if let Some(&declared_by) = self.declared_by.get(&i.to_id()) {
+            if Some(declared_by) != self.owner {
+                self.used.insert(declared_by);
+            }
+        }
+    }
+}
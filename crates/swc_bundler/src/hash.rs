@@ -6,6 +6,10 @@ use swc_common::{sync::Lrc, BytePos, SourceMap, Span};
 use swc_ecma_ast::Module;
 use swc_ecma_codegen::{text_writer::WriteJs, Emitter};
 
+/// Calculates a hash of the code of `m`.
+///
+/// The code is printed as minified, so the hash depends only on the code and
+/// not on the formatting of it.
 pub(crate) fn calc_hash(cm: Lrc<SourceMap>, m: &Module) -> Result<String, Error> {
     let crc = Crc::<u64>::new(&CRC_64_ECMA_182);
     let digest = crc.digest();
@@ -13,7 +17,7 @@ pub(crate) fn calc_hash(cm: Lrc<SourceMap>, m: &Module) -> Result<String, Error>
 
     {
         let mut emitter = Emitter {
-            cfg: Default::default(),
+            cfg: swc_ecma_codegen::Config::default().with_minify(true),
             cm,
             comments: None,
             wr: Box::new(&mut buf) as Box<dyn WriteJs>,
@@ -29,6 +33,20 @@ pub(crate) fn calc_hash(cm: Lrc<SourceMap>, m: &Module) -> Result<String, Error>
     Ok(radix_fmt::radix(result, 36).to_string())
 }
 
+/// Calculates a hash from hashes of multiple modules. The result depends on
+/// the order of `hashes`.
+pub(crate) fn combine_hashes<'a>(hashes: impl IntoIterator<Item = &'a str>) -> String {
+    let crc = Crc::<u64>::new(&CRC_64_ECMA_182);
+    let mut digest = crc.digest();
+
+    for hash in hashes {
+        digest.update(hash.as_bytes());
+        digest.update(b"\n");
+    }
+
+    radix_fmt::radix(digest.finalize(), 36).to_string()
+}
+
 struct Hasher<'a> {
     digest: Digest<'a, u64>,
 }
@@ -5,15 +5,29 @@ use swc_ecma_visit::{
     VisitMutWith, VisitWith,
 };
 
-use crate::{id::Id, modules::Modules, util::Readonly};
+use crate::{id::Id, modules::Modules};
 
 #[derive(Debug, Default)]
 pub(crate) struct InlineData {
     ids: AHashMap<Id, Id>,
 }
 
+impl InlineData {
+    /// Returns the identifier `id` is replaced with.
+    pub fn resolve(&self, id: &Id) -> Id {
+        let mut id = id;
+        while let Some(mapped) = self.ids.get(id) {
+            id = mapped;
+        }
+
+        id.clone()
+    }
+}
+
 /// Inline **injected** variables.
-pub(crate) fn inline(injected_ctxt: SyntaxContext, module: &mut Modules) {
+///
+/// Returns the identifiers replaced by this pass.
+pub(crate) fn inline(injected_ctxt: SyntaxContext, module: &mut Modules) -> InlineData {
     tracing::debug!("Inlining injected variables");
 
     let mut data = Default::default();
@@ -27,15 +41,17 @@ pub(crate) fn inline(injected_ctxt: SyntaxContext, module: &mut Modules) {
         module.visit_with(&mut analyzer);
     }
 
-    let mut v = Inliner { data: data.into() };
+    let mut v = Inliner { data: &data };
     module.par_visit_mut_with(&mut v);
     module.retain_mut(|_, s| !matches!(s, ModuleItem::Stmt(Stmt::Empty(..))));
+
+    data
 }
 
 #[derive(Debug)]
 #[cfg_attr(feature = "concurrent", derive(Clone))]
-struct Inliner {
-    data: Readonly<InlineData>,
+struct Inliner<'a> {
+    data: &'a InlineData,
 }
 
 struct Analyzer<'a> {
@@ -83,7 +99,7 @@ impl Visit for Analyzer<'_> {
     }
 }
 
-impl VisitMut for Inliner {
+impl VisitMut for Inliner<'_> {
     noop_visit_mut_type!();
 
     visit_mut_obj_and_computed!();
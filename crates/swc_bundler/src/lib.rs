@@ -3,13 +3,20 @@
 #![allow(clippy::mutable_key_type)]
 
 pub use self::{
-    bundler::{Bundle, BundleKind, Bundler, Config, ModuleType},
+    analyze::{
+        analyze_bundle, render_analysis_html, BundleAnalysis, ModuleSize, PackageSize, Size,
+    },
+    bundler::{
+        Bundle, BundleKind, Bundler, Config, ModuleTreeShaking, ModuleType, TreeShakingReport,
+    },
     hook::{Hook, ModuleRecord},
     id::ModuleId,
     load::{Load, ModuleData},
+    manifest::{Manifest, ManifestEntry},
     resolve::Resolve,
 };
 
+mod analyze;
 mod bundler;
 mod debug;
 mod dep_graph;
@@ -18,6 +25,7 @@ mod hook;
 mod id;
 mod inline;
 mod load;
+mod manifest;
 mod modules;
 mod resolve;
 mod util;
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use swc_common::collections::AHashMap;
+
+use crate::{Bundle, BundleKind, ModuleId};
+
+/// Maps names of bundles to their output files and the bundles they depend
+/// on.
+///
+/// This is serialized as a JSON object keyed by the name of [BundleKind].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    pub bundles: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// [Bundle::file_name]
+    pub file: String,
+
+    /// True for user-provided entries.
+    pub is_entry: bool,
+
+    /// True for targets of `import()`.
+    pub is_dynamic_entry: bool,
+
+    /// File names of bundles imported statically.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<String>,
+
+    /// File names of bundles imported using `import()`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dynamic_imports: Vec<String>,
+}
+
+impl Manifest {
+    /// Creates a manifest from the output of [crate::Bundler::bundle].
+    pub fn new(bundles: &[Bundle]) -> Self {
+        let files = bundles
+            .iter()
+            .map(|bundle| (bundle.id, &bundle.file_name))
+            .collect::<AHashMap<_, _>>();
+        let file_names = |ids: &[ModuleId]| {
+            ids.iter()
+                .filter_map(|id| files.get(id).map(|file| file.to_string()))
+                .collect()
+        };
+
+        let bundles = bundles
+            .iter()
+            .map(|bundle| {
+                let entry = ManifestEntry {
+                    file: bundle.file_name.clone(),
+                    is_entry: matches!(bundle.kind, BundleKind::Named { .. }),
+                    is_dynamic_entry: matches!(bundle.kind, BundleKind::Dynamic { .. }),
+                    imports: file_names(&bundle.imports),
+                    dynamic_imports: file_names(&bundle.dynamic_imports),
+                };
+
+                (bundle.kind.name().to_string(), entry)
+            })
+            .collect();
+
+        Manifest { bundles }
+    }
+}
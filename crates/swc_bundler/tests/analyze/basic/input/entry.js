@@ -0,0 +1,5 @@
+import { format } from '@scope/util';
+import { greet } from 'lib';
+import { name } from './name';
+
+console.log(greet(format(name)));
@@ -0,0 +1,15 @@
+import { readFileSync } from 'fs';
+import path from 'path';
+import { greet } from './greet';
+
+export function read(file) {
+    return readFileSync(path.join(__dirname, file), 'utf8');
+}
+
+export { greet };
+
+export default class Reader {
+    read(file) {
+        return greet(read(file));
+    }
+}
@@ -0,0 +1,38 @@
+"use strict";
+Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+function _export(target, all) {
+    for(var name in all)Object.defineProperty(target, name, {
+        enumerable: true,
+        get: all[name]
+    });
+}
+_export(exports, {
+    default: function() {
+        return Reader;
+    },
+    greet: function() {
+        return greet;
+    },
+    read: function() {
+        return read;
+    }
+});
+var _fs = require("fs");
+var _path = _interop_require_default(require("path"));
+function _interop_require_default(obj) {
+    return obj && obj.__esModule ? obj : {
+        default: obj
+    };
+}
+const greet = (name)=>`Hello, ${name}`;
+function read(file) {
+    return (0, _fs.readFileSync)(_path.default.join(__dirname, file), 'utf8');
+}
+var Reader;
+Reader = class Reader {
+    read(file) {
+        return greet(read(file));
+    }
+};
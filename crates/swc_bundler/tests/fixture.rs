@@ -1,13 +1,13 @@
 use std::{
     self,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::read_dir,
     io,
     path::{Path, PathBuf},
 };
 
 use anyhow::Error;
-use swc_bundler::{BundleKind, Bundler, Config, ModuleRecord};
+use swc_bundler::{analyze_bundle, Bundler, Config, Manifest, ModuleRecord, ModuleType};
 use swc_common::{errors::HANDLER, FileName, Globals, Span};
 use swc_ecma_ast::{
     Bool, Expr, Ident, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr, MetaPropKind,
@@ -24,7 +24,16 @@ use self::common::*;
 #[path = "common/mod.rs"]
 mod common;
 
-fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
+/// Returns the name, the file name and the code of each bundle.
+fn do_test(
+    entry: &Path,
+    entries: HashMap<String, FileName>,
+    inline: bool,
+    config: Config,
+    manifest: bool,
+) -> Vec<(String, String, String)> {
+    let tree_shaking_report = config.tree_shaking_report;
+
     testing::run_test2(false, |cm, handler| {
         HANDLER.set(&handler, || {
             let globals = Globals::default();
@@ -37,8 +46,7 @@ fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
                     require: true,
                     disable_inliner: !inline,
                     external_modules: NODE_BUILTINS.iter().copied().map(From::from).collect(),
-                    module: Default::default(),
-                    ..Default::default()
+                    ..config
                 },
                 Box::new(Hook),
             );
@@ -49,6 +57,18 @@ fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
             println!("Bundled as {} modules", modules.len());
 
             let mut error = false;
+            let mut outputs = vec![];
+
+            if manifest {
+                let manifest = serde_json::to_string_pretty(&Manifest::new(&modules)).unwrap();
+
+                if let Err(err) = NormalizedOutput::from(manifest)
+                    .compare_to_file(entry.join("output").join("manifest.json"))
+                {
+                    println!("Diff: {:?}", err);
+                    error = true;
+                }
+            }
 
             for bundled in modules {
                 let code = {
@@ -70,10 +90,7 @@ fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
                     String::from_utf8_lossy(&buf).to_string()
                 };
 
-                let name = match bundled.kind {
-                    BundleKind::Named { name } | BundleKind::Lib { name } => PathBuf::from(name),
-                    BundleKind::Dynamic => format!("dynamic.{}.js", bundled.id).into(),
-                };
+                let name = PathBuf::from(&bundled.file_name);
 
                 let output_dir = entry.join("output");
 
@@ -91,7 +108,37 @@ fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
 
                 println!("Printing {}", output_path.display());
 
+                if tree_shaking_report {
+                    // Use paths relative to the fixture.
+                    let report = bundled
+                        .tree_shaking
+                        .unwrap()
+                        .modules
+                        .into_iter()
+                        .map(|(name, module)| {
+                            let name = Path::new(&name)
+                                .strip_prefix(entry.join("input"))
+                                .map(|path| path.display().to_string())
+                                .unwrap_or(name);
+                            (name, module)
+                        })
+                        .collect::<BTreeMap<_, _>>();
+
+                    if let Err(err) =
+                        NormalizedOutput::from(serde_json::to_string_pretty(&report).unwrap())
+                            .compare_to_file(output_path.with_extension("tree-shaking.json"))
+                    {
+                        println!("Diff: {:?}", err);
+                        error = true;
+                    }
+                }
+
                 let s = NormalizedOutput::from(code.to_string());
+                outputs.push((
+                    bundled.kind.name().to_string(),
+                    bundled.file_name.clone(),
+                    code,
+                ));
 
                 match s.compare_to_file(&output_path) {
                     Ok(_) => {}
@@ -106,15 +153,189 @@ fn do_test(entry: &Path, entries: HashMap<String, FileName>, inline: bool) {
                 return Err(());
             }
 
-            Ok(())
+            Ok(outputs)
         })
     })
-    .expect("failed to process a module");
+    .expect("failed to process a module")
 }
 
 #[testing::fixture("tests/fixture/**/input")]
 fn pass(entry: PathBuf) {
-    let entries = read_dir(&entry)
+    let entries = read_entries(&entry);
+
+    do_test(
+        entry.parent().unwrap(),
+        entries.clone(),
+        true,
+        Default::default(),
+        false,
+    );
+    do_test(
+        entry.parent().unwrap(),
+        entries,
+        false,
+        Default::default(),
+        false,
+    );
+}
+
+#[testing::fixture("tests/split/**/input")]
+fn split(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    let inlined = do_test(
+        entry.parent().unwrap(),
+        entries.clone(),
+        true,
+        Config {
+            code_splitting: true,
+            ..Default::default()
+        },
+        false,
+    );
+    let not_inlined = do_test(
+        entry.parent().unwrap(),
+        entries,
+        false,
+        Config {
+            code_splitting: true,
+            ..Default::default()
+        },
+        false,
+    );
+
+    // Hashes in file names depend only on the code.
+    for (name, file_name, code) in &inlined {
+        if file_name == name {
+            continue;
+        }
+
+        if let Some((_, other_file_name, other_code)) =
+            not_inlined.iter().find(|(other, ..)| other == name)
+        {
+            assert_eq!(
+                code == other_code,
+                file_name == other_file_name,
+                "{} is emitted as {} and {}",
+                name,
+                file_name,
+                other_file_name
+            );
+        }
+    }
+}
+
+#[testing::fixture("tests/commonjs/**/input")]
+fn commonjs(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    do_test(
+        entry.parent().unwrap(),
+        entries,
+        true,
+        Config {
+            module: ModuleType::CommonJs,
+            ..Default::default()
+        },
+        false,
+    );
+}
+
+#[testing::fixture("tests/umd/**/input")]
+fn umd(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    do_test(
+        entry.parent().unwrap(),
+        entries,
+        true,
+        Config {
+            module: ModuleType::Umd,
+            ..Default::default()
+        },
+        false,
+    );
+}
+
+#[testing::fixture("tests/hash/**/input")]
+fn hash(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    do_test(
+        entry.parent().unwrap(),
+        entries,
+        true,
+        Config {
+            code_splitting: true,
+            entry_file_names: Some("[name].[hash].js".into()),
+            chunk_file_names: Some("chunks/[name].[hash].js".into()),
+            ..Default::default()
+        },
+        true,
+    );
+}
+
+#[testing::fixture("tests/side-effects/**/input")]
+fn side_effects(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    do_test(
+        entry.parent().unwrap(),
+        entries,
+        true,
+        Config {
+            tree_shaking_report: true,
+            ..Default::default()
+        },
+        false,
+    );
+}
+
+#[testing::fixture("tests/analyze/**/input")]
+fn analyze(entry: PathBuf) {
+    let entries = read_entries(&entry);
+
+    testing::run_test2(false, |cm, _| {
+        let globals = Globals::default();
+        let mut bundler = Bundler::new(
+            &globals,
+            cm.clone(),
+            Loader { cm: cm.clone() },
+            NodeResolver,
+            Default::default(),
+            Box::new(Hook),
+        );
+
+        let bundles = bundler.bundle(entries).unwrap();
+
+        for bundle in bundles {
+            let mut analysis = analyze_bundle(cm.clone(), &bundle, Default::default()).unwrap();
+            // Use paths relative to the fixture.
+            for module in &mut analysis.modules {
+                if let Ok(path) = Path::new(&module.file_name).strip_prefix(&entry) {
+                    module.file_name = path.display().to_string();
+                }
+            }
+
+            let output_path = entry
+                .parent()
+                .unwrap()
+                .join("output")
+                .join(&bundle.file_name)
+                .with_extension("analysis.json");
+
+            NormalizedOutput::from(serde_json::to_string_pretty(&analysis).unwrap())
+                .compare_to_file(output_path)
+                .unwrap();
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+fn read_entries(dir: &Path) -> HashMap<String, FileName> {
+    read_dir(dir)
         .unwrap()
         .filter(|e| match e {
             Ok(e) => e
@@ -133,10 +354,7 @@ fn pass(entry: PathBuf) {
             ))
         })
         .collect::<Result<HashMap<_, _>, _>>()
-        .unwrap();
-
-    do_test(entry.parent().unwrap(), entries.clone(), true);
-    do_test(entry.parent().unwrap(), entries, false);
+        .unwrap()
 }
 
 struct Hook;
@@ -0,0 +1,8 @@
+import { shared } from './shared';
+
+console.log(shared('a'));
+
+export async function loadLazy() {
+    const { lazy } = await import('./lazy');
+    return lazy();
+}
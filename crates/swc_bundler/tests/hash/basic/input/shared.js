@@ -0,0 +1,3 @@
+export function shared(name) {
+    return `shared by ${name}`;
+}
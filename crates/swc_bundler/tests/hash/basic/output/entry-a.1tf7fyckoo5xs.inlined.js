@@ -0,0 +1,7 @@
+import { shared as shared } from "./chunks/shared.2br26xpeaicq6.js";
+console.log(shared('a'));
+async function loadLazy() {
+    const { lazy } = await import("./chunks/lazy.3rcc9k11dk7aj.js");
+    return lazy();
+}
+export { loadLazy as loadLazy };
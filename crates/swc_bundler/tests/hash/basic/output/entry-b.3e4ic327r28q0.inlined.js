@@ -0,0 +1,2 @@
+import { shared as shared } from "./chunks/shared.2br26xpeaicq6.js";
+console.log(shared('b'));
@@ -0,0 +1,5 @@
+import { shared as shared } from "./shared.2br26xpeaicq6.js";
+function lazy() {
+    return shared('lazy');
+}
+export { lazy as lazy };
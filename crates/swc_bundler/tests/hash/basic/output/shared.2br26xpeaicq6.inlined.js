@@ -0,0 +1,4 @@
+function shared(name) {
+    return `shared by ${name}`;
+}
+export { shared as shared };
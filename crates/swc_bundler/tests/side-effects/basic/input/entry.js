@@ -0,0 +1,4 @@
+import { a } from './lib';
+import { used } from './pure';
+
+console.log(a(), used);
@@ -0,0 +1,9 @@
+console.log('a is loaded');
+
+export function a() {
+    return 'a';
+}
+
+export function unusedA() {
+    return 'unused';
+}
@@ -0,0 +1,5 @@
+console.log('b is loaded');
+
+export function b() {
+    return 'b';
+}
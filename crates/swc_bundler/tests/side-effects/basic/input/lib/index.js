@@ -0,0 +1,5 @@
+import './polyfill';
+import './style.css';
+
+export { a } from './a';
+export { b } from './b';
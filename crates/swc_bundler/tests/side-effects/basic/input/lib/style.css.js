@@ -0,0 +1 @@
+document.head.append(document.createElement('style'));
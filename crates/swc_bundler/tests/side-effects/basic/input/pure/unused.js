@@ -0,0 +1,4 @@
+const registry = [];
+registry.push('unused');
+
+export const unused = registry;
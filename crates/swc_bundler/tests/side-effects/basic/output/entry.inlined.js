@@ -0,0 +1,8 @@
+globalThis.polyfilled = true;
+document.head.append(document.createElement('style'));
+console.log('a is loaded');
+function a() {
+    return 'a';
+}
+const used = 'used';
+console.log(a(), used);
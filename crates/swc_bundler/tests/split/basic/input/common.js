@@ -0,0 +1,10 @@
+import { helper } from './helper';
+
+export let counter = 0;
+
+export function shared() {
+    counter++;
+    return helper();
+}
+
+export default 'def';
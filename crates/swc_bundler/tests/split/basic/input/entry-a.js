@@ -0,0 +1,9 @@
+import { shared, counter } from './common';
+import def from './common';
+
+console.log('a', shared(), counter, def);
+
+export async function loadLazy() {
+    const { lazy } = await import('./lazy');
+    return lazy();
+}
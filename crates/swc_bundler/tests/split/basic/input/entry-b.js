@@ -0,0 +1,3 @@
+import { shared } from './common';
+
+console.log('b', shared());
@@ -0,0 +1,6 @@
+import { helper } from './helper';
+import { onlyLazy } from './only-lazy';
+
+export function lazy() {
+    return helper() + onlyLazy;
+}
@@ -0,0 +1,8 @@
+import { helper as helper } from "./helper-1syrxn2bwnhv5.js";
+let counter = 0;
+function shared() {
+    counter++;
+    return helper();
+}
+const __default = 'def';
+export { shared as shared, counter as counter, __default as __default };
@@ -0,0 +1,7 @@
+import { shared as shared, counter as counter, __default as __default } from "./common-1e6nohm0i33bc.js";
+console.log('a', shared(), counter, __default);
+async function loadLazy() {
+    const { lazy } = await import("./lazy.js");
+    return lazy();
+}
+export { loadLazy as loadLazy };
@@ -0,0 +1,2 @@
+import { shared as shared } from "./common-1e6nohm0i33bc.js";
+console.log('b', shared());
@@ -0,0 +1,4 @@
+function helper() {
+    return 'helper';
+}
+export { helper as helper };
@@ -0,0 +1,6 @@
+import { helper as helper } from "./helper-1syrxn2bwnhv5.js";
+const onlyLazy = 'lazy';
+function lazy() {
+    return helper() + onlyLazy;
+}
+export { lazy as lazy };
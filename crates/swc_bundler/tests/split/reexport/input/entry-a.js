@@ -0,0 +1,4 @@
+import { x, y } from './reexport';
+import * as ns from './src';
+
+console.log('a', x, y, ns);
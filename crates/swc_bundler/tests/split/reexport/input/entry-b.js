@@ -0,0 +1,5 @@
+import { x, y } from './reexport';
+import cjs from './cjs';
+
+console.log('b', x, y, cjs);
+import('./cjs').then((m) => console.log(m));
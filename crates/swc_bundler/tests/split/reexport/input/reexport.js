@@ -0,0 +1,2 @@
+export { x } from './src';
+export * from './star';
@@ -0,0 +1,2 @@
+import { x as x, y as y, mod as mod } from "./src-rfwzyymw1gd4.js";
+console.log('a', x, y, mod);
@@ -0,0 +1,2 @@
+import { x as x, y as y, mod as mod } from "./src-26r4nvttmgce2.js";
+console.log('a', x, y, mod);
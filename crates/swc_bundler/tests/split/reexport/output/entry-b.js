@@ -0,0 +1,38 @@
+function __swcpack_require__(mod) {
+    function interop(obj) {
+        if (obj && obj.__esModule) {
+            return obj;
+        } else {
+            var newObj = {};
+            if (obj != null) {
+                for(var key in obj){
+                    if (Object.prototype.hasOwnProperty.call(obj, key)) {
+                        var desc = Object.defineProperty && Object.getOwnPropertyDescriptor ? Object.getOwnPropertyDescriptor(obj, key) : {};
+                        if (desc.get || desc.set) {
+                            Object.defineProperty(newObj, key, desc);
+                        } else {
+                            newObj[key] = obj[key];
+                        }
+                    }
+                }
+            }
+            newObj.default = obj;
+            return newObj;
+        }
+    }
+    var cache;
+    if (cache) {
+        return cache;
+    }
+    var module = {
+        exports: {}
+    };
+    mod(module, module.exports);
+    cache = interop(module.exports);
+    return cache;
+}
+import { load as load } from "./cjs.js";
+import { x as x, y as y } from "./src-26r4nvttmgce2.js";
+var { default: cjs } = load();
+console.log('b', x, y, cjs);
+import("./cjs.js").then((m)=>console.log(m));
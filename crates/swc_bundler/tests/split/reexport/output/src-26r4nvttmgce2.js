@@ -0,0 +1,8 @@
+const x = 1;
+const z = 2;
+const mod = {
+    x: x,
+    z: z
+};
+const y = 3;
+export { x as x, y as y, mod as mod };
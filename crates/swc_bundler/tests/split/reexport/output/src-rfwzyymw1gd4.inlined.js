@@ -0,0 +1,9 @@
+const x = 1;
+const mod = function() {
+    return {
+        x: 1,
+        z: 2
+    };
+}();
+const y = 3;
+export { x as x, y as y, mod as mod };
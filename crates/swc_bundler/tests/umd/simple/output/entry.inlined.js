@@ -0,0 +1,47 @@
+(function(global, factory) {
+    if (typeof module === "object" && typeof module.exports === "object") factory(exports, require("fs"), require("path"));
+    else if (typeof define === "function" && define.amd) define([
+        "exports",
+        "fs",
+        "path"
+    ], factory);
+    else if (global = typeof globalThis !== "undefined" ? globalThis : global || self) factory(global.entry = {}, global.fs, global.path);
+})(this, function(exports, _fs, _path) {
+    "use strict";
+    Object.defineProperty(exports, "__esModule", {
+        value: true
+    });
+    function _export(target, all) {
+        for(var name in all)Object.defineProperty(target, name, {
+            enumerable: true,
+            get: all[name]
+        });
+    }
+    _export(exports, {
+        default: function() {
+            return Reader;
+        },
+        greet: function() {
+            return greet;
+        },
+        read: function() {
+            return read;
+        }
+    });
+    _path = _interop_require_default(_path);
+    function _interop_require_default(obj) {
+        return obj && obj.__esModule ? obj : {
+            default: obj
+        };
+    }
+    const greet = (name)=>`Hello, ${name}`;
+    function read(file) {
+        return (0, _fs.readFileSync)(_path.default.join(__dirname, file), 'utf8');
+    }
+    var Reader;
+    Reader = class Reader {
+        read(file) {
+            return greet(read(file));
+        }
+    };
+});
@@ -1,12 +1,526 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
 use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use swc_core::{
+    atoms::JsWord,
+    base::{
+        config::{Options, SourceMapsConfig},
+        try_with_handler, Compiler, HandlerOpts, TransformOutput,
+    },
+    bundler::{
+        analyze_bundle,
+        node::{
+            loaders::swc::SwcLoader,
+            v1::{Config as SpackConfig, EntryConfig},
+        },
+        render_analysis_html, Bundler, Load, Manifest, ModuleRecord, ModuleType, Resolve,
+    },
+    common::{errors::ColorConfig, sync::Lazy, FilePathMapping, Globals, SourceMap, Span, GLOBALS},
+    ecma::{
+        ast::{
+            Bool, Expr, Ident, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr,
+            MetaPropKind, PropName, Str,
+        },
+        loader::{
+            resolvers::{
+                import_map::ImportMapResolver, lru::CachingResolver, node::NodeModulesResolver,
+                pnp::PnpManifest, tsc::TsConfigResolver,
+            },
+            TargetEnv, NODE_BUILTINS,
+        },
+    },
+    trace_macro::swc_trace,
+};
+
+use crate::util::trace::init_trace;
 
+/// Configuration option for bundling files.
 #[derive(Parser)]
-pub struct BundleOptions {}
+pub struct BundleOptions {
+    /// Path to a spack config file. Only JSON configs are supported, a
+    /// `spack.config.js` needs to be converted to its JSON equivalent.
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// The output directory. Overrides `output.path` of the config file.
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
+
+    /// Target environment of the bundle. Values: browser|node
+    #[clap(long, value_parser = parse_target_env)]
+    target: Option<TargetEnv>,
+
+    /// Module format of the emitted chunks. Values: es|iife|commonjs|umd
+    #[clap(long, value_parser = parse_module_type)]
+    module_type: Option<ModuleType>,
+
+    /// Emit a source map next to each chunk.
+    #[clap(long)]
+    source_maps: bool,
+
+    /// Minify the emitted chunks.
+    #[clap(long)]
+    minify: bool,
+
+    /// Conditions used to resolve `exports` and `imports` of package.json,
+    /// e.g. `--conditions import,development`. `default` always matches.
+    /// Defaults to `node,import` or `browser,import` depending on the target.
+    #[clap(long, value_delimiter = ',')]
+    conditions: Vec<String>,
+
+    /// Path to an import map, which is applied before resolving modules
+    /// from `node_modules`. See https://github.com/WICG/import-maps
+    #[clap(long)]
+    import_map: Option<PathBuf>,
+
+    /// Emit targets of `import()` as separate chunks and move modules shared
+    /// by multiple chunks into common chunks. Same as
+    /// `optimization.splitChunks` of the config file.
+    #[clap(long)]
+    code_splitting: bool,
+
+    /// File name pattern of chunks created by code splitting, e.g.
+    /// `chunks/[name].[hash].js`. `[name]`, `[ext]` and `[hash]` are
+    /// replaced. Entries use `output.name` of the config file.
+    #[clap(long)]
+    chunk_file_names: Option<String>,
+
+    /// Write `manifest.json` to the output directory, which maps entries and
+    /// chunks to the emitted files and their dependencies.
+    #[clap(long)]
+    manifest: bool,
+
+    /// Write `tree-shaking.json` to the output directory, which lists the
+    /// modules retained in each chunk and the exports removed by tree
+    /// shaking.
+    #[clap(long)]
+    tree_shaking_report: bool,
+
+    /// Write `analysis.json` and `analysis.html` to the output directory,
+    /// which show the size of each module and package in the chunks.
+    #[clap(long)]
+    analyze: bool,
+
+    /// Entry files. Overrides `entry` of the config file.
+    entries: Vec<String>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(group = "experimental_trace", long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(group = "experimental_trace", long)]
+    trace_out_file: Option<String>,
+}
+
+fn parse_target_env(s: &str) -> Result<TargetEnv, serde_json::Error> {
+    serde_json::from_value(serde_json::Value::String(s.to_string()))
+}
+
+fn parse_module_type(s: &str) -> anyhow::Result<ModuleType> {
+    Ok(match s {
+        "es" => ModuleType::Es,
+        "iife" => ModuleType::Iife,
+        "commonjs" => ModuleType::CommonJs,
+        "umd" => ModuleType::Umd,
+        _ => bail!("unknown module type `{}`", s),
+    })
+}
+
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+
+    Arc::new(Compiler::new(cm))
+});
+
+/// Name of the config file used if `--config-file` is not specified.
+const DEFAULT_CONFIG_FILE: &str = "spack.config.json";
+
+#[swc_trace]
+impl BundleOptions {
+    /// Reads the spack config and applies the overrides given on the command
+    /// line.
+    fn build_config(&self) -> anyhow::Result<SpackConfig> {
+        let config_file = match &self.config_file {
+            Some(config_file) => Some(config_file.clone()),
+            None => {
+                let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+                default.is_file().then_some(default)
+            }
+        };
+
+        let mut config = match config_file {
+            Some(config_file) => {
+                if matches!(
+                    config_file.extension().and_then(|ext| ext.to_str()),
+                    Some("js" | "cjs" | "mjs")
+                ) {
+                    bail!(
+                        "JavaScript config files are not supported by the swc cli, convert `{}` \
+                         to JSON instead",
+                        config_file.display()
+                    );
+                }
+
+                let content = fs::read_to_string(&config_file).with_context(|| {
+                    format!("failed to read config file {}", config_file.display())
+                })?;
+
+                let mut config: SpackConfig =
+                    serde_json::from_str(&content).with_context(|| {
+                        format!("failed to parse config file {}", config_file.display())
+                    })?;
+
+                if !self.entries.is_empty() {
+                    config.entry = EntryConfig::Multiple(self.entries.clone());
+                }
+
+                config
+            }
+            None => {
+                if self.entries.is_empty() {
+                    bail!(
+                        "No entry specified. Pass entry files or provide a `{}`",
+                        DEFAULT_CONFIG_FILE
+                    );
+                }
+
+                SpackConfig {
+                    working_dir: Default::default(),
+                    mode: Default::default(),
+                    target: Default::default(),
+                    entry: EntryConfig::Multiple(self.entries.clone()),
+                    output: None,
+                    module: Default::default(),
+                    optimization: None,
+                    resolve: None,
+                    options: None,
+                    external_modules: Default::default(),
+                    alias: Default::default(),
+                    preserve_symlinks: false,
+                }
+            }
+        };
+
+        if let Some(target) = self.target {
+            config.target = target;
+        }
+
+        Ok(config)
+    }
+
+    fn build_loader(&self, compiler: Arc<Compiler>, config: &SpackConfig) -> Box<dyn Load> {
+        let options = config.options.as_ref().cloned().unwrap_or_else(|| {
+            // `Options::default()` differs from the defaults of serde.
+            serde_json::from_value::<Options>(serde_json::Value::Object(Default::default()))
+                .unwrap()
+        });
+
+        Box::new(SwcLoader::new(compiler, options))
+    }
+
+    fn build_resolver(&self, config: &SpackConfig) -> anyhow::Result<Box<dyn Resolve>> {
+        let alias = config
+            .alias
+            .get(&config.target)
+            .cloned()
+            .unwrap_or_default();
+
+        // Packages of Yarn Plug'n'Play projects are resolved without `node_modules`.
+        let pnp = PnpManifest::find(&env::current_dir()?)?.map(Arc::new);
+
+        let node_resolver = |resolver: NodeModulesResolver| {
+            let resolver = match &pnp {
+                Some(pnp) => resolver.with_pnp(pnp.clone()),
+                None => resolver,
+            };
+
+            if self.conditions.is_empty() {
+                resolver
+            } else {
+                resolver.with_conditions(self.conditions.clone())
+            }
+        };
+
+        let resolver: Box<dyn Resolve> = match config.options.as_ref() {
+            Some(options) => {
+                let paths = options
+                    .config
+                    .jsc
+                    .paths
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+
+                Box::new(CachingResolver::new(
+                    40,
+                    TsConfigResolver::new(
+                        node_resolver(NodeModulesResolver::without_node_modules(
+                            config.target,
+                            alias,
+                            config.preserve_symlinks,
+                        )),
+                        options.config.jsc.base_url.clone(),
+                        paths,
+                    ),
+                ))
+            }
+            None => Box::new(CachingResolver::new(
+                40,
+                node_resolver(NodeModulesResolver::new(
+                    config.target,
+                    alias,
+                    config.preserve_symlinks,
+                )),
+            )),
+        };
+
+        Ok(match &self.import_map {
+            Some(path) => Box::new(ImportMapResolver::from_file(resolver, path)?),
+            None => resolver,
+        })
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let config = self.build_config()?;
+
+        let out_dir = match (&self.out_dir, &config.output) {
+            (Some(out_dir), _) => out_dir.clone(),
+            (None, Some(output)) => output.path.clone(),
+            (None, None) => bail!("No output directory specified. Use `--out-dir`"),
+        };
+        let entry_file_names = config
+            .output
+            .as_ref()
+            .map(|output| output.name.clone())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "[name].js".into());
+
+        let code_splitting = self.code_splitting || config.split_chunks();
+
+        let compiler = COMPILER.clone();
+        let loader = self.build_loader(compiler.clone(), &config);
+        let resolver = self.build_resolver(&config)?;
+
+        let mut external_modules: Vec<JsWord> = config.external_modules.clone();
+        if let TargetEnv::Node = config.target {
+            external_modules.extend(NODE_BUILTINS.iter().copied().map(JsWord::from));
+        }
+
+        // Defaults to es3
+        let codegen_target = config.codegen_target().unwrap_or_default();
+        let minify = self.minify
+            || config
+                .options
+                .as_ref()
+                .map(|v| v.config.minify.into_bool())
+                .unwrap_or(false);
+
+        let (outputs, manifest, tree_shaking, analysis) = try_with_handler(
+            compiler.cm.clone(),
+            HandlerOpts {
+                color: ColorConfig::Always,
+                skip_filename: false,
+            },
+            |_handler| {
+                let globals = Globals::default();
+                GLOBALS.set(&globals, || {
+                    let mut bundler = Bundler::new(
+                        &globals,
+                        compiler.cm.clone(),
+                        &loader,
+                        &resolver,
+                        swc_core::bundler::Config {
+                            require: true,
+                            external_modules,
+                            code_splitting,
+                            module: self.module_type.unwrap_or_default(),
+                            entry_file_names: Some(entry_file_names),
+                            chunk_file_names: self.chunk_file_names.clone(),
+                            tree_shaking_report: self.tree_shaking_report,
+                            ..Default::default()
+                        },
+                        Box::new(Hook),
+                    );
+
+                    let bundles = bundler.bundle(config.entry.clone().into())?;
+                    let manifest = self.manifest.then(|| Manifest::new(&bundles));
+                    let tree_shaking = self.tree_shaking_report.then(|| {
+                        bundles
+                            .iter()
+                            .map(|bundle| (bundle.file_name.clone(), bundle.tree_shaking.clone()))
+                            .collect::<BTreeMap<_, _>>()
+                    });
+
+                    let codegen_config = swc_core::ecma::codegen::Config::default()
+                        .with_target(codegen_target)
+                        .with_minify(minify);
+                    let analysis = if self.analyze {
+                        Some(
+                            bundles
+                                .iter()
+                                .map(|bundle| {
+                                    analyze_bundle(compiler.cm.clone(), bundle, codegen_config)
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?,
+                        )
+                    } else {
+                        None
+                    };
+
+                    let outputs = bundles
+                        .into_iter()
+                        .map(|bundle| {
+                            let output_file_name = bundle.file_name;
+
+                            let output = compiler.print(
+                                &bundle.module,
+                                None,
+                                Some(out_dir.join(&output_file_name)),
+                                true,
+                                SourceMapsConfig::Bool(self.source_maps),
+                                &Default::default(),
+                                None,
+                                None,
+                                true,
+                                Default::default(),
+                                codegen_config,
+                            )?;
+
+                            Ok((output_file_name, output))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    Ok((outputs, manifest, tree_shaking, analysis))
+                })
+            },
+        )?;
+
+        fs::create_dir_all(&out_dir)?;
+
+        for (output_file_name, output) in outputs {
+            emit_chunk(&out_dir, &output_file_name, output)?;
+        }
+
+        if let Some(manifest) = manifest {
+            let manifest_path = out_dir.join("manifest.json");
+
+            fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+                .with_context(|| format!("failed to write manifest {}", manifest_path.display()))?;
+        }
+
+        if let Some(tree_shaking) = tree_shaking {
+            let report_path = out_dir.join("tree-shaking.json");
+
+            fs::write(&report_path, serde_json::to_string_pretty(&tree_shaking)?).with_context(
+                || {
+                    format!(
+                        "failed to write tree shaking report {}",
+                        report_path.display()
+                    )
+                },
+            )?;
+        }
+
+        if let Some(analysis) = analysis {
+            let json_path = out_dir.join("analysis.json");
+            fs::write(&json_path, serde_json::to_string_pretty(&analysis)?)
+                .with_context(|| format!("failed to write analysis {}", json_path.display()))?;
+
+            let html_path = out_dir.join("analysis.html");
+            fs::write(&html_path, render_analysis_html(&analysis))
+                .with_context(|| format!("failed to write analysis {}", html_path.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn emit_chunk(out_dir: &Path, file_name: &str, mut output: TransformOutput) -> anyhow::Result<()> {
+    let output_file_path = out_dir.join(file_name);
+
+    if let Some(parent) = output_file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if let Some(source_map) = &output.map {
+        let source_map_path = PathBuf::from(format!("{}.map", output_file_path.display()));
+
+        output.code.push_str("\n//# sourceMappingURL=");
+        output
+            .code
+            .push_str(&source_map_path.file_name().unwrap().to_string_lossy());
+
+        fs::write(&source_map_path, source_map)
+            .with_context(|| format!("failed to write source map {}", source_map_path.display()))?;
+    }
+
+    fs::write(&output_file_path, &output.code)
+        .with_context(|| format!("failed to write chunk {}", output_file_path.display()))
+}
 
 #[swc_trace]
 impl super::CommandRunner for BundleOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Bundle command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
+    }
+}
+
+struct Hook;
+
+impl swc_core::bundler::Hook for Hook {
+    fn get_import_meta_props(
+        &self,
+        span: Span,
+        module_record: &ModuleRecord,
+    ) -> Result<Vec<KeyValueProp>, Error> {
+        let file_name = module_record.file_name.to_string();
+
+        Ok(vec![
+            KeyValueProp {
+                key: PropName::Ident(Ident::new("url".into(), span)),
+                value: Box::new(Expr::Lit(Lit::Str(Str {
+                    span,
+                    raw: None,
+                    value: file_name.into(),
+                }))),
+            },
+            KeyValueProp {
+                key: PropName::Ident(Ident::new("main".into(), span)),
+                value: Box::new(if module_record.is_entry {
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj: Box::new(Expr::MetaProp(MetaPropExpr {
+                            span,
+                            kind: MetaPropKind::ImportMeta,
+                        })),
+                        prop: MemberProp::Ident(Ident::new("main".into(), span)),
+                    })
+                } else {
+                    Expr::Lit(Lit::Bool(Bool { span, value: false }))
+                }),
+            },
+        ])
     }
 }
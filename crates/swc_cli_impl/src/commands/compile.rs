@@ -3,28 +3,36 @@ use std::{
     fs::{self, File},
     io::{self, Read, Write},
     path::{Component, Path, PathBuf},
-    sync::Arc,
+    sync::{mpsc, Arc},
+    time::Duration,
 };
 
 use anyhow::Context;
 use clap::Parser;
-use glob::glob;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
 use path_absolutize::Absolutize;
 use rayon::prelude::*;
-use relative_path::RelativePath;
 use swc_core::{
     base::{
         config::{Config, ConfigFile, Options, PluginConfig, SourceMapsConfig},
-        try_with_handler, Compiler, HandlerOpts, TransformOutput,
+        Compiler, TransformOutput,
     },
     common::{
-        errors::ColorConfig, sync::Lazy, FileName, FilePathMapping, SourceFile, SourceMap, GLOBALS,
+        collections::{AHashMap, AHashSet},
+        errors::{Handler, HANDLER},
+        sync::Lazy,
+        FileName, FilePathMapping, SourceFile, SourceMap, GLOBALS,
     },
     trace_macro::swc_trace,
 };
-use walkdir::WalkDir;
+use swc_error_reporters::{GraphicalReportHandler, PrettyEmitter, PrettyEmitterConfig};
 
-use crate::util::trace::init_trace;
+use crate::util::{
+    cache::CompileCache,
+    diagnostics::DiagnosticsBuffer,
+    files::{get_files_list, resolve_output_file_path, DEFAULT_EXTENSIONS},
+    trace::init_trace,
+};
 
 /// Configuration option for transform files.
 #[derive(Parser)]
@@ -34,7 +42,7 @@ pub struct CompileOptions {
     /// including plugin imports that are explicitly relative, starting with `.`
     /// or `..`
     #[clap(long = "config-json", value_parser = parse_config)]
-    config: Option<Config>,
+    config: Option<ConfigJson>,
 
     /// Path to a .swcrc file to use
     #[clap(long)]
@@ -90,6 +98,12 @@ pub struct CompileOptions {
     #[clap(group = "input")]
     files: Vec<PathBuf>,
 
+    /// Directory of the on-disk compile cache. Files whose content, config
+    /// and plugins are unchanged since the last run are read from the cache
+    /// instead of being compiled again.
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+
     /// Use a specific extension for the output files
     #[clap(long, default_value_t= String::from("js"))]
     out_file_extension: String,
@@ -111,8 +125,19 @@ pub struct CompileOptions {
      *no_swcrc: bool, */
 }
 
-fn parse_config(s: &str) -> Result<Config, serde_json::Error> {
-    serde_json::from_str(s)
+/// The value of `--config-json`. The JSON text is kept for the keys of the
+/// compile cache, as [Config] is not serializable.
+#[derive(Clone)]
+struct ConfigJson {
+    json: String,
+    config: Config,
+}
+
+fn parse_config(s: &str) -> Result<ConfigJson, serde_json::Error> {
+    Ok(ConfigJson {
+        json: s.to_string(),
+        config: serde_json::from_str(s)?,
+    })
 }
 
 static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
@@ -121,96 +146,6 @@ static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
     Arc::new(Compiler::new(cm))
 });
 
-/// List of file extensions supported by default.
-static DEFAULT_EXTENSIONS: &[&str] = &["js", "jsx", "es6", "es", "mjs", "ts", "tsx", "cts", "mts"];
-
-/// Infer list of files to be transformed from cli arguments.
-/// If given input is a directory, it'll traverse it and collect all supported
-/// files.
-#[tracing::instrument(level = "info", skip_all)]
-fn get_files_list(
-    raw_files_input: &[PathBuf],
-    extensions: &[String],
-    ignore_pattern: Option<&str>,
-    _include_dotfiles: bool,
-) -> anyhow::Result<Vec<PathBuf>> {
-    let input_dir = raw_files_input.iter().find(|p| p.is_dir());
-
-    let files = if let Some(input_dir) = input_dir {
-        if raw_files_input.len() > 1 {
-            return Err(anyhow::anyhow!(
-                "Cannot specify multiple files when using a directory as input"
-            ));
-        }
-
-        WalkDir::new(input_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .map(|e| e.into_path())
-            .filter(|e| {
-                extensions
-                    .iter()
-                    .any(|ext| e.extension().map(|v| v == &**ext).unwrap_or(false))
-            })
-            .collect()
-    } else {
-        raw_files_input.to_owned()
-    };
-
-    if let Some(ignore_pattern) = ignore_pattern {
-        let pattern: Vec<PathBuf> = glob(ignore_pattern)?.filter_map(|p| p.ok()).collect();
-
-        return Ok(files
-            .into_iter()
-            .filter(|file_path| !pattern.iter().any(|p| p.eq(file_path)))
-            .collect());
-    }
-
-    Ok(files)
-}
-
-/// Calculate full, absolute path to the file to emit.
-/// Currently this is quite naive calculation based on assumption input file's
-/// path and output dir are relative to the same directory.
-fn resolve_output_file_path(
-    out_dir: &Path,
-    file_path: &Path,
-    file_extension: PathBuf,
-) -> anyhow::Result<PathBuf> {
-    let default = PathBuf::from(".");
-    let base = file_path.parent().unwrap_or(&default).display().to_string();
-
-    let dist_absolute_path = out_dir.absolutize()?;
-
-    // These are possible combinations between input to output dir.
-    // cwd: /c/github/swc
-    //
-    // Input
-    // 1. Relative to cwd                   : ./crates/swc/tests/serde/a.js
-    // 2. Relative to cwd, traverse up      : ../repo/some/dir/b.js
-    // 3. Absolute path, relative to cwd: /c/github/swc/crates/swc/tests/serde/a.js
-    // 4. Absolute path, not relative to cwd: /c/github/repo/some/dir/b.js
-    //
-    // OutDir
-    // a. Relative to cwd: ./dist
-    // b. Relative to cwd, traverse up: ../outer_dist
-    // c. Absolute path: /c/github/swc/dist
-    // d. Absolute path, not relative to cwd: /c/github/outer_dist
-    //
-    // It is unclear how to calculate output path when either input or output is not
-    // relative to cwd (2,4 and b,d) and it is UB for now.
-    let base = RelativePath::new(&*base);
-    let output_path = base.to_logical_path(dist_absolute_path).join(
-        // Custom output file extension is not supported yet
-        file_path
-            .with_extension(file_extension)
-            .file_name()
-            .expect("Filename should be available"),
-    );
-
-    Ok(output_path)
-}
-
 fn emit_output(
     mut output: TransformOutput,
     source_file_name: &Option<String>,
@@ -294,7 +229,11 @@ impl CompileOptions {
         });
 
         let mut options = Options {
-            config: self.config.to_owned().unwrap_or_default(),
+            config: self
+                .config
+                .as_ref()
+                .map(|c| c.config.clone())
+                .unwrap_or_default(),
             config_file,
             ..Options::default()
         };
@@ -342,42 +281,74 @@ impl CompileOptions {
         Ok(options)
     }
 
+    /// Flags which affect the output of each file, as a part of the keys of the
+    /// compile cache.
+    fn cache_fingerprint(&self) -> String {
+        serde_json::json!({
+            "config": self.config.as_ref().map(|c| &c.json),
+            "configFile": self.config_file,
+            "envName": self.env_name,
+            "sourceMaps": self.source_maps,
+            "sourceFileName": self.source_file_name,
+            "sourceRoot": self.source_root,
+        })
+        .to_string()
+    }
+
+    /// Infer list of files to be compiled from `files` and `extensions`.
+    fn input_files(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let included_extensions = if let Some(extensions) = &self.extensions {
+            extensions.clone()
+        } else {
+            DEFAULT_EXTENSIONS.iter().map(|v| v.to_string()).collect()
+        };
+
+        get_files_list(
+            &self.files,
+            &included_extensions,
+            self.ignore.as_deref(),
+            false,
+        )
+    }
+
+    /// `fm` is the file loaded by a previous build, which is loaded again if
+    /// [None].
+    fn build_input(
+        &self,
+        compiler: &Arc<Compiler>,
+        file_path: &Path,
+        fm: Option<Arc<SourceFile>>,
+    ) -> anyhow::Result<InputContext> {
+        self.build_transform_options(&Some(file_path))
+            .and_then(|options| {
+                let fm = match fm {
+                    Some(fm) => Ok(fm),
+                    None => compiler
+                        .cm
+                        .load_file(file_path)
+                        .context(format!("Failed to open file {}", file_path.display())),
+                };
+                fm.map(|fm| InputContext {
+                    options,
+                    fm,
+                    compiler: compiler.clone(),
+                    file_path: file_path.to_path_buf(),
+                    file_extension: self.out_file_extension.clone().into(),
+                })
+            })
+    }
+
     /// Create canonical list of inputs to be processed across stdin / single
     /// file / multiple files.
     fn collect_inputs(&self) -> anyhow::Result<Vec<InputContext>> {
         let compiler = COMPILER.clone();
 
         if !self.files.is_empty() {
-            let included_extensions = if let Some(extensions) = &self.extensions {
-                extensions.clone()
-            } else {
-                DEFAULT_EXTENSIONS.iter().map(|v| v.to_string()).collect()
-            };
-
-            return get_files_list(
-                &self.files,
-                &included_extensions,
-                self.ignore.as_deref(),
-                false,
-            )?
-            .iter()
-            .map(|file_path| {
-                self.build_transform_options(&Some(file_path))
-                    .and_then(|options| {
-                        let fm = compiler
-                            .cm
-                            .load_file(file_path)
-                            .context(format!("Failed to open file {}", file_path.display()));
-                        fm.map(|fm| InputContext {
-                            options,
-                            fm,
-                            compiler: compiler.clone(),
-                            file_path: file_path.to_path_buf(),
-                            file_extension: self.out_file_extension.clone().into(),
-                        })
-                    })
-            })
-            .collect::<anyhow::Result<Vec<InputContext>>>();
+            return self
+                .input_files()?
+                .iter()
+                .map(|file_path| self.build_input(&compiler, file_path, None))
+                .collect::<anyhow::Result<Vec<InputContext>>>();
         }
 
         let stdin_input = collect_stdin_input();
@@ -413,21 +384,202 @@ impl CompileOptions {
     }
 
     fn execute_inner(&self) -> anyhow::Result<()> {
+        if self.watch && self.files.is_empty() {
+            anyhow::bail!("`--watch` requires input files");
+        }
+
         let inputs = self.collect_inputs()?;
+        let loaded = inputs
+            .iter()
+            .map(|input| (input.file_path.clone(), input.fm.clone()))
+            .collect();
+        let ret = self.compile_inputs(inputs);
+
+        if !self.watch {
+            return ret;
+        }
+
+        // Errors are reported, but they should not stop the watcher.
+        if let Err(err) = ret {
+            eprintln!("{:?}", err);
+        }
+
+        self.watch_inputs(loaded)
+    }
+
+    /// Keeps the compiler alive and recompiles the inputs affected by changes
+    /// on the file system.
+    ///
+    /// A change to the config file of the inputs recompiles all inputs, as the
+    /// config is resolved again for each file. `loaded` contains the files
+    /// loaded into the source map of the compiler, and only changed files are
+    /// loaded again.
+    fn watch_inputs(&self, mut loaded: AHashMap<PathBuf, Arc<SourceFile>>) -> anyhow::Result<()> {
+        let compiler = COMPILER.clone();
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+
+        for file in &self.files {
+            watcher.watch(file, RecursiveMode::Recursive)?;
+        }
+
+        let mut config_files = AHashSet::default();
+        self.watch_config_files(&mut watcher, &mut config_files)?;
+
+        let ignored_dir = self
+            .out_dir
+            .as_ref()
+            .map(|out_dir| out_dir.absolutize().map(|p| p.to_path_buf()))
+            .transpose()?;
+
+        eprintln!("Watching for file changes...");
+
+        while let Ok(event) = rx.recv() {
+            let mut changed = AHashSet::default();
+            collect_changed_paths(event, &mut changed);
 
+            // Editors usually emit a burst of events for a single save.
+            while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                collect_changed_paths(event, &mut changed);
+            }
+
+            changed.retain(|path| match &ignored_dir {
+                Some(ignored_dir) => !path.starts_with(ignored_dir),
+                None => true,
+            });
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            let config_changed = changed.iter().any(|path| config_files.contains(path));
+
+            let ret = self.input_files().and_then(|files| {
+                let is_changed = |file_path: &&PathBuf| {
+                    file_path
+                        .absolutize()
+                        .map(|p| changed.contains(&*p))
+                        .unwrap_or(false)
+                };
+
+                if !config_changed && !files.iter().any(|f| is_changed(&f)) {
+                    return Ok(());
+                }
+
+                let inputs = files
+                    .iter()
+                    // A single output file contains all inputs.
+                    .filter(|f| config_changed || self.out_file.is_some() || is_changed(f))
+                    .map(|file_path| {
+                        let fm = loaded
+                            .get(file_path)
+                            .filter(|_| !is_changed(&file_path))
+                            .cloned();
+                        let input = self.build_input(&compiler, file_path, fm)?;
+
+                        loaded.insert(file_path.clone(), input.fm.clone());
+
+                        Ok(input)
+                    })
+                    .collect::<anyhow::Result<Vec<InputContext>>>()?;
+
+                let count = inputs.len();
+                self.compile_inputs(inputs)?;
+
+                eprintln!(
+                    "Successfully compiled {} file{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                );
+
+                // New input files may use another config file.
+                self.watch_config_files(&mut watcher, &mut config_files)
+            });
+
+            if let Err(err) = ret {
+                eprintln!("{:?}", err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Watches the config files resolved for the input files, and adds their
+    /// absolute paths to `config_files`.
+    fn watch_config_files(
+        &self,
+        watcher: &mut impl Watcher,
+        config_files: &mut AHashSet<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let compiler = COMPILER.clone();
+
+        for file_path in self.input_files()? {
+            let options = self.build_transform_options(&Some(&file_path))?;
+            let config_file = match compiler.swcrc_path(&options, &FileName::Real(file_path)) {
+                Some(v) => v.absolutize()?.to_path_buf(),
+                None => continue,
+            };
+
+            if config_files.insert(config_file.clone()) {
+                watcher.watch(&config_file, RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn compile_inputs(&self, inputs: Vec<InputContext>) -> anyhow::Result<()> {
+        let cache = self
+            .cache_dir
+            .as_deref()
+            .map(|dir| CompileCache::new(dir, self.cache_fingerprint()))
+            .transpose()?;
+
+        // Diagnostics are buffered per file, so they can be stored in the
+        // cache and reported again when the entry is hit.
         let execute = |compiler: Arc<Compiler>, fm: Arc<SourceFile>, options: Options| {
-            try_with_handler(
+            let wr = DiagnosticsBuffer::default();
+            let emitter = PrettyEmitter::new(
                 compiler.cm.clone(),
-                HandlerOpts {
-                    color: ColorConfig::Always,
+                Box::new(wr.clone()),
+                GraphicalReportHandler::default().with_context_lines(3),
+                PrettyEmitterConfig {
                     skip_filename: false,
                 },
-                |handler| {
-                    GLOBALS.set(&Default::default(), || {
-                        compiler.process_js_file(fm, handler, &options)
-                    })
-                },
-            )
+            );
+            let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+            let process = || -> anyhow::Result<(TransformOutput, String)> {
+                let output = compiler.process_js_file(fm.clone(), &handler, &options)?;
+                // Outputs of files with errors are not cached.
+                if handler.has_errors() {
+                    anyhow::bail!("failed to process {}", fm.name);
+                }
+
+                Ok((output, wr.take()))
+            };
+
+            let ret = HANDLER.set(&handler, || {
+                GLOBALS.set(&Default::default(), || match &cache {
+                    Some(cache) => cache.get_or_insert_with(&compiler, &fm, &options, process),
+                    None => process(),
+                })
+            });
+
+            if handler.has_errors() {
+                let msg = wr.take();
+
+                return match ret {
+                    Ok(_) => Err(anyhow::anyhow!(msg)),
+                    Err(err) => Err(err.context(msg)),
+                };
+            }
+
+            let (output, diagnostics) = ret?;
+            eprint!("{}", diagnostics);
+
+            Ok(output)
         };
 
         if let Some(single_out_file) = self.out_file.as_ref() {
@@ -536,6 +688,31 @@ impl CompileOptions {
     }
 }
 
+/// Time to wait for more events after a change, before recompiling.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Collects absolute paths of created or modified files.
+fn collect_changed_paths(event: notify::Result<Event>, changed: &mut AHashSet<PathBuf>) {
+    let event = match event {
+        Ok(event) => event,
+        Err(err) => {
+            eprintln!("Failed to watch files: {:?}", err);
+            return;
+        }
+    };
+
+    if !matches!(event.kind, EventKind::Create(..) | EventKind::Modify(..)) {
+        return;
+    }
+
+    changed.extend(
+        event
+            .paths
+            .into_iter()
+            .filter_map(|path| path.absolutize().ok().map(|p| p.to_path_buf())),
+    );
+}
+
 // TODO: remove once fixed in core https://github.com/swc-project/swc/issues/1388
 fn extend_source_map(
     source_map: String,
@@ -1,12 +1,465 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
 use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use rayon::prelude::*;
+use serde::Serialize;
+use swc_core::{
+    base::{
+        config::{ConfigFile, Options},
+        Compiler,
+    },
+    common::{
+        comments::SingleThreadedComments,
+        errors::{Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
+        sync::Lazy,
+        FileName, FilePathMapping, Mark, SourceMap, SyntaxContext, GLOBALS,
+    },
+    css::{ast::Stylesheet, parser::parse_file},
+    ecma::{ast::Program, transforms::base::resolver, visit::VisitMutWith},
+    trace_macro::swc_trace,
+};
+use swc_css_lints::{
+    get_rules as get_css_rules, LintConfig as CssLintConfig, LintParams as CssLintParams, LintRule,
+};
+use swc_ecma_lints::{
+    fix::apply_fixes,
+    metrics::{function_metrics, FunctionMetrics},
+    rule::Rule,
+    rules::{all, LintParams},
+};
+use swc_error_reporters::{
+    GraphicalReportHandler, GraphicalTheme, PrettyEmitter, PrettyEmitterConfig,
+};
+
+use crate::util::{
+    diagnostics::DiagnosticsBuffer,
+    files::{get_files_list, DEFAULT_EXTENSIONS},
+    trace::init_trace,
+};
 
+/// Configuration option for linting files.
 #[derive(Parser)]
-pub struct LintOptions {}
+pub struct LintOptions {
+    /// Path to a .swcrc file to use. Lint rules are read from `jsc.lints`.
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// The name of the 'env' to use when loading configs. Defaults to the
+    /// value of SWC_ENV, or else NODE_ENV, or else development.
+    #[clap(long)]
+    env_name: Option<String>,
+
+    /// List of glob paths to not lint.
+    #[clap(long)]
+    ignore: Option<String>,
+
+    /// Specify specific file extensions to lint.
+    #[clap(long)]
+    extensions: Option<Vec<String>>,
+
+    /// Path to a JSON file with the rules for CSS files, in the format of
+    /// `swc_css_lints`, e.g. `{ "rules": { "block-no-empty": ["error"] } }`.
+    #[clap(long)]
+    css_config: Option<PathBuf>,
+
+    /// Apply the automatic fixes of reported problems and write the fixed
+    /// files back.
+    #[clap(long)]
+    fix: bool,
+
+    /// Write the complexity and size metrics of the functions of linted
+    /// files to a JSON file.
+    #[clap(long)]
+    metrics_out: Option<PathBuf>,
+
+    /// Disable colored output of diagnostics.
+    #[clap(long)]
+    no_color: bool,
+
+    /// Files or a directory to lint
+    #[clap(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(group = "experimental_trace", long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(group = "experimental_trace", long)]
+    trace_out_file: Option<String>,
+}
+
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+
+    Arc::new(Compiler::new(cm))
+});
+
+/// Collects the diagnostics of lint rules, so attached fixes can be applied
+/// before they are reported.
+#[derive(Clone, Default)]
+struct DiagnosticsCollector(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for DiagnosticsCollector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((**db).clone());
+    }
+}
+
+/// Metrics of the functions of a file, as written by `--metrics-out`.
+#[derive(Serialize)]
+struct FileMetrics {
+    file: PathBuf,
+    functions: Vec<FunctionMetrics>,
+}
+
+/// Upper bound of lint passes while fixing a file, as a fix may cause another
+/// problem which is fixed in the next pass.
+const MAX_FIX_PASSES: usize = 10;
+
+#[swc_trace]
+impl LintOptions {
+    fn build_options(&self, file_path: &Path) -> Options {
+        let config_file = self.config_file.as_ref().map(|config_file_path| {
+            ConfigFile::Str(config_file_path.to_string_lossy().to_string())
+        });
+
+        let mut options = Options {
+            config_file,
+            filename: file_path.to_string_lossy().to_string(),
+            swcrc: true,
+            ..Options::default()
+        };
+
+        if let Some(env_name) = &self.env_name {
+            options.env_name = env_name.to_string();
+        }
+
+        options
+    }
+
+    fn create_handler(&self, cm: Arc<SourceMap>, wr: DiagnosticsBuffer) -> Handler {
+        let reporter = if self.no_color {
+            GraphicalReportHandler::default().with_theme(GraphicalTheme::none())
+        } else {
+            GraphicalReportHandler::default()
+        }
+        .with_context_lines(3);
+
+        let emitter = PrettyEmitter::new(
+            cm,
+            Box::new(wr),
+            reporter,
+            PrettyEmitterConfig {
+                skip_filename: false,
+            },
+        );
+
+        Handler::with_emitter(true, false, Box::new(emitter))
+    }
+
+    fn read_css_config(&self) -> anyhow::Result<CssLintConfig> {
+        match &self.css_config {
+            Some(css_config) => {
+                let content = fs::read_to_string(css_config).with_context(|| {
+                    format!("failed to read css config {}", css_config.display())
+                })?;
+
+                serde_json::from_str(&content)
+                    .with_context(|| format!("failed to parse css config {}", css_config.display()))
+            }
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Lints a single CSS file and returns the number of reported errors.
+    ///
+    /// With `--fix`, the file is linted again after applying the fixes, until
+    /// there is nothing left to fix. Fixes are applied to the source text, so
+    /// comments and formatting are preserved.
+    fn lint_css_file(
+        &self,
+        compiler: &Compiler,
+        css_config: &CssLintConfig,
+        file_path: &Path,
+    ) -> anyhow::Result<usize> {
+        let mut fm = compiler
+            .cm
+            .load_file(file_path)
+            .with_context(|| format!("Failed to open file {}", file_path.display()))?;
+
+        let mut rules = get_css_rules(&CssLintParams {
+            lint_config: css_config,
+        })?;
+
+        let wr = DiagnosticsBuffer::default();
+        let handler = self.create_handler(compiler.cm.clone(), wr.clone());
+
+        let mut passes = 0;
+        let fixed = loop {
+            let mut errors = vec![];
+            let stylesheet: Stylesheet =
+                match parse_file(&fm, None, Default::default(), &mut errors) {
+                    Ok(stylesheet) => stylesheet,
+                    Err(err) => {
+                        err.to_diagnostics(&handler).emit();
+                        break None;
+                    }
+                };
+
+            let collector = DiagnosticsCollector::default();
+            {
+                let lint_handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+                HANDLER.set(&lint_handler, || {
+                    rules.lint_stylesheet(&stylesheet);
+                });
+            }
+            let diagnostics = collector.0.lock().unwrap().drain(..).collect::<Vec<_>>();
+
+            // Spans of recoverable parse errors may overlap with fixes, so such
+            // files are not fixed.
+            if self.fix && errors.is_empty() && passes < MAX_FIX_PASSES {
+                if let Some(src) = apply_fixes(&fm, &diagnostics) {
+                    fm = compiler
+                        .cm
+                        .new_source_file(FileName::Real(file_path.to_path_buf()), src);
+                    passes += 1;
+                    continue;
+                }
+            }
+
+            for err in errors {
+                err.to_diagnostics(&handler).emit();
+            }
+            for diagnostic in diagnostics {
+                DiagnosticBuilder::new_diagnostic(&handler, diagnostic).emit();
+            }
+
+            break (passes > 0).then(|| fm.src.clone());
+        };
+
+        if let Some(src) = fixed {
+            fs::write(file_path, src.as_bytes())
+                .with_context(|| format!("failed to write fixed file {}", file_path.display()))?;
+        }
+
+        let diagnostics = wr.0.lock().unwrap();
+        if !diagnostics.is_empty() {
+            io::stderr().write_all(diagnostics.as_bytes())?;
+        }
+
+        Ok(handler.err_count())
+    }
+
+    /// Lints a single file and returns the number of reported errors.
+    ///
+    /// With `--fix`, the file is linted again after applying the fixes, until
+    /// there is nothing left to fix. Unlike CSS files, the fixed source is
+    /// written as is, so the formatting of the file is preserved.
+    ///
+    /// The metrics of the functions of the file are pushed to `metrics`, if
+    /// any.
+    fn lint_file(
+        &self,
+        compiler: &Compiler,
+        file_path: &Path,
+        metrics: Option<&Mutex<Vec<FileMetrics>>>,
+    ) -> anyhow::Result<usize> {
+        let mut fm = compiler
+            .cm
+            .load_file(file_path)
+            .with_context(|| format!("Failed to open file {}", file_path.display()))?;
+
+        let options = self.build_options(file_path);
+        let name = FileName::Real(file_path.to_path_buf());
+
+        let mut config = options.config.clone();
+        if let Some(c) = compiler.read_config(&options, &name)? {
+            config = c;
+        }
+        config.adjust(file_path);
+
+        let syntax = config.jsc.syntax.unwrap_or_default();
+        let es_version = config.jsc.target.unwrap_or_default();
+        let is_module = config.is_module.unwrap_or_default();
+
+        let wr = DiagnosticsBuffer::default();
+        let handler = self.create_handler(compiler.cm.clone(), wr.clone());
+
+        let mut passes = 0;
+        let mut functions = None;
+        let fixed = loop {
+            let collector = DiagnosticsCollector::default();
+            let lint_handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+            // Returns the number of recoverable parse errors, or `None` if the
+            // file cannot be parsed.
+            let comments = SingleThreadedComments::default();
+            let parse_errors = HANDLER.set(&lint_handler, || {
+                let mut program = compiler
+                    .parse_js(
+                        fm.clone(),
+                        &lint_handler,
+                        es_version,
+                        syntax,
+                        is_module,
+                        Some(&comments),
+                    )
+                    .ok()?;
+                let parse_errors = collector.0.lock().unwrap().len();
+
+                let unresolved_mark = Mark::new();
+                let top_level_mark = Mark::new();
+
+                program.visit_mut_with(&mut resolver(
+                    unresolved_mark,
+                    top_level_mark,
+                    syntax.typescript(),
+                ));
+
+                let mut rules = all(LintParams {
+                    program: &program,
+                    lint_config: &config.jsc.lints,
+                    unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+                    top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                    es_version,
+                    source_map: compiler.cm.clone(),
+                    comments: Some(&comments),
+                });
+
+                match &program {
+                    Program::Module(m) => rules.lint_module(m),
+                    Program::Script(s) => rules.lint_script(s),
+                }
+
+                if metrics.is_some() {
+                    functions = Some(function_metrics(&program, &compiler.cm));
+                }
+
+                Some(parse_errors)
+            });
+            let diagnostics = collector.0.lock().unwrap().drain(..).collect::<Vec<_>>();
+
+            // Spans of recoverable parse errors may overlap with fixes, so such
+            // files are not fixed.
+            if self.fix && parse_errors == Some(0) && passes < MAX_FIX_PASSES {
+                if let Some(src) = apply_fixes(&fm, &diagnostics) {
+                    fm = compiler
+                        .cm
+                        .new_source_file(FileName::Real(file_path.to_path_buf()), src);
+                    passes += 1;
+                    continue;
+                }
+            }
+
+            for diagnostic in diagnostics {
+                DiagnosticBuilder::new_diagnostic(&handler, diagnostic).emit();
+            }
+
+            break (passes > 0).then(|| fm.src.clone());
+        };
+
+        if let Some(src) = fixed {
+            fs::write(file_path, src.as_bytes())
+                .with_context(|| format!("failed to write fixed file {}", file_path.display()))?;
+        }
+
+        if let (Some(metrics), Some(functions)) = (metrics, functions) {
+            metrics.lock().unwrap().push(FileMetrics {
+                file: file_path.to_path_buf(),
+                functions,
+            });
+        }
+
+        let diagnostics = wr.0.lock().unwrap();
+        if !diagnostics.is_empty() {
+            io::stderr().write_all(diagnostics.as_bytes())?;
+        }
+
+        Ok(handler.err_count())
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let included_extensions = if let Some(extensions) = &self.extensions {
+            extensions.clone()
+        } else {
+            DEFAULT_EXTENSIONS
+                .iter()
+                .chain(&["css"])
+                .map(|v| v.to_string())
+                .collect()
+        };
+
+        let files = get_files_list(
+            &self.files,
+            &included_extensions,
+            self.ignore.as_deref(),
+            false,
+        )?;
+
+        let css_config = self.read_css_config()?;
+        let compiler = COMPILER.clone();
+        let metrics = self.metrics_out.as_ref().map(|_| Mutex::new(vec![]));
+
+        let errors: usize = files
+            .par_iter()
+            .map(|file_path| {
+                GLOBALS.set(&Default::default(), || {
+                    if file_path.extension().map_or(false, |ext| ext == "css") {
+                        self.lint_css_file(&compiler, &css_config, file_path)
+                    } else {
+                        self.lint_file(&compiler, file_path, metrics.as_ref())
+                    }
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
+            .sum();
+
+        if let (Some(metrics_out), Some(metrics)) = (&self.metrics_out, metrics) {
+            let mut metrics = metrics.into_inner().unwrap();
+            metrics.sort_by(|a, b| a.file.cmp(&b.file));
+
+            fs::write(metrics_out, serde_json::to_string_pretty(&metrics)?)
+                .with_context(|| format!("failed to write metrics to {}", metrics_out.display()))?;
+        }
+
+        if errors > 0 {
+            anyhow::bail!(
+                "Found {} lint error{}",
+                errors,
+                if errors == 1 { "" } else { "s" }
+            );
+        }
+
+        Ok(())
+    }
+}
 
 #[swc_trace]
 impl super::CommandRunner for LintOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Lint command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
     }
 }
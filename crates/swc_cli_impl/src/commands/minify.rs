@@ -1,12 +1,482 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context};
 use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use rayon::prelude::*;
+use swc_core::{
+    base::{
+        config::{JsMinifyOptions, TerserSourceMapOption},
+        try_with_handler, BoolOr, BoolOrDataConfig, Compiler, HandlerOpts, TransformOutput,
+    },
+    common::{errors::ColorConfig, sync::Lazy, FilePathMapping, SourceMap, GLOBALS},
+    trace_macro::swc_trace,
+};
+
+use crate::util::{
+    files::{get_files_list, resolve_output_file_path},
+    trace::init_trace,
+};
 
+/// Configuration option for minifying files.
 #[derive(Parser)]
-pub struct MinifyOptions {}
+pub struct MinifyOptions {
+    /// Path to a JSON file with terser compatible minify options, i.e.
+    /// `compress`, `mangle`, `format`, `ecma`, `module`, ...
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// Generate a source map for each output file.
+    #[clap(long)]
+    source_map: bool,
+
+    /// Path to the source map of the input file. Only valid with a single
+    /// input file. Implies `--source-map`.
+    #[clap(long)]
+    input_source_map: Option<PathBuf>,
+
+    /// List of glob paths to not minify.
+    #[clap(long)]
+    ignore: Option<String>,
+
+    /// Specify specific file extensions to minify.
+    #[clap(long)]
+    extensions: Option<Vec<String>>,
+
+    /// The output file. Only valid with a single input file.
+    #[clap(long, short = 'o', group = "output")]
+    out_file: Option<PathBuf>,
+
+    /// The output directory
+    #[clap(long, group = "output")]
+    out_dir: Option<PathBuf>,
+
+    /// File to hold the names chosen by the mangler, in the format of
+    /// `nameCache` of terser. It's read before minifying and updated after.
+    /// Input files are minified one by one if this is given.
+    #[clap(long)]
+    name_cache: Option<PathBuf>,
+
+    /// File to write why the compressor kept code to, as a JSON object keyed
+    /// by input files.
+    #[clap(long)]
+    optimization_trace: Option<PathBuf>,
+
+    /// Files to minify
+    #[clap(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(group = "experimental_trace", long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(group = "experimental_trace", long)]
+    trace_out_file: Option<String>,
+}
+
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+
+    Arc::new(Compiler::new(cm))
+});
+
+/// List of file extensions minified by default. Unlike `compile`, the
+/// minifier only accepts JavaScript.
+static DEFAULT_EXTENSIONS: &[&str] = &["js", "mjs", "cjs"];
+
+#[swc_trace]
+impl MinifyOptions {
+    fn read_minify_options(&self) -> anyhow::Result<JsMinifyOptions> {
+        let mut options: JsMinifyOptions = match &self.config_file {
+            Some(config_file) => {
+                let content = fs::read_to_string(config_file).with_context(|| {
+                    format!("failed to read config file {}", config_file.display())
+                })?;
+
+                serde_json::from_str(&content).with_context(|| {
+                    format!("failed to parse config file {}", config_file.display())
+                })?
+            }
+            // `JsMinifyOptions::default()` differs from the defaults of serde.
+            None => serde_json::from_str("{}").unwrap(),
+        };
+
+        if let Some(input_source_map) = &self.input_source_map {
+            let content = fs::read_to_string(input_source_map).with_context(|| {
+                format!(
+                    "failed to read input source map {}",
+                    input_source_map.display()
+                )
+            })?;
+
+            let mut source_map = options
+                .source_map
+                .clone()
+                .into_inner()
+                .and_then(|v| match v {
+                    BoolOr::Data(v) => Some(v),
+                    BoolOr::Bool(..) => None,
+                })
+                .unwrap_or_default();
+            source_map.content = Some(content);
+
+            options.source_map = BoolOrDataConfig::from_obj(source_map);
+        } else if self.source_map && !options.source_map.is_obj() {
+            options.source_map = BoolOrDataConfig::from_obj(TerserSourceMapOption::default());
+        }
+
+        if let Some(name_cache) = &self.name_cache {
+            options.name_cache = Some(if name_cache.exists() {
+                let content = fs::read_to_string(name_cache).with_context(|| {
+                    format!("failed to read name cache {}", name_cache.display())
+                })?;
+
+                serde_json::from_str(&content).with_context(|| {
+                    format!("failed to parse name cache {}", name_cache.display())
+                })?
+            } else {
+                Default::default()
+            });
+        }
+
+        options.optimization_trace = self.optimization_trace.is_some();
+
+        Ok(options)
+    }
+
+    fn output_file_path(&self, file_path: &Path) -> anyhow::Result<Option<PathBuf>> {
+        if let Some(out_file) = &self.out_file {
+            return Ok(Some(out_file.clone()));
+        }
+
+        if let Some(out_dir) = &self.out_dir {
+            let file_extension = file_path
+                .extension()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("js"));
+
+            return resolve_output_file_path(out_dir, file_path, file_extension).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the extra output, like the updated name cache.
+    fn minify_file(
+        &self,
+        compiler: &Compiler,
+        options: &JsMinifyOptions,
+        file_path: &Path,
+    ) -> anyhow::Result<serde_json::Map<String, serde_json::Value>> {
+        let output_file_path = self.output_file_path(file_path)?;
+
+        let mut options = options.clone();
+        options.output_path = output_file_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string());
+
+        let fm = compiler
+            .cm
+            .load_file(file_path)
+            .with_context(|| format!("Failed to open file {}", file_path.display()))?;
+
+        let output = try_with_handler(
+            compiler.cm.clone(),
+            HandlerOpts {
+                color: ColorConfig::Always,
+                skip_filename: false,
+            },
+            |handler| {
+                GLOBALS.set(&Default::default(), || {
+                    compiler.minify(fm, handler, &options)
+                })
+            },
+        )
+        .with_context(|| format!("failed to minify {}", file_path.display()))?;
+
+        let extra = match &output.output {
+            Some(extra) => serde_json::from_str(extra)?,
+            None => Default::default(),
+        };
+
+        emit_output(output, output_file_path.as_deref())?;
+
+        Ok(extra)
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let included_extensions = if let Some(extensions) = &self.extensions {
+            extensions.clone()
+        } else {
+            DEFAULT_EXTENSIONS.iter().map(|v| v.to_string()).collect()
+        };
+
+        let files = get_files_list(
+            &self.files,
+            &included_extensions,
+            self.ignore.as_deref(),
+            false,
+        )?;
+
+        if files.len() > 1 {
+            if self.out_file.is_some() {
+                bail!("`--out-file` cannot be used with multiple input files, use `--out-dir`");
+            }
+
+            if self.input_source_map.is_some() {
+                bail!("`--input-source-map` cannot be used with multiple input files");
+            }
+        }
+
+        let mut options = self.read_minify_options()?;
+        let compiler = COMPILER.clone();
+
+        let extras = match &self.name_cache {
+            Some(name_cache_path) => {
+                let mut extras = vec![];
+
+                // Each file depends on the names chosen for the previous ones.
+                for file_path in &files {
+                    let mut extra = self.minify_file(&compiler, &options, file_path)?;
+                    if let Some(name_cache) = extra.remove("nameCache") {
+                        options.name_cache = Some(serde_json::from_value(name_cache)?);
+                    }
+                    extras.push((file_path, extra));
+                }
+
+                let name_cache = serde_json::to_string(&options.name_cache)?;
+                fs::write(name_cache_path, name_cache).with_context(|| {
+                    format!("failed to write name cache {}", name_cache_path.display())
+                })?;
+
+                extras
+            }
+            None => files
+                .par_iter()
+                .map(|file_path| {
+                    self.minify_file(&compiler, &options, file_path)
+                        .map(|extra| (file_path, extra))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        };
+
+        if let Some(trace_path) = &self.optimization_trace {
+            let traces = extras
+                .into_iter()
+                .filter_map(|(file_path, mut extra)| {
+                    let trace = extra.remove("optimizationTrace")?;
+                    Some((file_path.display().to_string(), trace))
+                })
+                .collect::<serde_json::Map<_, _>>();
+
+            fs::write(trace_path, serde_json::to_string_pretty(&traces)?).with_context(|| {
+                format!(
+                    "failed to write optimization trace {}",
+                    trace_path.display()
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the output to `output_file_path`, or only the code to stdout, so it
+/// can be piped. The source map is inlined in the latter case.
+fn emit_output(mut output: TransformOutput, output_file_path: Option<&Path>) -> anyhow::Result<()> {
+    match output_file_path {
+        Some(output_file_path) => {
+            if let Some(output_dir) = output_file_path.parent() {
+                if !output_dir.as_os_str().is_empty() && !output_dir.is_dir() {
+                    fs::create_dir_all(output_dir)?;
+                }
+            }
+
+            if let Some(source_map) = &output.map {
+                let source_map_path = PathBuf::from(format!("{}.map", output_file_path.display()));
+
+                output.code.push_str("\n//# sourceMappingURL=");
+                output
+                    .code
+                    .push_str(&source_map_path.file_name().unwrap().to_string_lossy());
+
+                fs::write(source_map_path, source_map)?;
+            }
+
+            fs::write(output_file_path, &output.code)?;
+        }
+        None => {
+            if let Some(source_map) = &output.map {
+                output
+                    .code
+                    .push_str("\n//# sourceMappingURL=data:application/json;base64,");
+                output.code.push_str(&base64::encode(source_map));
+            }
+
+            println!("{}", output.code);
+        }
+    }
+
+    Ok(())
+}
 
 #[swc_trace]
 impl super::CommandRunner for MinifyOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Minify command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use clap::Parser;
+    use swc_core::base::BoolOr;
+    use tempfile::tempdir;
+
+    use super::MinifyOptions;
+
+    fn parse(args: &[&str]) -> MinifyOptions {
+        MinifyOptions::try_parse_from(["minify"].iter().chain(args)).unwrap()
+    }
+
+    #[test]
+    fn source_map() {
+        let options = parse(&["a.js"]).read_minify_options().unwrap();
+        assert!(!options.source_map.is_obj());
+
+        let options = parse(&["--source-map", "a.js"])
+            .read_minify_options()
+            .unwrap();
+        assert!(options.source_map.is_obj());
+    }
+
+    #[test]
+    fn input_source_map() {
+        let dir = tempdir().unwrap();
+        let map = dir.path().join("a.js.map");
+        fs::write(
+            &map,
+            r#"{"version":3,"sources":[],"names":[],"mappings":""}"#,
+        )
+        .unwrap();
+
+        let options = parse(&["--input-source-map", map.to_str().unwrap(), "a.js"])
+            .read_minify_options()
+            .unwrap();
+
+        match options.source_map.into_inner() {
+            Some(BoolOr::Data(source_map)) => {
+                assert_eq!(source_map.content, Some(fs::read_to_string(&map).unwrap()))
+            }
+            _ => panic!("source map should be enabled"),
+        }
+    }
+
+    #[test]
+    fn config_file() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("minify.json");
+        fs::write(
+            &config,
+            r#"{ "mangle": { "toplevel": true }, "module": true }"#,
+        )
+        .unwrap();
+
+        let options = parse(&[
+            "--config-file",
+            config.to_str().unwrap(),
+            "--optimization-trace",
+            "trace.json",
+            "a.js",
+        ])
+        .read_minify_options()
+        .unwrap();
+
+        let mangle = options.mangle.unwrap_as_option(|_| None).unwrap();
+        assert_eq!(mangle.top_level, Some(true));
+        assert!(options.module);
+        assert!(options.optimization_trace);
+        assert_eq!(options.name_cache, None);
+    }
+
+    #[test]
+    fn missing_name_cache() {
+        let dir = tempdir().unwrap();
+        let name_cache = dir.path().join("names.json");
+
+        let options = parse(&["--name-cache", name_cache.to_str().unwrap(), "a.js"])
+            .read_minify_options()
+            .unwrap();
+        assert_eq!(options.name_cache, Some(Default::default()));
+    }
+
+    #[test]
+    fn name_cache_round_trip() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("minify.json");
+        let input = dir.path().join("a.js");
+        let output = dir.path().join("a.min.js");
+        let name_cache = dir.path().join("names.json");
+        fs::write(
+            &config,
+            r#"{ "compress": false, "mangle": { "toplevel": true } }"#,
+        )
+        .unwrap();
+        fs::write(&input, "var longName = 1;\nconsole.log(longName);\n").unwrap();
+
+        let options = parse(&[
+            "--config-file",
+            config.to_str().unwrap(),
+            "--name-cache",
+            name_cache.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            input.to_str().unwrap(),
+        ]);
+
+        options.execute_inner().unwrap();
+        let cache: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&name_cache).unwrap()).unwrap();
+        let name = cache["vars"]["props"]["$longName"].as_str().unwrap();
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains(&format!("var {}=1", name)));
+
+        // Names in the cache are reused by the next run.
+        fs::write(
+            &name_cache,
+            r#"{ "vars": { "props": { "$longName": "zz" } } }"#,
+        )
+        .unwrap();
+        options.execute_inner().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            "var zz=1;console.log(zz);"
+        );
+        let cache: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&name_cache).unwrap()).unwrap();
+        assert_eq!(cache["vars"]["props"]["$longName"], "zz");
     }
 }
@@ -20,8 +20,12 @@ pub enum Command {
     Plugin(PluginSubcommand),
     /// Run SWC's transformer.
     Compile(Box<CompileOptions>),
+    /// Bundle files using spack config, or the given entries.
     Bundle(BundleOptions),
+    /// Minify already transpiled JavaScript files, using terser compatible
+    /// options.
     Minify(MinifyOptions),
+    /// Run lint rules of SWC against files, using `jsc.lints` of .swcrc.
     Lint(LintOptions),
 }
 
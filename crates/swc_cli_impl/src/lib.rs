@@ -1,3 +1,5 @@
+use std::ffi::OsString;
+
 use clap::Parser;
 use commands::{Command, CommandRunner, PluginSubcommand, SwcCliOptions};
 
@@ -5,7 +7,17 @@ mod commands;
 mod util;
 
 pub fn run() -> anyhow::Result<()> {
-    let command = SwcCliOptions::parse().command;
+    run_from(std::env::args_os())
+}
+
+/// Runs the command given by `args`, where the first argument is the name of
+/// the binary.
+pub fn run_from<I, T>(args: I) -> anyhow::Result<()>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let command = SwcCliOptions::parse_from(args).command;
 
     match &command {
         Command::Plugin(PluginSubcommand::New(options)) => options.execute(),
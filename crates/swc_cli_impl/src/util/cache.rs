@@ -0,0 +1,343 @@
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use preset_env_base::query::targets_to_versions;
+use sha1::{Digest, Sha1};
+use swc_core::{
+    base::{
+        config::{Config, Options},
+        Compiler, TransformOutput,
+    },
+    common::{FileName, SourceFile},
+};
+
+/// Bump this if the layout of cache entries changes.
+const CACHE_VERSION: &str = "v2";
+
+/// An on-disk cache of compiled files, used by `swc compile --cache-dir`.
+///
+/// Entries are keyed by the name and the content of the source file, its input
+/// source map, the command line flags, the content of the config file and the
+/// files it references, the resolved browserslist targets, and the hashes of
+/// the plugin binaries. As a result, a stale entry is never hit and
+/// invalidation is not required; removing the directory is enough to reset the
+/// cache.
+///
+/// Diagnostics emitted while compiling a file are stored with its output, so
+/// warnings are reported again when the entry is hit.
+pub(crate) struct CompileCache {
+    dir: PathBuf,
+    /// Stable representation of the command line flags.
+    fingerprint: String,
+}
+
+impl CompileCache {
+    pub fn new(dir: &Path, fingerprint: String) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create cache directory {}", dir.display()))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            fingerprint,
+        })
+    }
+
+    /// Computes the key of the cache entry for a file.
+    ///
+    /// Returns [None] if the file cannot be cached, e.g. because the hash of a
+    /// plugin is not available or the browserslist query of `env.targets`
+    /// fails.
+    ///
+    /// Options and config contain hash maps, so they are hashed as the raw
+    /// content of the config file instead of their debug representation,
+    /// which changes between runs.
+    ///
+    /// This should be called in a scope of [swc_core::common::GLOBALS].
+    pub fn key(
+        &self,
+        compiler: &Compiler,
+        fm: &SourceFile,
+        options: &Options,
+    ) -> anyhow::Result<Option<String>> {
+        let swcrc = match compiler.swcrc_path(options, &fm.name) {
+            Some(path) => {
+                fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?
+            }
+            None => vec![],
+        };
+        let config = compiler.read_config(options, &fm.name)?;
+
+        let plugin_hashes = match plugin_hashes(options, config.as_ref())? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // Queries depend on the browserslist data, environment variables and
+        // the browserslist config files, so the resolved versions are hashed.
+        let mut targets = vec![];
+        for env in config
+            .as_ref()
+            .and_then(|c| c.env.as_ref())
+            .into_iter()
+            .chain(options.config.env.as_ref())
+        {
+            match targets_to_versions(env.targets.clone()) {
+                Ok(versions) => targets.push(format!("{:?}", versions)),
+                Err(..) => return Ok(None),
+            }
+        }
+
+        // `pure_libraries` may be a path, which is loaded by `read_config`.
+        let pure_libraries = config
+            .as_ref()
+            .and_then(|c| c.jsc.minify.as_ref())
+            .and_then(|m| m.compress.as_ref().unwrap_as_option(|_| None))
+            .and_then(|c| c.pure_libraries.as_ref());
+        let pure_libraries = serde_json::to_string(&pure_libraries)?;
+
+        let mut hasher = Sha1::new();
+
+        hasher.update(CACHE_VERSION);
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(&self.fingerprint);
+        // The default is read from `SWC_ENV` or `NODE_ENV`.
+        hasher.update(&options.env_name);
+        hasher.update(fm.name.to_string());
+        hasher.update(fm.src.as_bytes());
+        hasher.update(swcrc);
+        hasher.update(pure_libraries);
+        for versions in targets {
+            hasher.update(versions);
+        }
+        for map in input_source_maps(fm)? {
+            hasher.update(map);
+        }
+        for hash in plugin_hashes {
+            hasher.update(hash);
+        }
+
+        let mut key = String::with_capacity(40);
+        for byte in hasher.finalize() {
+            write!(key, "{:02x}", byte).unwrap();
+        }
+
+        Ok(Some(key))
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(&key[..2]).join(key)
+    }
+
+    /// Returns the output and the diagnostics of an entry.
+    pub fn get(&self, key: &str) -> Option<(TransformOutput, String)> {
+        let path = self.entry_path(key);
+
+        let code = fs::read_to_string(path.with_extension("js")).ok()?;
+        let map = path.with_extension("js.map");
+        let map = if map.is_file() {
+            Some(fs::read_to_string(map).ok()?)
+        } else {
+            None
+        };
+
+        let diagnostics = path.with_extension("diagnostics");
+        let diagnostics = if diagnostics.is_file() {
+            fs::read_to_string(diagnostics).ok()?
+        } else {
+            String::new()
+        };
+
+        Some((
+            TransformOutput {
+                code,
+                map,
+                output: None,
+            },
+            diagnostics,
+        ))
+    }
+
+    pub fn insert(
+        &self,
+        key: &str,
+        output: &TransformOutput,
+        diagnostics: &str,
+    ) -> anyhow::Result<()> {
+        let path = self.entry_path(key);
+
+        fs::create_dir_all(path.parent().expect("Parent should be available"))?;
+
+        // The code is written last, as its presence marks the entry as complete.
+        if let Some(map) = &output.map {
+            write_atomic(&path.with_extension("js.map"), map)?;
+        }
+        if !diagnostics.is_empty() {
+            write_atomic(&path.with_extension("diagnostics"), diagnostics)?;
+        }
+        write_atomic(&path.with_extension("js"), &output.code)?;
+
+        Ok(())
+    }
+
+    /// Returns the cached output and diagnostics for the file, or stores the
+    /// result of `op` if there is no entry.
+    ///
+    /// This should be called in a scope of [swc_core::common::GLOBALS].
+    pub fn get_or_insert_with<F>(
+        &self,
+        compiler: &Compiler,
+        fm: &SourceFile,
+        options: &Options,
+        op: F,
+    ) -> anyhow::Result<(TransformOutput, String)>
+    where
+        F: FnOnce() -> anyhow::Result<(TransformOutput, String)>,
+    {
+        // Files which cannot be cached are compiled as usual. Errors of the
+        // config are reported by `op`.
+        let key = match (&fm.name, self.key(compiler, fm, options)) {
+            (FileName::Real(..), Ok(Some(key))) => key,
+            _ => return op(),
+        };
+
+        if let Some(entry) = self.get(&key) {
+            return Ok(entry);
+        }
+
+        let (output, diagnostics) = op()?;
+        self.insert(&key, &output, &diagnostics)?;
+
+        Ok((output, diagnostics))
+    }
+}
+
+fn write_atomic(path: &Path, content: &str) -> anyhow::Result<()> {
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)
+        .with_context(|| format!("failed to write cache entry {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Paths and contents of the files `swc` may read as the input source map of
+/// `fm`.
+///
+/// This is a superset of the files actually read, as it does not depend on
+/// `inputSourceMap`.
+fn input_source_maps(fm: &SourceFile) -> anyhow::Result<Vec<Vec<u8>>> {
+    let path = match &fm.name {
+        FileName::Real(path) => path,
+        _ => return Ok(vec![]),
+    };
+
+    let mut candidates = vec![PathBuf::from(format!("{}.map", path.display()))];
+    if let Some(idx) = fm.src.rfind("sourceMappingURL=") {
+        let url = fm.src[idx + "sourceMappingURL=".len()..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim();
+
+        // Inline source maps are a part of the source.
+        if !url.starts_with("data:") {
+            if let Some(dir) = path.parent() {
+                candidates.push(dir.join(url));
+            }
+        }
+    }
+
+    let mut maps = vec![];
+    for candidate in candidates {
+        if candidate.is_file() {
+            let content = fs::read(&candidate)
+                .with_context(|| format!("failed to read {}", candidate.display()))?;
+
+            maps.push(candidate.to_string_lossy().into_owned().into_bytes());
+            maps.push(content);
+        }
+    }
+
+    Ok(maps)
+}
+
+/// Hashes of the plugin binaries used for a file, in order.
+#[cfg(feature = "plugin")]
+fn plugin_hashes(
+    options: &Options,
+    config: Option<&Config>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    use swc_core::{
+        base::{
+            config::{init_plugin_module_cache_once, PLUGIN_MODULE_CACHE},
+            resolver::environment_resolver,
+        },
+        ecma::loader::{resolve::Resolve, TargetEnv},
+    };
+
+    let experimental = config
+        .and_then(|c| {
+            c.jsc
+                .experimental
+                .plugins
+                .as_ref()
+                .map(|_| &c.jsc.experimental)
+        })
+        .unwrap_or(&options.config.jsc.experimental);
+
+    let plugins = match &experimental.plugins {
+        Some(plugins) => plugins,
+        None => return Ok(Some(vec![])),
+    };
+
+    // This populates the same cache `swc` uses while transforming, so the
+    // plugins are only read once.
+    init_plugin_module_cache_once(true, &experimental.cache_root);
+
+    let mut inner_cache = PLUGIN_MODULE_CACHE
+        .inner
+        .get()
+        .expect("Cache should be available")
+        .lock();
+
+    let resolver = environment_resolver(TargetEnv::Node, Default::default(), true);
+
+    let mut hashes = vec![];
+    for plugin_config in plugins {
+        let plugin_name = &plugin_config.0;
+
+        if !inner_cache.contains(plugin_name) {
+            let resolved_path =
+                resolver.resolve(&FileName::Real(PathBuf::from(plugin_name)), plugin_name)?;
+
+            let path = match resolved_path {
+                FileName::Real(value) => value,
+                _ => anyhow::bail!("Failed to resolve plugin path: {:?}", resolved_path),
+            };
+
+            inner_cache.store_bytes_from_path(&path, plugin_name)?;
+        }
+
+        match inner_cache.get_module_hash(plugin_name) {
+            Some(hash) => hashes.push(hash),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(hashes))
+}
+
+/// Plugins are not executed without the `plugin` feature, so they do not
+/// affect the output.
+#[cfg(not(feature = "plugin"))]
+fn plugin_hashes(
+    _options: &Options,
+    _config: Option<&Config>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    Ok(Some(vec![]))
+}
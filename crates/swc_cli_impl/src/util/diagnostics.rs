@@ -0,0 +1,24 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Buffers the diagnostics of a single file, so reports of files processed in
+/// parallel are not interleaved on stderr.
+#[derive(Clone, Default)]
+pub(crate) struct DiagnosticsBuffer(pub Arc<Mutex<String>>);
+
+impl DiagnosticsBuffer {
+    /// Returns the buffered diagnostics and clears the buffer.
+    pub fn take(&self) -> String {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl fmt::Write for DiagnosticsBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.lock().unwrap().push_str(s);
+
+        Ok(())
+    }
+}
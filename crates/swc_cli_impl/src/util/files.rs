@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+use path_absolutize::Absolutize;
+use relative_path::RelativePath;
+use walkdir::WalkDir;
+
+/// List of file extensions supported by default.
+pub(crate) static DEFAULT_EXTENSIONS: &[&str] =
+    &["js", "jsx", "es6", "es", "mjs", "ts", "tsx", "cts", "mts"];
+
+/// Infer list of files to be transformed from cli arguments.
+/// If given input is a directory, it'll traverse it and collect all supported
+/// files.
+#[tracing::instrument(level = "info", skip_all)]
+pub(crate) fn get_files_list(
+    raw_files_input: &[PathBuf],
+    extensions: &[String],
+    ignore_pattern: Option<&str>,
+    _include_dotfiles: bool,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let input_dir = raw_files_input.iter().find(|p| p.is_dir());
+
+    let files = if let Some(input_dir) = input_dir {
+        if raw_files_input.len() > 1 {
+            return Err(anyhow::anyhow!(
+                "Cannot specify multiple files when using a directory as input"
+            ));
+        }
+
+        WalkDir::new(input_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .map(|e| e.into_path())
+            .filter(|e| {
+                extensions
+                    .iter()
+                    .any(|ext| e.extension().map(|v| v == &**ext).unwrap_or(false))
+            })
+            .collect()
+    } else {
+        raw_files_input.to_owned()
+    };
+
+    if let Some(ignore_pattern) = ignore_pattern {
+        let pattern: Vec<PathBuf> = glob(ignore_pattern)?.filter_map(|p| p.ok()).collect();
+
+        return Ok(files
+            .into_iter()
+            .filter(|file_path| !pattern.iter().any(|p| p.eq(file_path)))
+            .collect());
+    }
+
+    Ok(files)
+}
+
+/// Calculate full, absolute path to the file to emit.
+/// Currently this is quite naive calculation based on assumption input file's
+/// path and output dir are relative to the same directory.
+pub(crate) fn resolve_output_file_path(
+    out_dir: &Path,
+    file_path: &Path,
+    file_extension: PathBuf,
+) -> anyhow::Result<PathBuf> {
+    let default = PathBuf::from(".");
+    let base = file_path.parent().unwrap_or(&default).display().to_string();
+
+    let dist_absolute_path = out_dir.absolutize()?;
+
+    // These are possible combinations between input to output dir.
+    // cwd: /c/github/swc
+    //
+    // Input
+    // 1. Relative to cwd                   : ./crates/swc/tests/serde/a.js
+    // 2. Relative to cwd, traverse up      : ../repo/some/dir/b.js
+    // 3. Absolute path, relative to cwd: /c/github/swc/crates/swc/tests/serde/a.js
+    // 4. Absolute path, not relative to cwd: /c/github/repo/some/dir/b.js
+    //
+    // OutDir
+    // a. Relative to cwd: ./dist
+    // b. Relative to cwd, traverse up: ../outer_dist
+    // c. Absolute path: /c/github/swc/dist
+    // d. Absolute path, not relative to cwd: /c/github/outer_dist
+    //
+    // It is unclear how to calculate output path when either input or output is not
+    // relative to cwd (2,4 and b,d) and it is UB for now.
+    let base = RelativePath::new(&*base);
+    let output_path = base.to_logical_path(dist_absolute_path).join(
+        // Custom output file extension is not supported yet
+        file_path
+            .with_extension(file_extension)
+            .file_name()
+            .expect("Filename should be available"),
+    );
+
+    Ok(output_path)
+}
@@ -1 +1,4 @@
+pub(crate) mod cache;
+pub(crate) mod diagnostics;
+pub(crate) mod files;
 pub(crate) mod trace;
@@ -0,0 +1,320 @@
+//! Runs the commands of the cli on the fixtures in `tests/fixture`.
+//!
+//! `swc` exits with a non-zero code if and only if [swc_cli_impl::run]
+//! returns an error, so the result is checked instead of spawning a process.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+/// Copies a fixture to a temporary directory, as commands write to it.
+fn fixture(name: &str) -> TempDir {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join(name);
+    let dir = tempfile::tempdir().unwrap();
+
+    for entry in WalkDir::new(&src) {
+        let entry = entry.unwrap();
+        let dest = dir.path().join(entry.path().strip_prefix(&src).unwrap());
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest).unwrap();
+        } else {
+            fs::copy(entry.path(), &dest).unwrap();
+        }
+    }
+
+    dir
+}
+
+fn swc(args: &[&Path]) -> anyhow::Result<()> {
+    swc_cli_impl::run_from(
+        Path::new("swc")
+            .iter()
+            .chain(args.iter().map(|arg| arg.as_os_str())),
+    )
+}
+
+fn read(path: impl AsRef<Path>) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+/// Number of entries in a compile cache.
+fn cache_entries(cache_dir: &Path) -> usize {
+    WalkDir::new(cache_dir)
+        .into_iter()
+        .filter(|entry| {
+            let path = entry.as_ref().unwrap().path();
+            path.extension().map_or(false, |ext| ext == "js")
+        })
+        .count()
+}
+
+fn wait_until(mut cond: impl FnMut() -> bool) {
+    let start = Instant::now();
+
+    while !cond() {
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "timed out while waiting for the watcher"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn lint_reports_errors() {
+    let dir = fixture("lint");
+    let input = dir.path().join("input.js");
+    let src = read(&input);
+
+    let err = swc(&[
+        "lint".as_ref(),
+        "--no-color".as_ref(),
+        "--config-file".as_ref(),
+        &dir.path().join(".swcrc"),
+        &input,
+    ])
+    .unwrap_err();
+
+    assert_eq!(err.to_string(), "Found 2 lint errors");
+    assert_eq!(read(&input), src);
+}
+
+#[test]
+fn lint_passes_clean_files() {
+    let dir = fixture("lint");
+
+    swc(&[
+        "lint".as_ref(),
+        "--config-file".as_ref(),
+        &dir.path().join(".swcrc"),
+        &dir.path().join("clean.js"),
+    ])
+    .unwrap();
+}
+
+#[test]
+fn lint_fix_converges() {
+    let dir = fixture("lint");
+    let input = dir.path().join("input.js");
+    let args = [
+        "lint".as_ref(),
+        "--fix".as_ref(),
+        "--config-file".as_ref(),
+        &*dir.path().join(".swcrc"),
+        &input,
+    ];
+
+    // `var a` is fixed to `let a` by `no-var`, and then to `const a` by
+    // `prefer-const` in the next pass.
+    swc(&args).unwrap();
+    let fixed = read(&input);
+    assert_eq!(
+        fixed,
+        "const a = 1;\nlet b = 2;\nb = 3;\nconsole.log(a, b);\n"
+    );
+
+    swc(&args).unwrap();
+    assert_eq!(read(&input), fixed);
+}
+
+#[test]
+fn bundle() {
+    let dir = fixture("bundle");
+    let out_dir = dir.path().join("dist");
+
+    swc(&[
+        "bundle".as_ref(),
+        "--target".as_ref(),
+        "browser".as_ref(),
+        "--out-dir".as_ref(),
+        &out_dir,
+        "--manifest".as_ref(),
+        &dir.path().join("src").join("index.js"),
+    ])
+    .unwrap();
+
+    let output = read(out_dir.join("index.js"));
+    assert!(output.contains("Hello, "), "{}", output);
+    assert!(!output.contains("import"), "{}", output);
+    assert!(!output.contains("unused"), "{}", output);
+
+    let manifest: serde_json::Value = serde_json::from_str(&read(out_dir.join("manifest.json")))
+        .expect("manifest should be valid json");
+    assert!(manifest.is_object());
+}
+
+#[test]
+fn bundle_fails_without_entries() {
+    let dir = fixture("bundle");
+    let out_dir = dir.path().join("dist");
+
+    let err = swc(&["bundle".as_ref(), "--out-dir".as_ref(), &out_dir]).unwrap_err();
+
+    assert!(err.to_string().starts_with("No entry specified"), "{}", err);
+    assert!(!out_dir.exists());
+}
+
+#[test]
+fn minify() {
+    let dir = fixture("minify");
+    let output = dir.path().join("input.min.js");
+
+    swc(&[
+        "minify".as_ref(),
+        "--config-file".as_ref(),
+        &dir.path().join("minify.json"),
+        "--source-map".as_ref(),
+        "-o".as_ref(),
+        &output,
+        &dir.path().join("input.js"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        read(&output),
+        "function add(d,n){return d+n}console.log(add(1,2));\n//# \
+         sourceMappingURL=input.min.js.map"
+    );
+    let map: serde_json::Value = serde_json::from_str(&read(dir.path().join("input.min.js.map")))
+        .expect("source map should be valid json");
+    assert_eq!(map["version"], 3);
+}
+
+#[test]
+fn minify_fails_on_syntax_errors() {
+    let dir = fixture("minify");
+    let input = dir.path().join("invalid.js");
+    let output = dir.path().join("invalid.min.js");
+    fs::write(&input, "function (").unwrap();
+
+    swc(&["minify".as_ref(), "-o".as_ref(), &output, &input]).unwrap_err();
+
+    assert!(!output.exists());
+}
+
+#[test]
+fn compile() {
+    let dir = fixture("compile");
+    let output = dir.path().join("output.js");
+
+    swc(&[
+        "compile".as_ref(),
+        "--config-file".as_ref(),
+        &dir.path().join(".swcrc"),
+        "--out-file".as_ref(),
+        &output,
+        &dir.path().join("input.js"),
+    ])
+    .unwrap();
+
+    let output = read(&output);
+    assert!(
+        output.contains("var double = function(value)"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn compile_cache() {
+    let dir = fixture("compile");
+    let input = dir.path().join("input.js");
+    let config = dir.path().join(".swcrc");
+    let output = dir.path().join("output.js");
+    let cache_dir = dir.path().join("cache");
+    let args: [&Path; 8] = [
+        "compile".as_ref(),
+        "--config-file".as_ref(),
+        &config,
+        "--cache-dir".as_ref(),
+        &cache_dir,
+        "--out-file".as_ref(),
+        &output,
+        &input,
+    ];
+
+    swc(&args).unwrap();
+    assert_eq!(cache_entries(&cache_dir), 1);
+
+    // A hit is read from the cache, so a modified entry is emitted as is.
+    let entry: PathBuf = WalkDir::new(&cache_dir)
+        .into_iter()
+        .map(|entry| entry.unwrap().into_path())
+        .find(|path| path.extension().map_or(false, |ext| ext == "js"))
+        .unwrap();
+    fs::write(entry, "cached();\n").unwrap();
+    swc(&args).unwrap();
+    assert_eq!(read(&output).trim_end(), "cached();");
+    assert_eq!(cache_entries(&cache_dir), 1);
+
+    // Changes to the input and to the config file are misses.
+    fs::write(&input, "const triple = (value) => value * 3;\n").unwrap();
+    swc(&args).unwrap();
+    assert_eq!(cache_entries(&cache_dir), 2);
+    assert!(read(&output).contains("var triple = function(value)"));
+
+    fs::write(&config, r#"{ "jsc": { "target": "es2015" } }"#).unwrap();
+    swc(&args).unwrap();
+    assert_eq!(cache_entries(&cache_dir), 3);
+    assert!(read(&output).contains("const triple = (value)=>"));
+
+    // Reverting them hits the first entry again.
+    let original = fixture("compile");
+    fs::copy(original.path().join("input.js"), &input).unwrap();
+    fs::copy(original.path().join(".swcrc"), &config).unwrap();
+    swc(&args).unwrap();
+    assert_eq!(cache_entries(&cache_dir), 3);
+    assert_eq!(read(&output).trim_end(), "cached();");
+}
+
+#[test]
+fn compile_watch() {
+    let dir = fixture("compile");
+    let input = dir.path().join("input.js");
+    let config = dir.path().join(".swcrc");
+    let output = dir.path().join("output.js");
+
+    {
+        let (input, output) = (input.clone(), output.clone());
+
+        // The watcher does not return, so it's left running until the test
+        // binary exits.
+        thread::spawn(move || {
+            swc(&[
+                "compile".as_ref(),
+                "--watch".as_ref(),
+                "--config-file".as_ref(),
+                &config,
+                "--out-file".as_ref(),
+                &output,
+                &input,
+            ])
+        });
+    }
+
+    wait_until(|| output.exists() && read(&output).contains("var double"));
+
+    // The watcher may not be registered right after the initial compilation.
+    let mut n = 0;
+    wait_until(|| {
+        n += 1;
+        fs::write(
+            &input,
+            format!("const triple{} = (value) => value * 3;\n", n),
+        )
+        .unwrap();
+        thread::sleep(Duration::from_millis(500));
+
+        read(&output).contains(&format!("var triple{} = function(value)", n))
+    });
+}
@@ -0,0 +1,3 @@
+const double = (value) => value * 2;
+
+console.log(double(2));
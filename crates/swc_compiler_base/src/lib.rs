@@ -1,9 +1,11 @@
 use std::{
+    cell::RefCell,
     env, fmt,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
-use anyhow::{Context, Error};
+use anyhow::{bail, Context, Error};
 use serde::{
     de::{Unexpected, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
@@ -15,12 +17,17 @@ use swc_common::{
     errors::Handler,
     source_map::SourceMapGenConfig,
     sync::Lrc,
-    BytePos, FileName, SourceFile, SourceMap,
+    BytePos, FileName, LineCol, SourceFile, SourceMap,
 };
 use swc_config::{config_types::BoolOr, merge::Merge};
 use swc_ecma_ast::{EsVersion, Ident, Program};
 use swc_ecma_codegen::{text_writer::WriteJs, Emitter, Node};
-use swc_ecma_minifier::js::JsMinifyCommentOption;
+use swc_ecma_minifier::{
+    js::{JsMinifyCommentOption, JsMinifyOptions},
+    option::SimpleMangleCache,
+    size::{Mapping as SizeMapping, SizeReport, TopLevelDecl},
+    trace::OptimizationTrace,
+};
 use swc_ecma_parser::{parse_file_as_module, parse_file_as_program, parse_file_as_script, Syntax};
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 use swc_timer::timer;
@@ -32,6 +39,9 @@ pub struct TransformOutput {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub map: Option<String>,
+    /// Extra data as JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
 }
 
 #[cfg(not(feature = "node"))]
@@ -40,6 +50,9 @@ pub struct TransformOutput {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub map: Option<String>,
+    /// Extra data as JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
 }
 
 /// This method parses a javascript / typescript file
@@ -221,7 +234,11 @@ where
         }
     };
 
-    Ok(TransformOutput { code, map })
+    Ok(TransformOutput {
+        code,
+        map,
+        output: None,
+    })
 }
 
 struct SwcSourceMapConfig<'a> {
@@ -321,6 +338,122 @@ pub fn minify_file_comments(
     }
 }
 
+/// Returns the extra output of `minify` as JSON, e.g. `nameCache` and `size`.
+///
+/// `decls` should be collected by
+/// [top_level_decls](swc_ecma_minifier::size::top_level_decls) from the input
+/// if `report_size` is enabled. Sizes are measured by printing `program`
+/// again, with a copy of `comments`.
+///
+/// This should be called in a scope of [swc_common::GLOBALS].
+#[allow(clippy::too_many_arguments)]
+pub fn minify_extra_output(
+    cm: Lrc<SourceMap>,
+    handler: &Handler,
+    program: &Program,
+    file_name: &FileName,
+    opts: &JsMinifyOptions,
+    comments: &SingleThreadedComments,
+    codegen_config: swc_ecma_codegen::Config,
+    decls: Option<&[TopLevelDecl]>,
+    name_cache: Option<&SimpleMangleCache>,
+    optimization_trace: Option<&OptimizationTrace>,
+) -> Result<Option<String>, Error> {
+    let mut extra = serde_json::Map::new();
+
+    if let Some(name_cache) = name_cache {
+        extra.insert(
+            "nameCache".into(),
+            serde_json::to_value(name_cache.name_cache())
+                .context("failed to serialize the name cache")?,
+        );
+    }
+
+    if let Some(optimization_trace) = optimization_trace {
+        extra.insert("optimizationTrace".into(), optimization_trace.to_json(&cm));
+    }
+
+    if opts.report_size || opts.size_budget.is_some() {
+        // Printing takes the comments.
+        let size_comments = {
+            let (leading, trailing) = comments.borrow_all();
+
+            SingleThreadedComments::from_leading_and_trailing(
+                Rc::new(RefCell::new(leading.clone())),
+                Rc::new(RefCell::new(trailing.clone())),
+            )
+        };
+
+        // The code is printed again, as the output may contain an inline
+        // source map and the breakdown requires a source map with columns.
+        let measured = print(
+            cm,
+            program,
+            Some(&file_name.to_string()),
+            opts.output_path.clone().map(From::from),
+            false,
+            SourceMapsConfig::Bool(decls.is_some()),
+            &Default::default(),
+            None,
+            Some(&size_comments),
+            true,
+            &opts.format.preamble,
+            codegen_config,
+        )?;
+
+        let mut report = SizeReport::new(&measured.code).context("failed to measure the output")?;
+
+        if let (Some(decls), Some(map)) = (decls, &measured.map) {
+            let map = sourcemap::SourceMap::from_slice(map.as_bytes())
+                .context("failed to read the generated source map")?;
+
+            let mappings = map
+                .tokens()
+                .filter(|token| token.get_source().is_some())
+                .map(|token| SizeMapping {
+                    dst: LineCol {
+                        line: token.get_dst_line(),
+                        col: token.get_dst_col(),
+                    },
+                    src: LineCol {
+                        line: token.get_src_line(),
+                        col: token.get_src_col(),
+                    },
+                })
+                .collect();
+
+            report = report.with_declarations(&measured.code, mappings, decls);
+        }
+
+        if let Some(budget) = &opts.size_budget {
+            let errors = budget.check(&report);
+
+            if !errors.is_empty() {
+                for err in &errors {
+                    handler.err(&format!("{}: {}", file_name, err));
+                }
+
+                bail!("size budget of {} is exceeded", file_name);
+            }
+        }
+
+        if opts.report_size {
+            extra.insert(
+                "size".into(),
+                serde_json::to_value(&report).context("failed to serialize the size report")?,
+            );
+        }
+    }
+
+    if extra.is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::to_string(&extra)
+        .map(Some)
+        .context("failed to serialize the extra output")
+}
+
 /// Configuration related to source map generated by swc.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(untagged)]
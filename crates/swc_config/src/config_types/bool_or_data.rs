@@ -22,6 +22,14 @@ impl<T> BoolOrDataConfig<T> {
         }
     }
 
+    pub fn as_mut(&mut self) -> BoolOrDataConfig<&mut T> {
+        match &mut self.0 {
+            Some(BoolOr::Data(v)) => BoolOrDataConfig::from_obj(v),
+            Some(BoolOr::Bool(b)) => BoolOrDataConfig::from_bool(*b),
+            None => BoolOrDataConfig::default(),
+        }
+    }
+
     pub fn or<F>(self, default: F) -> Self
     where
         F: FnOnce() -> Self,
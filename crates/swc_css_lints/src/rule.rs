@@ -5,8 +5,8 @@ use parking_lot::Mutex;
 use rayon::prelude::*;
 use serde::Serialize;
 use swc_common::{
-    errors::{Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
-    Spanned,
+    errors::{Applicability, Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
+    Span, Spanned,
 };
 use swc_css_ast::Stylesheet;
 use swc_css_visit::{Visit, VisitWith};
@@ -121,6 +121,36 @@ where
         });
     }
 
+    /// Reports an error along with a fix, i.e. replacements of the source text
+    /// which resolve it. The fix is attached to the diagnostic as a machine
+    /// applicable suggestion, and can be applied with
+    /// `swc_ecma_lints::fix::apply_fixes`.
+    pub(crate) fn report_with_fix<N, S>(&self, ast_node: N, message: S, fix: Vec<(Span, String)>)
+    where
+        N: Spanned,
+        S: AsRef<str>,
+    {
+        HANDLER.with(|handler| {
+            let mut builder = match self.reaction {
+                LintRuleReaction::Error => {
+                    handler.struct_span_err(ast_node.span(), message.as_ref())
+                }
+                LintRuleReaction::Warning => {
+                    handler.struct_span_warn(ast_node.span(), message.as_ref())
+                }
+                _ => return,
+            };
+
+            builder
+                .multipart_suggestion_with_applicability(
+                    message.as_ref(),
+                    fix,
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+        });
+    }
+
     #[inline]
     pub(crate) fn config(&self) -> &C {
         &self.config
@@ -1,10 +1,11 @@
+use swc_common::Span;
 use swc_css_ast::*;
 use swc_css_visit::{Visit, VisitWith};
 
 use crate::rule::{visitor_rule, LintRule, LintRuleContext};
 
 pub fn block_no_empty(ctx: LintRuleContext<()>) -> Box<dyn LintRule> {
-    visitor_rule(ctx.reaction(), BlockNoEmpty { ctx })
+    visitor_rule(ctx.reaction(), BlockNoEmpty { ctx, rule: None })
 }
 
 const MESSAGE: &str = "Unexpected empty block.";
@@ -12,12 +13,44 @@ const MESSAGE: &str = "Unexpected empty block.";
 #[derive(Debug, Default)]
 struct BlockNoEmpty {
     ctx: LintRuleContext<()>,
+
+    // rule internal
+    /// Spans of the block of the innermost rule, and of the rule itself.
+    rule: Option<(Span, Span)>,
 }
 
 impl Visit for BlockNoEmpty {
+    fn visit_qualified_rule(&mut self, qualified_rule: &QualifiedRule) {
+        let old = self
+            .rule
+            .replace((qualified_rule.block.span, qualified_rule.span));
+
+        qualified_rule.visit_children_with(self);
+
+        self.rule = old;
+    }
+
+    fn visit_at_rule(&mut self, at_rule: &AtRule) {
+        let old = self.rule.take();
+        if let Some(block) = &at_rule.block {
+            self.rule = Some((block.span, at_rule.span));
+        }
+
+        at_rule.visit_children_with(self);
+
+        self.rule = old;
+    }
+
     fn visit_simple_block(&mut self, simple_block: &SimpleBlock) {
         if simple_block.value.is_empty() {
-            self.ctx.report(simple_block, MESSAGE);
+            match self.rule {
+                // An empty block of a rule is fixed by removing the whole rule.
+                Some((block, rule)) if block == simple_block.span => {
+                    self.ctx
+                        .report_with_fix(simple_block, MESSAGE, vec![(rule, String::new())]);
+                }
+                _ => self.ctx.report(simple_block, MESSAGE),
+            }
         }
 
         simple_block.visit_children_with(self);
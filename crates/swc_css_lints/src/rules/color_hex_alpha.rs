@@ -32,19 +32,35 @@ struct ColorHexAlpha {
 
 impl Visit for ColorHexAlpha {
     fn visit_hex_color(&mut self, hex_color: &HexColor) {
-        let length = hex_color.value.len();
+        let value = &*hex_color.value;
+        let length = value.len();
         match self.preference {
             Preference::Always if length == 3 || length == 6 => {
-                self.ctx.report(
+                // Adding an opaque alpha channel doesn't change the color.
+                let alpha = if length == 3 { "f" } else { "ff" };
+                self.ctx.report_with_fix(
                     hex_color,
-                    format!("Expected alpha channel in '#{}'.", hex_color.value),
+                    format!("Expected alpha channel in '#{}'.", value),
+                    vec![(hex_color.span, format!("#{}{}", value, alpha))],
                 );
             }
             Preference::Never if length == 4 || length == 8 => {
-                self.ctx.report(
-                    hex_color,
-                    format!("Unexpected alpha channel in '#{}'.", hex_color.value),
-                );
+                let message = format!("Unexpected alpha channel in '#{}'.", value);
+                // Keep the case of the source text in the fix.
+                let raw = hex_color.raw.as_deref().unwrap_or(value);
+                let (color, alpha) = raw.split_at(length / 4 * 3);
+
+                // Only an opaque alpha channel can be removed without changing
+                // the color.
+                if alpha.chars().all(|c| c.eq_ignore_ascii_case(&'f')) {
+                    self.ctx.report_with_fix(
+                        hex_color,
+                        message,
+                        vec![(hex_color.span, format!("#{}", color))],
+                    );
+                } else {
+                    self.ctx.report(hex_color, message);
+                }
             }
             _ => {}
         }
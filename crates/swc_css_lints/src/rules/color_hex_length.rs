@@ -45,13 +45,21 @@ impl Visit for ColorHexLength {
             HexForm::Long => {
                 if let Some(lengthened) = lengthen(&hex_color.value) {
                     let message = self.build_message(&hex_color.value, &lengthened);
-                    self.ctx.report(hex_color, message);
+                    self.ctx.report_with_fix(
+                        hex_color,
+                        message,
+                        vec![(hex_color.span, format!("#{}", lengthened))],
+                    );
                 }
             }
             HexForm::Short => {
                 if let Some(shortened) = shorten(&hex_color.value) {
                     let message = self.build_message(&hex_color.value, &shortened);
-                    self.ctx.report(hex_color, message);
+                    self.ctx.report_with_fix(
+                        hex_color,
+                        message,
+                        vec![(hex_color.span, format!("#{}", shortened))],
+                    );
                 }
             }
         }
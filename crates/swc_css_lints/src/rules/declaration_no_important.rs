@@ -1,4 +1,4 @@
-use swc_common::Span;
+use swc_common::{Span, Spanned};
 use swc_css_ast::*;
 use swc_css_visit::{Visit, VisitWith};
 
@@ -35,14 +35,28 @@ impl Visit for DeclarationNoImportant {
         }
     }
 
-    fn visit_important_flag(&mut self, important_flag: &ImportantFlag) {
-        match self.keyframe_rules.last() {
-            Some(span) if span.contains(important_flag.span) => {
-                // This rule doesn't check `!important` flag inside `@keyframe`.
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        if let Some(important_flag) = &declaration.important {
+            match self.keyframe_rules.last() {
+                Some(span) if span.contains(important_flag.span) => {
+                    // This rule doesn't check `!important` flag inside `@keyframe`.
+                }
+                _ => {
+                    // Remove the whitespace before `!important` along with it.
+                    let lo = declaration
+                        .value
+                        .last()
+                        .map_or(important_flag.span.lo, |value| value.span_hi());
+
+                    self.ctx.report_with_fix(
+                        important_flag,
+                        MESSAGE,
+                        vec![(important_flag.span.with_lo(lo), String::new())],
+                    )
+                }
             }
-            _ => self.ctx.report(important_flag, MESSAGE),
         }
 
-        important_flag.visit_children_with(self);
+        declaration.visit_children_with(self);
     }
 }
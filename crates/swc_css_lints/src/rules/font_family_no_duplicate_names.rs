@@ -82,11 +82,18 @@ impl FontFamilyNoDuplicateNames {
 
         fonts
             .iter()
-            .fold(AHashSet::default(), |mut seen, (font, span)| {
+            .enumerate()
+            .fold(AHashSet::default(), |mut seen, (index, (font, span))| {
                 let name = font.name();
                 if seen.contains(&font) && self.ignored.iter().all(|item| !item.is_match(name)) {
-                    self.ctx
-                        .report(span, format!("Unexpected duplicate name '{}'.", name));
+                    // A duplicate is never the first font, so removing everything
+                    // from the end of the previous font removes the comma as well.
+                    let previous = fonts[index - 1].1;
+                    self.ctx.report_with_fix(
+                        span,
+                        format!("Unexpected duplicate name '{}'.", name),
+                        vec![(span.with_lo(previous.hi), String::new())],
+                    );
                 }
                 seen.insert(font);
                 seen
@@ -1,10 +1,20 @@
 #![allow(clippy::needless_update)]
 
-use std::{fs, path::PathBuf};
-
-use swc_common::{errors::HANDLER, input::SourceFileInput};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use swc_common::{
+    errors::{Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
+    input::SourceFileInput,
+    FileName,
+};
 use swc_css_lints::{get_rules, LintConfig, LintParams, LintRule};
 use swc_css_parser::{lexer::Lexer, parser::Parser};
+use swc_ecma_lints::fix::apply_fixes;
+use testing::NormalizedOutput;
 
 #[testing::fixture("tests/rules/pass/**/input.css")]
 fn pass(input: PathBuf) {
@@ -86,3 +96,62 @@ fn fail(input: PathBuf) {
 
     stderr.compare_to_file(stderr_path).unwrap();
 }
+
+#[derive(Default, Clone)]
+struct Collector(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((**db).clone());
+    }
+}
+
+#[testing::fixture("tests/rules/fix/**/input.css")]
+fn fix(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.css");
+    let config_path = input.parent().unwrap().join("config.json");
+    let lint_config =
+        serde_json::from_str::<LintConfig>(&fs::read_to_string(config_path).unwrap()).unwrap();
+
+    testing::run_test2(false, |cm, handler| -> Result<(), _> {
+        let config = Default::default();
+
+        let fm = cm.load_file(&input).unwrap();
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), None, config);
+        let mut parser = Parser::new(lexer, config);
+
+        let stylesheet = match parser.parse_all() {
+            Ok(stylesheet) => stylesheet,
+            Err(err) => {
+                err.to_diagnostics(&handler).emit();
+                panic!();
+            }
+        };
+
+        let mut rules = get_rules(&LintParams {
+            lint_config: &lint_config,
+        })
+        .unwrap();
+
+        let collector = Collector::default();
+        let lint_handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+        HANDLER.set(&lint_handler, || {
+            rules.lint_stylesheet(&stylesheet);
+        });
+
+        let diagnostics = collector.0.lock().unwrap();
+        let fixed = apply_fixes(&fm, &diagnostics).expect("should have fixes");
+
+        let fm = cm.new_source_file(FileName::Anon, fixed.clone());
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), None, config);
+        let mut parser = Parser::new(lexer, config);
+        parser.parse_all().expect("fixed stylesheet should be valid");
+
+        NormalizedOutput::from(fixed)
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -42,6 +42,7 @@ fn main() {
                 top_level_ctxt,
                 es_version: EsVersion::latest(),
                 source_map: cm.clone(),
+                comments: None,
             });
 
             let module = program.expect_module();
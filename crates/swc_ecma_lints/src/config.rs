@@ -5,13 +5,26 @@ use swc_config::merge::Merge;
 
 #[cfg(feature = "non_critical_lints")]
 use crate::rules::non_critical_lints::{
-    dot_notation::DotNotationConfig, eqeqeq::EqeqeqConfig, no_bitwise::NoBitwiseConfig,
-    no_console::NoConsoleConfig, no_empty_function::NoEmptyFunctionConfig,
-    no_param_reassign::NoParamReassignConfig, no_restricted_syntax::NoRestrictedSyntaxConfig,
-    no_use_before_define::NoUseBeforeDefineConfig, prefer_const::PreferConstConfig,
-    prefer_regex_literals::PreferRegexLiteralsConfig, quotes::QuotesConfig, radix::RadixConfig,
-    symbol_description::SymbolDescriptionConfig, use_is_nan::UseIsNanConfig,
-    valid_typeof::ValidTypeofConfig, yoda::YodaConfig,
+    dot_notation::DotNotationConfig,
+    eqeqeq::EqeqeqConfig,
+    function_limits::{
+        ComplexityConfig, MaxDepthConfig, MaxLinesPerFunctionConfig, MaxParamsConfig,
+        MaxStatementsConfig,
+    },
+    no_bitwise::NoBitwiseConfig,
+    no_console::NoConsoleConfig,
+    no_empty_function::NoEmptyFunctionConfig,
+    no_param_reassign::NoParamReassignConfig,
+    no_restricted_syntax::NoRestrictedSyntaxConfig,
+    no_use_before_define::NoUseBeforeDefineConfig,
+    prefer_const::PreferConstConfig,
+    prefer_regex_literals::PreferRegexLiteralsConfig,
+    quotes::QuotesConfig,
+    radix::RadixConfig,
+    symbol_description::SymbolDescriptionConfig,
+    use_is_nan::UseIsNanConfig,
+    valid_typeof::ValidTypeofConfig,
+    yoda::YodaConfig,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -211,4 +224,42 @@ pub struct LintConfig {
     #[cfg(feature = "non_critical_lints")]
     #[serde(default, alias = "noAwaitInLoop")]
     pub no_await_in_loop: RuleConfig<()>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "noFloatingPromises")]
+    pub no_floating_promises: RuleConfig<()>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "awaitThenable")]
+    pub await_thenable: RuleConfig<()>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "requireAwait")]
+    pub require_await: RuleConfig<()>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default)]
+    pub complexity: RuleConfig<ComplexityConfig>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "maxDepth")]
+    pub max_depth: RuleConfig<MaxDepthConfig>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "maxParams")]
+    pub max_params: RuleConfig<MaxParamsConfig>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "maxStatements")]
+    pub max_statements: RuleConfig<MaxStatementsConfig>,
+
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "maxLinesPerFunction")]
+    pub max_lines_per_function: RuleConfig<MaxLinesPerFunctionConfig>,
+
+    /// Reports `swc-lint-disable` directives which do not suppress any
+    /// problem.
+    #[cfg(feature = "non_critical_lints")]
+    #[serde(default, alias = "reportUnusedDisableDirectives")]
+    pub report_unused_disable_directives: RuleConfig<()>,
 }
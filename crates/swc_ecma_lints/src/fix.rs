@@ -0,0 +1,136 @@
+//! Automatic fixes of reported problems.
+
+#[cfg(feature = "non_critical_lints")]
+use swc_common::errors::DiagnosticBuilder;
+use swc_common::{
+    errors::{Applicability, Diagnostic},
+    BytePos, SourceFile, Span,
+};
+
+/// A fix of a reported problem, as edits of the source text keyed by [Span].
+///
+/// Rules attach a fix to their diagnostic, where it is stored as a machine
+/// applicable suggestion. [apply_fixes] applies the fixes
+/// attached to a list of diagnostics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fix {
+    edits: Vec<(Span, String)>,
+}
+
+impl Fix {
+    /// Replaces the source text of `span` with `text`.
+    pub fn replace(span: Span, text: impl Into<String>) -> Self {
+        Self::default().and_replace(span, text)
+    }
+
+    /// Removes the source text of `span`.
+    pub fn remove(span: Span) -> Self {
+        Self::replace(span, "")
+    }
+
+    /// Inserts `text` at `pos`.
+    pub fn insert(pos: BytePos, text: impl Into<String>) -> Self {
+        Self::replace(Span::new(pos, pos, Default::default()), text)
+    }
+
+    /// Adds another edit to the fix. Edits of a fix must not overlap.
+    pub fn and_replace(mut self, span: Span, text: impl Into<String>) -> Self {
+        self.edits.push((span, text.into()));
+        self.edits.sort_by_key(|(span, _)| (span.lo, span.hi));
+        self
+    }
+
+    /// The edits of the fix, ordered by position.
+    pub fn edits(&self) -> &[(Span, String)] {
+        &self.edits
+    }
+
+    /// The span covering all edits of the fix.
+    pub fn span(&self) -> Option<Span> {
+        self.edits
+            .iter()
+            .map(|(span, _)| *span)
+            .reduce(|a, b| a.to(b))
+    }
+
+    /// Extracts the fix attached to `diagnostic`, if any.
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        diagnostic
+            .suggestions
+            .iter()
+            .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+            .find_map(|suggestion| suggestion.substitutions.first())
+            .map(|substitution| {
+                substitution
+                    .parts
+                    .iter()
+                    .fold(Self::default(), |fix, part| {
+                        fix.and_replace(part.span, part.snippet.clone())
+                    })
+            })
+    }
+
+    #[cfg(feature = "non_critical_lints")]
+    pub(crate) fn attach_to(self, builder: &mut DiagnosticBuilder, message: &str) {
+        builder.multipart_suggestion_with_applicability(
+            message,
+            self.edits,
+            Applicability::MachineApplicable,
+        );
+    }
+}
+
+/// Applies the fixes attached to `diagnostics` to the source text of `fm`.
+///
+/// Fixes are applied in source order, and a fix which overlaps with a fix
+/// applied before is skipped. Linting the fixed source again reports it with
+/// an updated span, so fixing can be repeated until this returns [None].
+///
+/// Returns [None] if there is nothing to fix, or if the fixes do not change
+/// the source text.
+pub fn apply_fixes(fm: &SourceFile, diagnostics: &[Diagnostic]) -> Option<String> {
+    let mut fixes = diagnostics
+        .iter()
+        .filter_map(Fix::from_diagnostic)
+        .filter_map(|fix| {
+            let span = fix.span()?;
+
+            // Edits outside of the file cannot be applied.
+            if span.lo < fm.start_pos || span.hi > fm.end_pos {
+                return None;
+            }
+
+            Some((span, fix))
+        })
+        .collect::<Vec<_>>();
+
+    if fixes.is_empty() {
+        return None;
+    }
+
+    fixes.sort_by_key(|(span, _)| (span.lo, span.hi));
+
+    let src = &*fm.src;
+    let mut fixed = String::with_capacity(src.len());
+    let mut pos = 0;
+    let mut last_hi = fm.start_pos;
+
+    for (span, fix) in fixes {
+        if span.lo < last_hi {
+            continue;
+        }
+        last_hi = span.hi;
+
+        for (span, text) in fix.edits {
+            let lo = (span.lo - fm.start_pos).0 as usize;
+            let hi = (span.hi - fm.start_pos).0 as usize;
+
+            fixed.push_str(&src[pos..lo]);
+            fixed.push_str(&text);
+            pos = hi;
+        }
+    }
+    fixed.push_str(&src[pos..]);
+
+    (fixed != *src).then_some(fixed)
+}
@@ -2,5 +2,9 @@
 #![cfg_attr(feature = "non_critical_lints", deny(clippy::all))]
 
 pub mod config;
+pub mod fix;
+pub mod metrics;
 pub mod rule;
 pub mod rules;
+#[cfg(feature = "non_critical_lints")]
+mod suppression;
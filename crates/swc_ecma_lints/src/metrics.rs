@@ -0,0 +1,449 @@
+//! Complexity and size metrics of functions.
+//!
+//! The metrics are used by rules like `complexity` and `max-statements`, and
+//! can be collected with [function_metrics] without linting, e.g. to export
+//! them as JSON.
+
+use serde::{Deserialize, Serialize};
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+/// A position in a file. Lines are 1-based and columns are 0-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Metrics of a single function, method or arrow function.
+///
+/// Metrics of nested functions are not included in the metrics of the
+/// enclosing function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionMetrics {
+    /// The name of the function, or the name of the binding or property it is
+    /// assigned to.
+    pub name: Option<String>,
+
+    #[serde(skip)]
+    pub span: Span,
+    /// Span of the name, if any.
+    #[serde(skip)]
+    pub name_span: Option<Span>,
+
+    pub start: LineCol,
+    pub end: LineCol,
+
+    /// Number of lines the function spans.
+    pub lines: usize,
+    pub params: usize,
+    /// Cyclomatic complexity.
+    pub complexity: usize,
+    /// Number of statements in blocks of the function.
+    pub statements: usize,
+    /// Maximum nesting depth of control flow statements.
+    pub depth: usize,
+
+    /// `true` if the function is not nested in another function.
+    #[serde(skip)]
+    pub top_level: bool,
+    /// Control flow statements and their nesting depth.
+    #[serde(skip)]
+    pub blocks: Vec<(Span, usize)>,
+}
+
+impl FunctionMetrics {
+    /// The span to report problems of the function at.
+    pub fn report_span(&self) -> Span {
+        self.name_span.unwrap_or(self.span)
+    }
+
+    /// Describes the function in messages, e.g. "Function `foo`".
+    pub fn describe(&self) -> String {
+        match &self.name {
+            Some(name) => format!("Function `{}`", name),
+            None => "Function".to_string(),
+        }
+    }
+}
+
+/// Collects the metrics of all functions of a program, in source order.
+pub fn function_metrics(program: &Program, source_map: &SourceMap) -> Vec<FunctionMetrics> {
+    collect(program, source_map)
+}
+
+pub(crate) fn collect<N>(node: &N, source_map: &SourceMap) -> Vec<FunctionMetrics>
+where
+    N: VisitWith<MetricsCollector>,
+{
+    let mut collector = MetricsCollector::default();
+
+    node.visit_with(&mut collector);
+
+    collector
+        .done
+        .into_iter()
+        .map(|(name, name_span, span, params, metrics)| {
+            let start = source_map.lookup_char_pos(span.lo);
+            let end = source_map.lookup_char_pos(span.hi);
+
+            FunctionMetrics {
+                name,
+                span,
+                name_span,
+                start: LineCol {
+                    line: start.line,
+                    column: start.col.0,
+                },
+                end: LineCol {
+                    line: end.line,
+                    column: end.col.0,
+                },
+                lines: end.line - start.line + 1,
+                params,
+                complexity: metrics.complexity,
+                statements: metrics.statements,
+                depth: metrics.max_depth,
+                top_level: metrics.top_level,
+                blocks: metrics.blocks,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct Current {
+    complexity: usize,
+    statements: usize,
+    depth: usize,
+    max_depth: usize,
+    top_level: bool,
+    blocks: Vec<(Span, usize)>,
+}
+
+type Done = (Option<String>, Option<Span>, Span, usize, Current);
+
+#[derive(Debug, Default)]
+pub(crate) struct MetricsCollector {
+    /// Metrics of the functions being visited.
+    stack: Vec<Current>,
+    /// Name given to the next function by a binding or property.
+    name: Option<(String, Span)>,
+    /// Finished functions, sorted by span.
+    done: Vec<Done>,
+}
+
+impl MetricsCollector {
+    fn add_complexity(&mut self) {
+        if let Some(current) = self.stack.last_mut() {
+            current.complexity += 1;
+        }
+    }
+
+    fn with_function<F>(&mut self, name: Option<(String, Span)>, span: Span, params: usize, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        // Keep the functions in source order, although nested functions are
+        // finished first.
+        let index = self.done.len();
+        self.done.push(Default::default());
+
+        self.stack.push(Current {
+            complexity: 1,
+            top_level: self.stack.is_empty(),
+            ..Default::default()
+        });
+        self.name = None;
+
+        op(self);
+
+        let current = self.stack.pop().unwrap();
+        let (name, name_span) = match name {
+            Some((name, span)) => (Some(name), Some(span)),
+            None => (None, None),
+        };
+
+        self.done[index] = (name, name_span, span, params, current);
+    }
+
+    /// Visits a control flow statement, which increases the nesting depth.
+    fn with_block<F>(&mut self, span: Span, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        if let Some(current) = self.stack.last_mut() {
+            current.depth += 1;
+            current.max_depth = current.max_depth.max(current.depth);
+            current.blocks.push((span, current.depth));
+        }
+
+        op(self);
+
+        if let Some(current) = self.stack.last_mut() {
+            current.depth -= 1;
+        }
+    }
+
+    /// `else if` does not increase the depth.
+    fn visit_if_chain(&mut self, if_stmt: &IfStmt) {
+        self.add_complexity();
+
+        if_stmt.test.visit_with(self);
+        if_stmt.cons.visit_with(self);
+
+        match if_stmt.alt.as_deref() {
+            Some(Stmt::If(alt)) => self.visit_if_chain(alt),
+            Some(alt) => alt.visit_with(self),
+            None => {}
+        }
+    }
+}
+
+fn prop_name(key: &PropName) -> Option<(String, Span)> {
+    match key {
+        PropName::Ident(ident) => Some((ident.sym.to_string(), ident.span)),
+        PropName::Str(s) => Some((s.value.to_string(), s.span)),
+        PropName::Num(n) => Some((n.value.to_string(), n.span)),
+        _ => None,
+    }
+}
+
+fn pat_name(pat: &Pat) -> Option<(String, Span)> {
+    match pat {
+        Pat::Ident(binding) => Some((binding.id.sym.to_string(), binding.id.span)),
+        _ => None,
+    }
+}
+
+impl Visit for MetricsCollector {
+    noop_visit_type!();
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        let name = Some((fn_decl.ident.sym.to_string(), fn_decl.ident.span));
+
+        self.with_function(
+            name,
+            fn_decl.function.span,
+            fn_decl.function.params.len(),
+            |v| fn_decl.function.visit_children_with(v),
+        );
+    }
+
+    fn visit_fn_expr(&mut self, fn_expr: &FnExpr) {
+        let name = match &fn_expr.ident {
+            Some(ident) => Some((ident.sym.to_string(), ident.span)),
+            None => self.name.take(),
+        };
+
+        self.with_function(
+            name,
+            fn_expr.function.span,
+            fn_expr.function.params.len(),
+            |v| fn_expr.function.visit_children_with(v),
+        );
+    }
+
+    fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+        let name = self.name.take();
+
+        self.with_function(name, arrow_expr.span, arrow_expr.params.len(), |v| {
+            arrow_expr.visit_children_with(v)
+        });
+    }
+
+    fn visit_class_method(&mut self, class_method: &ClassMethod) {
+        class_method.key.visit_with(self);
+
+        self.with_function(
+            prop_name(&class_method.key),
+            class_method.function.span,
+            class_method.function.params.len(),
+            |v| class_method.function.visit_children_with(v),
+        );
+    }
+
+    fn visit_private_method(&mut self, private_method: &PrivateMethod) {
+        let name = Some((
+            format!("#{}", private_method.key.id.sym),
+            private_method.key.span,
+        ));
+
+        self.with_function(
+            name,
+            private_method.function.span,
+            private_method.function.params.len(),
+            |v| private_method.function.visit_children_with(v),
+        );
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        self.with_function(
+            prop_name(&constructor.key),
+            constructor.span,
+            constructor.params.len(),
+            |v| constructor.visit_children_with(v),
+        );
+    }
+
+    fn visit_method_prop(&mut self, method_prop: &MethodProp) {
+        method_prop.key.visit_with(self);
+
+        self.with_function(
+            prop_name(&method_prop.key),
+            method_prop.function.span,
+            method_prop.function.params.len(),
+            |v| method_prop.function.visit_children_with(v),
+        );
+    }
+
+    fn visit_getter_prop(&mut self, getter_prop: &GetterProp) {
+        getter_prop.key.visit_with(self);
+
+        self.with_function(prop_name(&getter_prop.key), getter_prop.span, 0, |v| {
+            getter_prop.body.visit_with(v)
+        });
+    }
+
+    fn visit_setter_prop(&mut self, setter_prop: &SetterProp) {
+        setter_prop.key.visit_with(self);
+
+        self.with_function(prop_name(&setter_prop.key), setter_prop.span, 1, |v| {
+            setter_prop.param.visit_with(v);
+            setter_prop.body.visit_with(v);
+        });
+    }
+
+    fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
+        var_declarator.name.visit_with(self);
+
+        self.name = pat_name(&var_declarator.name);
+        var_declarator.init.visit_with(self);
+        self.name = None;
+    }
+
+    fn visit_key_value_prop(&mut self, key_value_prop: &KeyValueProp) {
+        key_value_prop.key.visit_with(self);
+
+        self.name = prop_name(&key_value_prop.key);
+        key_value_prop.value.visit_with(self);
+        self.name = None;
+    }
+
+    fn visit_class_prop(&mut self, class_prop: &ClassProp) {
+        class_prop.key.visit_with(self);
+
+        self.name = prop_name(&class_prop.key);
+        class_prop.value.visit_with(self);
+        self.name = None;
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        // Names are only given to functions which are assigned directly.
+        match expr {
+            Expr::Fn(..) | Expr::Arrow(..) | Expr::Paren(..) => {}
+            _ => self.name = None,
+        }
+
+        expr.visit_children_with(self);
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        if let Some(current) = self.stack.last_mut() {
+            current.statements += block.stmts.len();
+        }
+
+        block.visit_children_with(self);
+    }
+
+    fn visit_if_stmt(&mut self, if_stmt: &IfStmt) {
+        self.with_block(if_stmt.span, |v| v.visit_if_chain(if_stmt));
+    }
+
+    fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
+        self.with_block(switch_stmt.span, |v| switch_stmt.visit_children_with(v));
+    }
+
+    fn visit_switch_case(&mut self, switch_case: &SwitchCase) {
+        if switch_case.test.is_some() {
+            self.add_complexity();
+        }
+
+        switch_case.visit_children_with(self);
+    }
+
+    fn visit_try_stmt(&mut self, try_stmt: &TryStmt) {
+        self.with_block(try_stmt.span, |v| try_stmt.visit_children_with(v));
+    }
+
+    fn visit_catch_clause(&mut self, catch_clause: &CatchClause) {
+        self.add_complexity();
+
+        catch_clause.visit_children_with(self);
+    }
+
+    fn visit_with_stmt(&mut self, with_stmt: &WithStmt) {
+        self.with_block(with_stmt.span, |v| with_stmt.visit_children_with(v));
+    }
+
+    fn visit_while_stmt(&mut self, while_stmt: &WhileStmt) {
+        self.add_complexity();
+
+        self.with_block(while_stmt.span, |v| while_stmt.visit_children_with(v));
+    }
+
+    fn visit_do_while_stmt(&mut self, do_while_stmt: &DoWhileStmt) {
+        self.add_complexity();
+
+        self.with_block(do_while_stmt.span, |v| do_while_stmt.visit_children_with(v));
+    }
+
+    fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
+        self.add_complexity();
+
+        self.with_block(for_stmt.span, |v| for_stmt.visit_children_with(v));
+    }
+
+    fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt) {
+        self.add_complexity();
+
+        self.with_block(for_in_stmt.span, |v| for_in_stmt.visit_children_with(v));
+    }
+
+    fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt) {
+        self.add_complexity();
+
+        self.with_block(for_of_stmt.span, |v| for_of_stmt.visit_children_with(v));
+    }
+
+    fn visit_cond_expr(&mut self, cond_expr: &CondExpr) {
+        self.add_complexity();
+
+        cond_expr.visit_children_with(self);
+    }
+
+    fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
+        if matches!(bin_expr.op, op!("&&") | op!("||") | op!("??")) {
+            self.add_complexity();
+        }
+
+        bin_expr.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        if matches!(assign_expr.op, op!("&&=") | op!("||=") | op!("??=")) {
+            self.add_complexity();
+        }
+
+        assign_expr.visit_children_with(self);
+    }
+
+    fn visit_assign_pat(&mut self, assign_pat: &AssignPat) {
+        // Default values are conditional.
+        self.add_complexity();
+
+        assign_pat.visit_children_with(self);
+    }
+}
@@ -28,19 +28,7 @@ macro_rules! for_vec {
         } else {
             let errors = $s
                 .par_iter_mut()
-                .flat_map(|rule| {
-                    let emitter = Capturing::default();
-                    {
-                        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
-                        HANDLER.set(&handler, || {
-                            rule.$name(program);
-                        });
-                    }
-
-                    let errors = Arc::try_unwrap(emitter.errors).unwrap().into_inner();
-
-                    errors
-                })
+                .flat_map(|rule| capture_diagnostics(|| rule.$name(program)))
                 .collect::<Vec<_>>();
 
             HANDLER.with(|handler| {
@@ -66,6 +54,21 @@ where
     }
 }
 
+/// Runs `op` and returns the diagnostics reported to [HANDLER], instead of
+/// emitting them.
+pub(crate) fn capture_diagnostics<F>(op: F) -> Vec<Diagnostic>
+where
+    F: FnOnce(),
+{
+    let emitter = Capturing::default();
+    {
+        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+        HANDLER.set(&handler, op);
+    }
+
+    Arc::try_unwrap(emitter.errors).unwrap().into_inner()
+}
+
 #[derive(Default, Clone)]
 struct Capturing {
     errors: Arc<Mutex<Vec<Diagnostic>>>,
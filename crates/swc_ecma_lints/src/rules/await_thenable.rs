@@ -0,0 +1,105 @@
+use swc_common::{errors::HANDLER, Span, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::{
+    config::{LintRuleReaction, RuleConfig},
+    rule::{visitor_rule, Rule},
+    rules::non_critical_lints::declared_types::{DeclaredTypes, Thenable},
+};
+
+const MESSAGE: &str = "Unexpected `await` of a non-Promise value";
+
+pub fn await_thenable(
+    program: &Program,
+    unresolved_ctxt: SyntaxContext,
+    config: &RuleConfig<()>,
+) -> Option<Box<dyn Rule>> {
+    let rule_reaction = config.get_rule_reaction();
+
+    match rule_reaction {
+        LintRuleReaction::Off => None,
+        _ => Some(visitor_rule(AwaitThenable::new(
+            program,
+            unresolved_ctxt,
+            rule_reaction,
+        ))),
+    }
+}
+
+#[derive(Debug, Default)]
+struct AwaitThenable {
+    expected_reaction: LintRuleReaction,
+    types: DeclaredTypes,
+
+    // rule internal
+    /// Span of the class `this` refers to.
+    class: Option<Span>,
+}
+
+impl AwaitThenable {
+    fn new(
+        program: &Program,
+        unresolved_ctxt: SyntaxContext,
+        expected_reaction: LintRuleReaction,
+    ) -> Self {
+        Self {
+            expected_reaction,
+            types: DeclaredTypes::collect(program, unresolved_ctxt),
+            class: None,
+        }
+    }
+
+    fn emit_report(&self, span: Span) {
+        HANDLER.with(|handler| match self.expected_reaction {
+            LintRuleReaction::Error => {
+                handler.struct_span_err(span, MESSAGE).emit();
+            }
+            LintRuleReaction::Warning => {
+                handler.struct_span_warn(span, MESSAGE).emit();
+            }
+            _ => {}
+        });
+    }
+}
+
+impl Visit for AwaitThenable {
+    noop_visit_type!();
+
+    fn visit_await_expr(&mut self, await_expr: &AwaitExpr) {
+        if self.types.expr_thenable(&await_expr.arg, self.class) == Thenable::No {
+            self.emit_report(await_expr.span);
+        }
+
+        await_expr.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, class: &Class) {
+        let prev = self.class.replace(class.span);
+
+        class.visit_children_with(self);
+
+        self.class = prev;
+    }
+
+    fn visit_class_method(&mut self, class_method: &ClassMethod) {
+        class_method.key.visit_with(self);
+
+        // Unlike other functions, methods do not change `this`.
+        class_method.function.visit_children_with(self);
+    }
+
+    fn visit_private_method(&mut self, private_method: &PrivateMethod) {
+        private_method.key.visit_with(self);
+
+        private_method.function.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let prev = self.class.take();
+
+        function.visit_children_with(self);
+
+        self.class = prev;
+    }
+}
@@ -0,0 +1,362 @@
+//! What is known about values of a module from the declared TypeScript
+//! annotations, e.g. the return types of functions.
+//!
+//! This is not type checking. Only explicit annotations of bindings declared
+//! in the module are used, so most values are [Thenable::Unknown].
+
+use swc_atoms::JsWord;
+use swc_common::{collections::AHashMap, Span, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+/// Whether a value may be a `Promise` or another thenable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Thenable {
+    Yes,
+    No,
+    Unknown,
+}
+
+impl Thenable {
+    /// Combines the results of two declarations, e.g. of overloads.
+    fn merge(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// Classifies a type annotation.
+pub(crate) fn type_thenable(ty: &TsType) -> Thenable {
+    match ty {
+        TsType::TsTypeRef(TsTypeRef {
+            type_name: TsEntityName::Ident(ident),
+            ..
+        }) if &*ident.sym == "Promise" || &*ident.sym == "PromiseLike" => Thenable::Yes,
+
+        TsType::TsKeywordType(TsKeywordType { kind, .. }) => match kind {
+            TsKeywordTypeKind::TsAnyKeyword
+            | TsKeywordTypeKind::TsUnknownKeyword
+            | TsKeywordTypeKind::TsObjectKeyword
+            | TsKeywordTypeKind::TsIntrinsicKeyword => Thenable::Unknown,
+            _ => Thenable::No,
+        },
+
+        TsType::TsLitType(..)
+        | TsType::TsArrayType(..)
+        | TsType::TsTupleType(..)
+        | TsType::TsFnOrConstructorType(..) => Thenable::No,
+
+        TsType::TsParenthesizedType(TsParenthesizedType { type_ann, .. }) => {
+            type_thenable(type_ann)
+        }
+
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union)) => {
+            let types = union.types.iter().map(|ty| type_thenable(ty));
+
+            if types.clone().any(|t| t == Thenable::Yes) {
+                Thenable::Yes
+            } else if types.clone().all(|t| t == Thenable::No) {
+                Thenable::No
+            } else {
+                Thenable::Unknown
+            }
+        }
+
+        _ => Thenable::Unknown,
+    }
+}
+
+/// Classifies the value returned by a call of a function.
+fn function_thenable(function: &Function) -> Thenable {
+    match (function.is_async, function.is_generator) {
+        // Async generators return async iterators.
+        (true, true) => Thenable::No,
+        (true, false) => Thenable::Yes,
+        _ => function
+            .return_type
+            .as_ref()
+            .map_or(Thenable::Unknown, |ann| type_thenable(&ann.type_ann)),
+    }
+}
+
+/// Classifies the value returned by a call of an arrow function.
+fn arrow_thenable(arrow: &ArrowExpr) -> Thenable {
+    match (arrow.is_async, &arrow.return_type) {
+        (true, _) => Thenable::Yes,
+        (false, Some(ann)) => type_thenable(&ann.type_ann),
+        (false, None) => Thenable::Unknown,
+    }
+}
+
+/// Classifies the value returned by a call of a value with the type `ty`.
+fn fn_type_returns(ty: &TsType) -> Thenable {
+    match ty {
+        TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(fn_type)) => {
+            type_thenable(&fn_type.type_ann.type_ann)
+        }
+        TsType::TsParenthesizedType(TsParenthesizedType { type_ann, .. }) => {
+            fn_type_returns(type_ann)
+        }
+        _ => Thenable::Unknown,
+    }
+}
+
+fn expr_returns(expr: &Expr) -> Thenable {
+    match expr {
+        Expr::Fn(FnExpr { function, .. }) => function_thenable(function),
+        Expr::Arrow(arrow) => arrow_thenable(arrow),
+        Expr::Paren(ParenExpr { expr, .. }) => expr_returns(expr),
+        _ => Thenable::Unknown,
+    }
+}
+
+/// Declared types of the bindings and methods of a module.
+#[derive(Debug, Default)]
+pub(crate) struct DeclaredTypes {
+    /// Values returned by calls of bindings.
+    returns: AHashMap<Id, Thenable>,
+    /// Values of bindings.
+    values: AHashMap<Id, Thenable>,
+    /// Values returned by calls of methods, by the span of the class.
+    methods: AHashMap<Span, AHashMap<JsWord, Thenable>>,
+    unresolved_ctxt: SyntaxContext,
+}
+
+impl DeclaredTypes {
+    pub fn collect<N>(node: &N, unresolved_ctxt: SyntaxContext) -> Self
+    where
+        N: VisitWith<Collector>,
+    {
+        let mut collector = Collector {
+            types: Self {
+                unresolved_ctxt,
+                ..Default::default()
+            },
+        };
+
+        node.visit_with(&mut collector);
+
+        collector.types
+    }
+
+    /// Classifies the value of an expression.
+    ///
+    /// `class` is the span of the class `this` refers to, if any.
+    pub fn expr_thenable(&self, expr: &Expr, class: Option<Span>) -> Thenable {
+        match expr {
+            Expr::Paren(ParenExpr { expr, .. }) => self.expr_thenable(expr, class),
+
+            Expr::Ident(ident) => self
+                .values
+                .get(&ident.to_id())
+                .copied()
+                .unwrap_or(Thenable::Unknown),
+
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) => self.call_thenable(callee, class),
+
+            Expr::New(NewExpr { callee, .. }) => match &**callee {
+                Expr::Ident(ident) if self.is_global_promise(ident) => Thenable::Yes,
+                _ => Thenable::Unknown,
+            },
+
+            Expr::Lit(..)
+            | Expr::Tpl(..)
+            | Expr::Array(..)
+            | Expr::Fn(..)
+            | Expr::Arrow(..)
+            | Expr::Class(..)
+            | Expr::Unary(..)
+            | Expr::Update(..) => Thenable::No,
+
+            Expr::Bin(BinExpr { op, .. }) => match op {
+                op!("||") | op!("&&") | op!("??") => Thenable::Unknown,
+                _ => Thenable::No,
+            },
+
+            _ => Thenable::Unknown,
+        }
+    }
+
+    fn call_thenable(&self, callee: &Expr, class: Option<Span>) -> Thenable {
+        match callee {
+            Expr::Paren(ParenExpr { expr, .. }) => self.call_thenable(expr, class),
+
+            Expr::Ident(ident) => self
+                .returns
+                .get(&ident.to_id())
+                .copied()
+                .unwrap_or(Thenable::Unknown),
+
+            Expr::Fn(..) | Expr::Arrow(..) => expr_returns(callee),
+
+            Expr::Member(MemberExpr { obj, prop, .. }) => {
+                let name = match prop {
+                    MemberProp::Ident(ident) => ident.sym.clone(),
+                    MemberProp::PrivateName(name) => format!("#{}", name.id.sym).into(),
+                    MemberProp::Computed(..) => return Thenable::Unknown,
+                };
+
+                match &**obj {
+                    Expr::This(..) => class
+                        .and_then(|class| self.methods.get(&class))
+                        .and_then(|methods| methods.get(&name))
+                        .copied()
+                        .unwrap_or(Thenable::Unknown),
+
+                    // `Promise.resolve()`, `Promise.all()`, ...
+                    Expr::Ident(ident) if self.is_global_promise(ident) => match &*name {
+                        "all" | "allSettled" | "any" | "race" | "reject" | "resolve" => {
+                            Thenable::Yes
+                        }
+                        _ => Thenable::Unknown,
+                    },
+
+                    obj if matches!(&*name, "then" | "catch" | "finally") => {
+                        match self.expr_thenable(obj, class) {
+                            Thenable::Yes => Thenable::Yes,
+                            _ => Thenable::Unknown,
+                        }
+                    }
+
+                    _ => Thenable::Unknown,
+                }
+            }
+
+            _ => Thenable::Unknown,
+        }
+    }
+
+    fn is_global_promise(&self, ident: &Ident) -> bool {
+        &*ident.sym == "Promise" && ident.span.ctxt == self.unresolved_ctxt
+    }
+
+    fn insert_returns(&mut self, id: Id, thenable: Thenable) {
+        self.returns
+            .entry(id)
+            .and_modify(|v| *v = v.merge(thenable))
+            .or_insert(thenable);
+    }
+
+    fn insert_binding(&mut self, binding: &BindingIdent) {
+        if let Some(ann) = &binding.type_ann {
+            self.values
+                .insert(binding.to_id(), type_thenable(&ann.type_ann));
+            self.insert_returns(binding.to_id(), fn_type_returns(&ann.type_ann));
+        }
+    }
+}
+
+pub(crate) struct Collector {
+    types: DeclaredTypes,
+}
+
+impl Visit for Collector {
+    noop_visit_type!();
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.types
+            .insert_returns(fn_decl.ident.to_id(), function_thenable(&fn_decl.function));
+
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        if let Pat::Ident(binding) = &declarator.name {
+            if binding.type_ann.is_some() {
+                self.types.insert_binding(binding);
+            } else if let Some(init) = &declarator.init {
+                self.types
+                    .insert_returns(binding.to_id(), expr_returns(init));
+            }
+        }
+
+        declarator.visit_children_with(self);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        if let Pat::Ident(binding) = &param.pat {
+            self.types.insert_binding(binding);
+        }
+
+        param.visit_children_with(self);
+    }
+
+    fn visit_ts_param_prop(&mut self, param: &TsParamProp) {
+        if let TsParamPropParam::Ident(binding) = &param.param {
+            self.types.insert_binding(binding);
+        }
+
+        param.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, class: &Class) {
+        let mut methods: AHashMap<JsWord, Thenable> = Default::default();
+        let mut insert = |name: JsWord, thenable: Thenable| {
+            methods
+                .entry(name)
+                .and_modify(|v| *v = v.merge(thenable))
+                .or_insert(thenable);
+        };
+
+        for member in &class.body {
+            match member {
+                ClassMember::Method(ClassMethod {
+                    key: PropName::Ident(key),
+                    function,
+                    kind: MethodKind::Method,
+                    ..
+                }) => insert(key.sym.clone(), function_thenable(function)),
+
+                ClassMember::PrivateMethod(PrivateMethod {
+                    key,
+                    function,
+                    kind: MethodKind::Method,
+                    ..
+                }) => insert(
+                    format!("#{}", key.id.sym).into(),
+                    function_thenable(function),
+                ),
+
+                ClassMember::ClassProp(ClassProp {
+                    key: PropName::Ident(key),
+                    value,
+                    type_ann,
+                    ..
+                }) => insert(
+                    key.sym.clone(),
+                    match (type_ann, value) {
+                        (Some(ann), _) => fn_type_returns(&ann.type_ann),
+                        (None, Some(value)) => expr_returns(value),
+                        (None, None) => Thenable::Unknown,
+                    },
+                ),
+
+                ClassMember::PrivateProp(PrivateProp {
+                    key,
+                    value,
+                    type_ann,
+                    ..
+                }) => insert(
+                    format!("#{}", key.id.sym).into(),
+                    match (type_ann, value) {
+                        (Some(ann), _) => fn_type_returns(&ann.type_ann),
+                        (None, Some(value)) => expr_returns(value),
+                        (None, None) => Thenable::Unknown,
+                    },
+                ),
+
+                _ => {}
+            }
+        }
+
+        self.types.methods.insert(class.span, methods);
+
+        class.visit_children_with(self);
+    }
+}
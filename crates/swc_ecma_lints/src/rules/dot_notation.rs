@@ -1,12 +1,18 @@
 use dashmap::DashMap;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use swc_common::{collections::ARandomState, errors::HANDLER, sync::Lazy, Span};
+use swc_common::{
+    collections::{AHashSet, ARandomState},
+    errors::HANDLER,
+    sync::Lazy,
+    Span, Spanned,
+};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
     rules::utils::{resolve_string_quote_type, QuotesType},
 };
@@ -36,6 +42,14 @@ struct DotNotation {
     allow_keywords: bool,
     pattern: Option<String>,
     is_module: bool,
+
+    // rule internal
+    /// Spans of properties accessed with `?.`, which are fixed to `?.prop`
+    /// instead of `.prop`.
+    optional_props: AHashSet<Span>,
+    /// Spans of properties which cannot be written in dot notation without
+    /// parentheses, e.g. `1['toFixed']`.
+    unfixable_props: AHashSet<Span>,
 }
 
 impl DotNotation {
@@ -47,6 +61,24 @@ impl DotNotation {
             allow_keywords: dot_notation_config.allow_keywords.unwrap_or(true),
             is_module,
             pattern: dot_notation_config.allow_pattern.clone(),
+            optional_props: Default::default(),
+            unfixable_props: Default::default(),
+        }
+    }
+
+    fn build_fix(&self, span: Span, prop: &str) -> Option<Fix> {
+        let mut chars = prop.chars();
+        let is_valid_ident = chars.next().map_or(false, Ident::is_valid_start)
+            && chars.all(Ident::is_valid_continue);
+
+        if !is_valid_ident || self.unfixable_props.contains(&span) {
+            return None;
+        }
+
+        if self.optional_props.contains(&span) {
+            Some(Fix::replace(span, prop))
+        } else {
+            Some(Fix::replace(span, format!(".{}", prop)))
         }
     }
 
@@ -57,14 +89,18 @@ impl DotNotation {
             quote = quote_type.get_char()
         );
 
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, &message).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, &message).emit();
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, &message),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, &message),
+                _ => return,
+            };
+
+            if let Some(fix) = self.build_fix(span, prop) {
+                fix.attach_to(&mut builder, &message);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
 
@@ -98,6 +134,22 @@ impl DotNotation {
 impl Visit for DotNotation {
     noop_visit_type!();
 
+    fn visit_opt_chain_expr(&mut self, opt_chain: &OptChainExpr) {
+        if let (true, OptChainBase::Member(member)) = (opt_chain.optional, &*opt_chain.base) {
+            self.optional_props.insert(member.prop.span());
+        }
+
+        opt_chain.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let Expr::Lit(Lit::Num(..)) = &*member.obj {
+            self.unfixable_props.insert(member.prop.span());
+        }
+
+        member.visit_children_with(self);
+    }
+
     fn visit_member_prop(&mut self, member: &MemberProp) {
         if let MemberProp::Computed(prop) = member {
             match &*prop.expr {
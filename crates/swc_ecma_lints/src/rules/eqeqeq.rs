@@ -1,11 +1,18 @@
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
 use serde::{Deserialize, Serialize};
-use swc_common::{errors::HANDLER, Span};
+use swc_common::{errors::HANDLER, SourceMap, Span};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
+    rules::utils::bin_expr_op_span,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -27,73 +34,105 @@ pub struct EqeqeqConfig {
     mode: EqEqEqMode,
 }
 
-pub fn eqeqeq(config: &RuleConfig<EqeqeqConfig>) -> Option<Box<dyn Rule>> {
+pub fn eqeqeq(
+    source_map: &Arc<SourceMap>,
+    config: &RuleConfig<EqeqeqConfig>,
+) -> Option<Box<dyn Rule>> {
     match config.get_rule_reaction() {
         LintRuleReaction::Off => None,
-        _ => Some(visitor_rule(Eqeqeq::new(config))),
+        _ => Some(visitor_rule(Eqeqeq::new(source_map.clone(), config))),
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 struct Eqeqeq {
     expected_reaction: LintRuleReaction,
+    source_map: Arc<SourceMap>,
     mode: EqEqEqMode,
 }
 
+impl Debug for Eqeqeq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Eqeqeq")
+            .field("expected_reaction", &self.expected_reaction)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
 impl Eqeqeq {
-    fn new(config: &RuleConfig<EqeqeqConfig>) -> Self {
+    fn new(source_map: Arc<SourceMap>, config: &RuleConfig<EqeqeqConfig>) -> Self {
         Self {
             expected_reaction: config.get_rule_reaction(),
+            source_map,
             mode: config.get_rule_config().mode,
         }
     }
 
-    fn emit_report(&self, span: Span, actual: &str, expected: &str) {
+    fn emit_report(&self, span: Span, actual: &str, expected: &str, fix: Option<Fix>) {
         let message = format!("Use '{}' instead of '{}'", expected, actual);
 
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, &message).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, &message).emit();
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, &message),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, &message),
+                _ => return,
+            };
+
+            if let Some(fix) = fix {
+                fix.attach_to(&mut builder, &message);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
 
-    fn check(&self, span: Span, bin_op: &BinaryOp) {
-        match bin_op {
-            op!("==") => {
-                if let EqEqEqMode::Always = self.mode {
-                    self.emit_report(span, "==", "===");
-                }
-            }
-            op!("!=") => {
-                if let EqEqEqMode::Always = self.mode {
-                    self.emit_report(span, "!=", "!==");
-                }
-            }
-            op!("===") => {
-                if let EqEqEqMode::Never = self.mode {
-                    self.emit_report(span, "===", "==");
-                }
-            }
-            op!("!==") => {
-                if let EqEqEqMode::Never = self.mode {
-                    self.emit_report(span, "!==", "!=");
-                }
-            }
-            _ => {}
+    /// Changing the operator is only safe if both operands are known to have
+    /// the same type.
+    fn is_safe_to_fix(&self, bin_expr: &BinExpr) -> bool {
+        let is_typeof = |expr: &Expr| {
+            matches!(
+                expr,
+                Expr::Unary(UnaryExpr {
+                    op: op!("typeof"),
+                    ..
+                })
+            )
+        };
+
+        match (bin_expr.left.as_ref(), bin_expr.right.as_ref()) {
+            (left, right) if is_typeof(left) || is_typeof(right) => true,
+            (Expr::Lit(Lit::Str(..)), Expr::Lit(Lit::Str(..)))
+            | (Expr::Lit(Lit::Num(..)), Expr::Lit(Lit::Num(..)))
+            | (Expr::Lit(Lit::Bool(..)), Expr::Lit(Lit::Bool(..))) => true,
+            _ => false,
         }
     }
+
+    fn check(&self, bin_expr: &BinExpr) {
+        let expected = match (bin_expr.op, self.mode) {
+            (op!("=="), EqEqEqMode::Always) => "===",
+            (op!("!="), EqEqEqMode::Always) => "!==",
+            (op!("==="), EqEqEqMode::Never) => "==",
+            (op!("!=="), EqEqEqMode::Never) => "!=",
+            _ => return,
+        };
+
+        let fix = if self.is_safe_to_fix(bin_expr) {
+            bin_expr_op_span(&self.source_map, bin_expr).map(|span| Fix::replace(span, expected))
+        } else {
+            None
+        };
+
+        self.emit_report(bin_expr.span, bin_expr.op.as_str(), expected, fix);
+    }
 }
 
 impl Visit for Eqeqeq {
     noop_visit_type!();
 
     fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
-        self.check(bin_expr.span, &bin_expr.op);
+        self.check(bin_expr);
 
         bin_expr.visit_children_with(self);
     }
@@ -0,0 +1,286 @@
+//! Rules which limit the complexity and the size of functions, like
+//! `complexity` and `max-statements`.
+//!
+//! The metrics of functions are collected once per program by
+//! [SharedMetrics], and each rule checks one of them.
+
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use swc_common::{errors::HANDLER, sync::OnceCell, SourceMap, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::VisitWith;
+
+use crate::{
+    config::{LintRuleReaction, RuleConfig},
+    metrics::{self, FunctionMetrics},
+    rule::Rule,
+};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplexityConfig {
+    max: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxDepthConfig {
+    max: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxParamsConfig {
+    max: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxStatementsConfig {
+    max: Option<usize>,
+    ignore_top_level_functions: Option<bool>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxLinesPerFunctionConfig {
+    max: Option<usize>,
+    skip_blank_lines: Option<bool>,
+}
+
+/// Metrics of the functions of the program being linted, which are collected
+/// by the first rule which needs them.
+pub struct SharedMetrics {
+    source_map: Arc<SourceMap>,
+    functions: OnceCell<Vec<FunctionMetrics>>,
+}
+
+impl SharedMetrics {
+    pub fn new(source_map: Arc<SourceMap>) -> Arc<Self> {
+        Arc::new(Self {
+            source_map,
+            functions: Default::default(),
+        })
+    }
+
+    fn get<N>(&self, program: &N) -> &[FunctionMetrics]
+    where
+        N: VisitWith<metrics::MetricsCollector>,
+    {
+        self.functions
+            .get_or_init(|| metrics::collect(program, &self.source_map))
+    }
+}
+
+pub fn complexity(
+    metrics: &Arc<SharedMetrics>,
+    config: &RuleConfig<ComplexityConfig>,
+) -> Option<Box<dyn Rule>> {
+    let max = config.get_rule_config().max.unwrap_or(20);
+
+    FunctionLimit::boxed(metrics, config, Limit::Complexity { max })
+}
+
+pub fn max_depth(
+    metrics: &Arc<SharedMetrics>,
+    config: &RuleConfig<MaxDepthConfig>,
+) -> Option<Box<dyn Rule>> {
+    let max = config.get_rule_config().max.unwrap_or(4);
+
+    FunctionLimit::boxed(metrics, config, Limit::Depth { max })
+}
+
+pub fn max_params(
+    metrics: &Arc<SharedMetrics>,
+    config: &RuleConfig<MaxParamsConfig>,
+) -> Option<Box<dyn Rule>> {
+    let max = config.get_rule_config().max.unwrap_or(3);
+
+    FunctionLimit::boxed(metrics, config, Limit::Params { max })
+}
+
+pub fn max_statements(
+    metrics: &Arc<SharedMetrics>,
+    config: &RuleConfig<MaxStatementsConfig>,
+) -> Option<Box<dyn Rule>> {
+    let rule_config = config.get_rule_config();
+    let limit = Limit::Statements {
+        max: rule_config.max.unwrap_or(10),
+        ignore_top_level_functions: rule_config.ignore_top_level_functions.unwrap_or(false),
+    };
+
+    FunctionLimit::boxed(metrics, config, limit)
+}
+
+pub fn max_lines_per_function(
+    metrics: &Arc<SharedMetrics>,
+    config: &RuleConfig<MaxLinesPerFunctionConfig>,
+) -> Option<Box<dyn Rule>> {
+    let rule_config = config.get_rule_config();
+    let limit = Limit::Lines {
+        max: rule_config.max.unwrap_or(50),
+        skip_blank_lines: rule_config.skip_blank_lines.unwrap_or(false),
+    };
+
+    FunctionLimit::boxed(metrics, config, limit)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Limit {
+    Complexity {
+        max: usize,
+    },
+    Depth {
+        max: usize,
+    },
+    Params {
+        max: usize,
+    },
+    Statements {
+        max: usize,
+        ignore_top_level_functions: bool,
+    },
+    Lines {
+        max: usize,
+        skip_blank_lines: bool,
+    },
+}
+
+struct FunctionLimit {
+    metrics: Arc<SharedMetrics>,
+    expected_reaction: LintRuleReaction,
+    limit: Limit,
+}
+
+impl Debug for FunctionLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FunctionLimit")
+            .field("expected_reaction", &self.expected_reaction)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl FunctionLimit {
+    fn boxed<T>(
+        metrics: &Arc<SharedMetrics>,
+        config: &RuleConfig<T>,
+        limit: Limit,
+    ) -> Option<Box<dyn Rule>>
+    where
+        T: Debug + Clone + Serialize + Default,
+    {
+        match config.get_rule_reaction() {
+            LintRuleReaction::Off => None,
+            expected_reaction => Some(Box::new(Self {
+                metrics: metrics.clone(),
+                expected_reaction,
+                limit,
+            })),
+        }
+    }
+
+    fn emit_report(&self, span: Span, message: &str) {
+        HANDLER.with(|handler| match self.expected_reaction {
+            LintRuleReaction::Error => {
+                handler.struct_span_err(span, message).emit();
+            }
+            LintRuleReaction::Warning => {
+                handler.struct_span_warn(span, message).emit();
+            }
+            _ => {}
+        });
+    }
+
+    fn count_lines(&self, function: &FunctionMetrics) -> usize {
+        self.metrics
+            .source_map
+            .with_snippet_of_span(function.span, |snippet| {
+                snippet
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .count()
+            })
+            .unwrap_or(function.lines)
+    }
+
+    fn check(&self, function: &FunctionMetrics) {
+        let report = |problem: String, max: usize| {
+            self.emit_report(
+                function.report_span(),
+                &format!(
+                    "{} has {}. Maximum allowed is {}",
+                    function.describe(),
+                    problem,
+                    max
+                ),
+            )
+        };
+
+        match self.limit {
+            Limit::Complexity { max } if function.complexity > max => {
+                report(format!("a complexity of {}", function.complexity), max)
+            }
+            Limit::Depth { max } => {
+                for (span, depth) in &function.blocks {
+                    if *depth > max {
+                        self.emit_report(
+                            *span,
+                            &format!(
+                                "Blocks are nested too deeply ({}). Maximum allowed is {}",
+                                depth, max
+                            ),
+                        );
+                    }
+                }
+            }
+            Limit::Params { max } if function.params > max => {
+                report(format!("too many parameters ({})", function.params), max)
+            }
+            Limit::Statements {
+                max,
+                ignore_top_level_functions,
+            } if function.statements > max
+                && !(ignore_top_level_functions && function.top_level) =>
+            {
+                report(
+                    format!("too many statements ({})", function.statements),
+                    max,
+                )
+            }
+            Limit::Lines {
+                max,
+                skip_blank_lines,
+            } => {
+                let lines = if skip_blank_lines {
+                    self.count_lines(function)
+                } else {
+                    function.lines
+                };
+
+                if lines > max {
+                    report(format!("too many lines ({})", lines), max)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Rule for FunctionLimit {
+    fn lint_module(&mut self, program: &Module) {
+        for function in self.metrics.get(program) {
+            self.check(function);
+        }
+    }
+
+    fn lint_script(&mut self, program: &Script) {
+        for function in self.metrics.get(program) {
+            self.check(function);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use swc_common::{SourceMap, SyntaxContext};
+use swc_common::{comments::Comments, SourceMap, SyntaxContext};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold};
 
@@ -15,11 +15,14 @@ mod utils;
 #[cfg(feature = "non_critical_lints")]
 #[path = ""]
 pub(crate) mod non_critical_lints {
+    pub mod await_thenable;
     pub mod constructor_super;
+    mod declared_types;
     pub mod default_case_last;
     pub mod default_param_last;
     pub mod dot_notation;
     pub mod eqeqeq;
+    pub mod function_limits;
     pub mod no_alert;
     pub mod no_await_in_loop;
     pub mod no_bitwise;
@@ -28,6 +31,7 @@ pub(crate) mod non_critical_lints {
     pub mod no_debugger;
     pub mod no_empty_function;
     pub mod no_empty_pattern;
+    pub mod no_floating_promises;
     pub mod no_loop_func;
     pub mod no_new;
     pub mod no_new_symbol;
@@ -42,6 +46,7 @@ pub(crate) mod non_critical_lints {
     pub mod prefer_regex_literals;
     pub mod quotes;
     pub mod radix;
+    pub mod require_await;
     pub mod symbol_description;
     pub mod use_is_nan;
     pub mod valid_typeof;
@@ -51,6 +56,9 @@ pub(crate) mod non_critical_lints {
 #[cfg(feature = "non_critical_lints")]
 use non_critical_lints::*;
 
+#[cfg(feature = "non_critical_lints")]
+use crate::suppression::{SuppressibleRules, Suppressions};
+
 pub struct LintParams<'a> {
     pub program: &'a Program,
     pub lint_config: &'a LintConfig,
@@ -58,6 +66,9 @@ pub struct LintParams<'a> {
     pub top_level_ctxt: SyntaxContext,
     pub es_version: EsVersion,
     pub source_map: Arc<SourceMap>,
+    /// Comments of the program, which are used to read `swc-lint-disable`
+    /// directives.
+    pub comments: Option<&'a dyn Comments>,
 }
 
 pub fn all(lint_params: LintParams) -> Vec<Box<dyn Rule>> {
@@ -77,120 +88,215 @@ pub fn all(lint_params: LintParams) -> Vec<Box<dyn Rule>> {
             top_level_ctxt: _,
             es_version,
             source_map,
+            comments,
         } = lint_params;
 
-        rules.extend(no_use_before_define::no_use_before_define(
-            &lint_params.lint_config.no_use_before_define,
-        ));
-
-        rules.extend(no_console::no_console(
-            &lint_config.no_console,
-            unresolved_ctxt,
-        ));
-
-        rules.extend(no_alert::no_alert(
-            &lint_config.no_alert,
-            unresolved_ctxt,
-            es_version,
-        ));
-
-        rules.extend(no_debugger::no_debugger(&lint_config.no_debugger));
-
-        rules.extend(quotes::quotes(&lint_config.quotes));
-
-        rules.extend(prefer_regex_literals::prefer_regex_literals(
-            &lint_config.prefer_regex_literals,
-            unresolved_ctxt,
-            es_version,
-        ));
-
-        rules.extend(dot_notation::dot_notation(
+        let mut suppressible = vec![];
+        let mut add = |name: &'static str, rule: Option<Box<dyn Rule>>| {
+            suppressible.extend(rule.map(|rule| (name, rule)));
+        };
+
+        add(
+            "no-use-before-define",
+            no_use_before_define::no_use_before_define(&lint_config.no_use_before_define),
+        );
+
+        add(
+            "no-console",
+            no_console::no_console(&lint_config.no_console, unresolved_ctxt),
+        );
+
+        add(
+            "no-alert",
+            no_alert::no_alert(&lint_config.no_alert, unresolved_ctxt, es_version),
+        );
+
+        add(
+            "no-debugger",
+            no_debugger::no_debugger(&lint_config.no_debugger),
+        );
+
+        add("quotes", quotes::quotes(&lint_config.quotes));
+
+        add(
+            "prefer-regex-literals",
+            prefer_regex_literals::prefer_regex_literals(
+                &lint_config.prefer_regex_literals,
+                unresolved_ctxt,
+                es_version,
+            ),
+        );
+
+        add(
+            "dot-notation",
+            dot_notation::dot_notation(program, &lint_config.dot_notation),
+        );
+
+        add(
+            "no-empty-function",
+            no_empty_function::no_empty_function(&source_map, &lint_config.no_empty_function),
+        );
+
+        add(
+            "no-empty-pattern",
+            no_empty_pattern::no_empty_pattern(&lint_config.no_empty_pattern),
+        );
+
+        add("eqeqeq", eqeqeq::eqeqeq(&source_map, &lint_config.eqeqeq));
+
+        add(
+            "no-loop-func",
+            no_loop_func::no_loop_func(&lint_config.no_loop_func),
+        );
+
+        add("no-new", no_new::no_new(&lint_config.no_new));
+
+        add(
+            "no-restricted-syntax",
+            no_restricted_syntax::no_restricted_syntax(&lint_config.no_restricted_syntax),
+        );
+
+        add("radix", radix::radix(unresolved_ctxt, &lint_config.radix));
+
+        add(
+            "no-bitwise",
+            no_bitwise::no_bitwise(&lint_config.no_bitwise),
+        );
+
+        add(
+            "default-param-last",
+            default_param_last::default_param_last(&lint_config.default_param_last),
+        );
+
+        add("yoda", yoda::yoda(&source_map, &lint_config.yoda));
+
+        add(
+            "no-new-symbol",
+            no_new_symbol::no_new_symbol(unresolved_ctxt, &lint_config.no_new_symbol),
+        );
+
+        add(
+            "use-isnan",
+            use_is_nan::use_is_nan(unresolved_ctxt, &lint_config.use_isnan),
+        );
+
+        add(
+            "valid-typeof",
+            valid_typeof::valid_typeof(&lint_config.valid_typeof),
+        );
+
+        add(
+            "no-param-reassign",
+            no_param_reassign::no_param_reassign(&lint_config.no_param_reassign),
+        );
+
+        add(
+            "symbol-description",
+            symbol_description::symbol_description(
+                unresolved_ctxt,
+                &lint_config.symbol_description,
+            ),
+        );
+
+        add(
+            "no-obj-calls",
+            no_obj_calls::no_obj_calls(unresolved_ctxt, &lint_config.no_obj_calls),
+        );
+
+        add(
+            "no-throw-literal",
+            no_throw_literal::no_throw_literal(&lint_config.no_throw_literal),
+        );
+
+        add("no-var", no_var::no_var(&lint_config.no_var));
+
+        add(
+            "prefer-const",
+            prefer_const::prefer_const(&lint_config.prefer_const),
+        );
+
+        add(
+            "no-compare-neg-zero",
+            no_compare_neg_zero::no_compare_neg_zero(&lint_config.no_compare_neg_zero),
+        );
+
+        add(
+            "constructor-super",
+            constructor_super::constructor_super(&lint_config.constructor_super),
+        );
+
+        add(
+            "no-sparse-arrays",
+            no_sparse_arrays::no_sparse_arrays(&lint_config.no_sparse_arrays),
+        );
+
+        add(
+            "default-case-last",
+            default_case_last::default_case_last(&lint_config.default_case_last),
+        );
+
+        add(
+            "no-await-in-loop",
+            no_await_in_loop::no_await_in_loop(&lint_config.no_await_in_loop),
+        );
+
+        add(
+            "no-floating-promises",
+            no_floating_promises::no_floating_promises(
+                program,
+                unresolved_ctxt,
+                &lint_config.no_floating_promises,
+            ),
+        );
+
+        add(
+            "await-thenable",
+            await_thenable::await_thenable(program, unresolved_ctxt, &lint_config.await_thenable),
+        );
+
+        add(
+            "require-await",
+            require_await::require_await(&lint_config.require_await),
+        );
+
+        let function_metrics = function_limits::SharedMetrics::new(source_map.clone());
+
+        add(
+            "complexity",
+            function_limits::complexity(&function_metrics, &lint_config.complexity),
+        );
+
+        add(
+            "max-depth",
+            function_limits::max_depth(&function_metrics, &lint_config.max_depth),
+        );
+
+        add(
+            "max-params",
+            function_limits::max_params(&function_metrics, &lint_config.max_params),
+        );
+
+        add(
+            "max-statements",
+            function_limits::max_statements(&function_metrics, &lint_config.max_statements),
+        );
+
+        add(
+            "max-lines-per-function",
+            function_limits::max_lines_per_function(
+                &function_metrics,
+                &lint_config.max_lines_per_function,
+            ),
+        );
+
+        let suppressions = Suppressions::new(
             program,
-            &lint_config.dot_notation,
-        ));
-
-        rules.extend(no_empty_function::no_empty_function(
+            comments,
             &source_map,
-            &lint_config.no_empty_function,
-        ));
-
-        rules.extend(no_empty_pattern::no_empty_pattern(
-            &lint_config.no_empty_pattern,
-        ));
-
-        rules.extend(eqeqeq::eqeqeq(&lint_config.eqeqeq));
-
-        rules.extend(no_loop_func::no_loop_func(&lint_config.no_loop_func));
-
-        rules.extend(no_new::no_new(&lint_config.no_new));
-
-        rules.extend(no_restricted_syntax::no_restricted_syntax(
-            &lint_config.no_restricted_syntax,
-        ));
-
-        rules.extend(radix::radix(unresolved_ctxt, &lint_config.radix));
-
-        rules.extend(no_bitwise::no_bitwise(&lint_config.no_bitwise));
-
-        rules.extend(default_param_last::default_param_last(
-            &lint_config.default_param_last,
-        ));
-
-        rules.extend(yoda::yoda(&lint_config.yoda));
-
-        rules.extend(no_new_symbol::no_new_symbol(
-            unresolved_ctxt,
-            &lint_config.no_new_symbol,
-        ));
-
-        rules.extend(use_is_nan::use_is_nan(
-            unresolved_ctxt,
-            &lint_config.use_isnan,
-        ));
-
-        rules.extend(valid_typeof::valid_typeof(&lint_config.valid_typeof));
-
-        rules.extend(no_param_reassign::no_param_reassign(
-            &lint_config.no_param_reassign,
-        ));
-
-        rules.extend(symbol_description::symbol_description(
-            unresolved_ctxt,
-            &lint_config.symbol_description,
-        ));
-
-        rules.extend(no_obj_calls::no_obj_calls(
-            unresolved_ctxt,
-            &lint_config.no_obj_calls,
-        ));
-
-        rules.extend(no_throw_literal::no_throw_literal(
-            &lint_config.no_throw_literal,
-        ));
-
-        rules.extend(no_var::no_var(&lint_config.no_var));
-
-        rules.extend(prefer_const::prefer_const(&lint_config.prefer_const));
-
-        rules.extend(no_compare_neg_zero::no_compare_neg_zero(
-            &lint_config.no_compare_neg_zero,
-        ));
-
-        rules.extend(constructor_super::constructor_super(
-            &lint_config.constructor_super,
-        ));
-
-        rules.extend(no_sparse_arrays::no_sparse_arrays(
-            &lint_config.no_sparse_arrays,
-        ));
-
-        rules.extend(default_case_last::default_case_last(
-            &lint_config.default_case_last,
-        ));
+            &lint_config.report_unused_disable_directives,
+        );
 
-        rules.extend(no_await_in_loop::no_await_in_loop(
-            &lint_config.no_await_in_loop,
-        ));
+        rules.push(Box::new(SuppressibleRules::new(suppressible, suppressions)));
     }
 
     rules
@@ -0,0 +1,155 @@
+use swc_common::{errors::HANDLER, Span, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::{
+    config::{LintRuleReaction, RuleConfig},
+    rule::{visitor_rule, Rule},
+    rules::non_critical_lints::declared_types::{DeclaredTypes, Thenable},
+};
+
+const MESSAGE: &str =
+    "Promises must be awaited, returned, or have a rejection handler attached with `.catch()`";
+
+pub fn no_floating_promises(
+    program: &Program,
+    unresolved_ctxt: SyntaxContext,
+    config: &RuleConfig<()>,
+) -> Option<Box<dyn Rule>> {
+    let rule_reaction = config.get_rule_reaction();
+
+    match rule_reaction {
+        LintRuleReaction::Off => None,
+        _ => Some(visitor_rule(NoFloatingPromises::new(
+            program,
+            unresolved_ctxt,
+            rule_reaction,
+        ))),
+    }
+}
+
+#[derive(Debug, Default)]
+struct NoFloatingPromises {
+    expected_reaction: LintRuleReaction,
+    types: DeclaredTypes,
+
+    // rule internal
+    /// Span of the class `this` refers to.
+    class: Option<Span>,
+}
+
+impl NoFloatingPromises {
+    fn new(
+        program: &Program,
+        unresolved_ctxt: SyntaxContext,
+        expected_reaction: LintRuleReaction,
+    ) -> Self {
+        Self {
+            expected_reaction,
+            types: DeclaredTypes::collect(program, unresolved_ctxt),
+            class: None,
+        }
+    }
+
+    fn emit_report(&self, span: Span) {
+        HANDLER.with(|handler| match self.expected_reaction {
+            LintRuleReaction::Error => {
+                handler.struct_span_err(span, MESSAGE).emit();
+            }
+            LintRuleReaction::Warning => {
+                handler.struct_span_warn(span, MESSAGE).emit();
+            }
+            _ => {}
+        });
+    }
+
+    /// Checks the value of an expression statement.
+    ///
+    /// Promises in arrow function bodies like `() => promise` are returned,
+    /// so they are not reported even if the caller ignores the return value.
+    fn is_floating(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Paren(ParenExpr { expr, .. }) => self.is_floating(expr),
+
+            Expr::Seq(SeqExpr { exprs, .. }) => exprs.iter().any(|expr| self.is_floating(expr)),
+
+            Expr::Bin(BinExpr {
+                op: op!("&&") | op!("||") | op!("??"),
+                left,
+                right,
+                ..
+            }) => self.is_floating(left) || self.is_floating(right),
+
+            Expr::Cond(CondExpr { cons, alt, .. }) => {
+                self.is_floating(cons) || self.is_floating(alt)
+            }
+
+            // `void promise` marks the promise as ignored on purpose.
+            Expr::Unary(UnaryExpr {
+                op: op!("void"), ..
+            }) => false,
+
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) => match &**callee {
+                Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Ident(prop),
+                    ..
+                }) if self.types.expr_thenable(obj, self.class) == Thenable::Yes => {
+                    match &*prop.sym {
+                        "catch" => args.is_empty(),
+                        "then" => args.len() < 2,
+                        _ => true,
+                    }
+                }
+                _ => self.types.expr_thenable(expr, self.class) == Thenable::Yes,
+            },
+
+            _ => self.types.expr_thenable(expr, self.class) == Thenable::Yes,
+        }
+    }
+}
+
+impl Visit for NoFloatingPromises {
+    noop_visit_type!();
+
+    fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt) {
+        if self.is_floating(&expr_stmt.expr) {
+            self.emit_report(expr_stmt.span);
+        }
+
+        expr_stmt.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, class: &Class) {
+        let prev = self.class.replace(class.span);
+
+        class.visit_children_with(self);
+
+        self.class = prev;
+    }
+
+    fn visit_class_method(&mut self, class_method: &ClassMethod) {
+        class_method.key.visit_with(self);
+
+        // Unlike other functions, methods do not change `this`.
+        class_method.function.visit_children_with(self);
+    }
+
+    fn visit_private_method(&mut self, private_method: &PrivateMethod) {
+        private_method.key.visit_with(self);
+
+        private_method.function.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let prev = self.class.take();
+
+        function.visit_children_with(self);
+
+        self.class = prev;
+    }
+}
@@ -1,9 +1,15 @@
-use swc_common::{errors::HANDLER, Span};
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    errors::HANDLER,
+    BytePos, Span,
+};
 use swc_ecma_ast::*;
+use swc_ecma_utils::{find_pat_ids, DestructuringFinder};
 use swc_ecma_visit::{Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
 };
 
@@ -18,35 +24,225 @@ pub fn no_var(config: &RuleConfig<()>) -> Option<Box<dyn Rule>> {
     }
 }
 
+#[derive(Debug)]
+struct VarDeclMeta {
+    span: Span,
+    bindings: Vec<Ident>,
+    /// `declare var` is not fixed, as the span does not start with `var`.
+    declare: bool,
+}
+
 #[derive(Debug, Default)]
 struct NoVar {
     expected_reaction: LintRuleReaction,
+
+    // rule internal
+    var_decls: Vec<VarDeclMeta>,
+    /// Spans of `var` declarations which are statements of a function body or
+    /// a module, so their scope does not change with `let`.
+    top_level_decls: AHashSet<Span>,
+    /// Number of declarations of each binding.
+    declarations: AHashMap<Id, usize>,
+    /// Positions of all identifiers referring to each binding.
+    occurrences: AHashMap<Id, Vec<BytePos>>,
 }
 
 impl NoVar {
     fn new(expected_reaction: LintRuleReaction) -> Self {
-        Self { expected_reaction }
+        Self {
+            expected_reaction,
+            ..Default::default()
+        }
     }
 
-    fn emit_error(&self, span: Span) {
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, MESSAGE).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, MESSAGE).emit();
+    fn emit_error(&self, span: Span, fix: Option<Fix>) {
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, MESSAGE),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, MESSAGE),
+                _ => return,
+            };
+
+            if let Some(fix) = fix {
+                fix.attach_to(&mut builder, MESSAGE);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
+
+    fn add_declarations<T>(&mut self, node: &T)
+    where
+        T: VisitWith<DestructuringFinder<Id>>,
+    {
+        for id in find_pat_ids::<_, Id>(node) {
+            *self.declarations.entry(id).or_default() += 1;
+        }
+    }
+
+    fn mark_top_level<'a>(&mut self, stmts: impl Iterator<Item = &'a Stmt>) {
+        for stmt in stmts {
+            if let Stmt::Decl(Decl::Var(var_decl)) = stmt {
+                self.top_level_decls.insert(var_decl.span);
+            }
+        }
+    }
+
+    /// Replacing `var` with `let` is safe if the scope of the bindings stays
+    /// the same, they are declared once and they are not used before the
+    /// declaration, which would be an error in the temporal dead zone.
+    fn is_safe_to_fix(&self, var_decl: &VarDeclMeta) -> bool {
+        if var_decl.declare || !self.top_level_decls.contains(&var_decl.span) {
+            return false;
+        }
+
+        var_decl.bindings.iter().all(|binding| {
+            let id = binding.to_id();
+
+            self.declarations.get(&id) == Some(&1)
+                && self.occurrences.get(&id).map_or(true, |occurrences| {
+                    occurrences
+                        .iter()
+                        .all(|pos| *pos == binding.span.lo || *pos >= var_decl.span.hi)
+                })
+        })
+    }
+
+    fn emit_ordered(&self) {
+        for var_decl in &self.var_decls {
+            let fix = self.is_safe_to_fix(var_decl).then(|| {
+                Fix::replace(
+                    Span::new(
+                        var_decl.span.lo,
+                        var_decl.span.lo + BytePos(3),
+                        Default::default(),
+                    ),
+                    "let",
+                )
+            });
+
+            self.emit_error(var_decl.span, fix);
+        }
+    }
 }
 
 impl Visit for NoVar {
+    fn visit_module(&mut self, module: &Module) {
+        for item in &module.body {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(var_decl),
+                    ..
+                })) => {
+                    self.top_level_decls.insert(var_decl.span);
+                }
+                _ => {}
+            }
+        }
+
+        module.visit_children_with(self);
+
+        self.emit_ordered();
+    }
+
+    fn visit_script(&mut self, script: &Script) {
+        // `var` declarations of scripts are not fixed, as they create
+        // properties of the global object.
+        script.visit_children_with(self);
+
+        self.emit_ordered();
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.mark_top_level(body.stmts.iter());
+        }
+
+        function.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &*arrow_expr.body {
+            self.mark_top_level(body.stmts.iter());
+        }
+
+        self.add_declarations(&arrow_expr.params);
+
+        arrow_expr.visit_children_with(self);
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        if let Some(body) = &constructor.body {
+            self.mark_top_level(body.stmts.iter());
+        }
+
+        constructor.visit_children_with(self);
+    }
+
+    fn visit_getter_prop(&mut self, getter_prop: &GetterProp) {
+        if let Some(body) = &getter_prop.body {
+            self.mark_top_level(body.stmts.iter());
+        }
+
+        getter_prop.visit_children_with(self);
+    }
+
+    fn visit_setter_prop(&mut self, setter_prop: &SetterProp) {
+        if let Some(body) = &setter_prop.body {
+            self.mark_top_level(body.stmts.iter());
+        }
+
+        self.add_declarations(&setter_prop.param);
+
+        setter_prop.visit_children_with(self);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        self.add_declarations(&param.pat);
+
+        param.visit_children_with(self);
+    }
+
+    fn visit_catch_clause(&mut self, catch_clause: &CatchClause) {
+        self.add_declarations(&catch_clause.param);
+
+        catch_clause.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        *self.declarations.entry(fn_decl.ident.to_id()).or_default() += 1;
+
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        *self
+            .declarations
+            .entry(class_decl.ident.to_id())
+            .or_default() += 1;
+
+        class_decl.visit_children_with(self);
+    }
+
     fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        self.add_declarations(&var_decl.decls);
+
         if let VarDeclKind::Var = var_decl.kind {
-            self.emit_error(var_decl.span);
+            self.var_decls.push(VarDeclMeta {
+                span: var_decl.span,
+                bindings: find_pat_ids(&var_decl.decls),
+                declare: var_decl.declare,
+            });
         }
 
         var_decl.visit_children_with(self);
     }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.occurrences
+            .entry(ident.to_id())
+            .or_default()
+            .push(ident.span.lo);
+    }
 }
@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
-use swc_common::{collections::AHashMap, errors::HANDLER, Span};
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    errors::HANDLER,
+    BytePos, Span,
+};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
     rules::utils::unwrap_seqs_and_parens,
 };
@@ -34,6 +39,8 @@ pub fn prefer_const(config: &RuleConfig<PreferConstConfig>) -> Option<Box<dyn Ru
 #[derive(Debug)]
 struct VariableMeta {
     span: Span,
+    /// Span of the `let` declaration of the variable, if it can be fixed.
+    decl_span: Option<Span>,
     order: usize,
     initialized: bool,
     block_depth: usize,
@@ -59,6 +66,10 @@ struct PreferConst {
     scope_vars_idx: usize,
     block_depth: usize,
     cycle_head_depth: usize,
+    /// The `let` declaration which is visited.
+    decl_span: Option<Span>,
+    /// Number of bindings of each `let` declaration.
+    decl_bindings: AHashMap<Span, usize>,
 
     ignore_read_before_assign: bool,
 }
@@ -71,32 +82,43 @@ impl PreferConst {
             scope_vars_idx: 0,
             block_depth: 0,
             cycle_head_depth: 0,
+            decl_span: None,
+            decl_bindings: Default::default(),
 
             ignore_read_before_assign: rule_config.ignore_read_before_assign,
         }
     }
 
-    fn emit_report(&self, span: Span, var_name: &str) {
+    fn emit_report(&self, span: Span, var_name: &str, fix: Option<Fix>) {
         let message = format!("'{}' is never reassigned. Use 'const' insted", var_name);
 
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, &message).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, &message).emit();
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, &message),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, &message),
+                _ => return,
+            };
+
+            if let Some(fix) = fix {
+                fix.attach_to(&mut builder, &message);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
 
     fn add_var_meta(&mut self, ident: &Ident, initialized: bool) {
         self.scope_vars_idx += 1;
 
+        if let Some(decl_span) = self.decl_span {
+            *self.decl_bindings.entry(decl_span).or_default() += 1;
+        }
+
         self.vars_meta.insert(
             ident.to_id(),
             VariableMeta {
                 span: ident.span,
+                decl_span: self.decl_span,
                 order: self.scope_vars_idx,
                 initialized,
                 block_depth: self.block_depth,
@@ -188,24 +210,55 @@ impl PreferConst {
     }
 
     fn emit_ordered(&self) {
-        let mut vars = self.vars_meta.iter().collect::<Vec<_>>();
+        let mut vars = self
+            .vars_meta
+            .iter()
+            .filter(|(_, var_meta)| {
+                let postinitialized = if self.ignore_read_before_assign {
+                    var_meta.postinitialized && !var_meta.used_before_initialize
+                } else {
+                    var_meta.postinitialized
+                };
+
+                var_meta.initialized
+                    || postinitialized
+                    || var_meta.destructuring_assign
+                    || var_meta.declared_into_cycle_head
+            })
+            .collect::<Vec<_>>();
 
         vars.sort_by(|(_, a), (_, b)| a.order.cmp(&b.order));
 
+        // A declaration can only be changed to `const` if every binding of it
+        // is reported, and is assigned by the declaration itself.
+        let mut const_bindings = AHashMap::<Span, usize>::default();
+        for (_, var_meta) in &vars {
+            if let Some(decl_span) = var_meta.decl_span {
+                if (var_meta.initialized || var_meta.declared_into_cycle_head)
+                    && !var_meta.postinitialized
+                    && !var_meta.destructuring_assign
+                {
+                    *const_bindings.entry(decl_span).or_default() += 1;
+                }
+            }
+        }
+
+        let mut fixed_decls = AHashSet::default();
         vars.into_iter().for_each(|(id, var_meta)| {
-            let postinitialized = if self.ignore_read_before_assign {
-                var_meta.postinitialized && !var_meta.used_before_initialize
-            } else {
-                var_meta.postinitialized
-            };
+            let fix = var_meta
+                .decl_span
+                .filter(|decl_span| {
+                    const_bindings.get(decl_span) == self.decl_bindings.get(decl_span)
+                        && fixed_decls.insert(*decl_span)
+                })
+                .map(|decl_span| {
+                    Fix::replace(
+                        Span::new(decl_span.lo, decl_span.lo + BytePos(3), Default::default()),
+                        "const",
+                    )
+                });
 
-            if var_meta.initialized
-                || postinitialized
-                || var_meta.destructuring_assign
-                || var_meta.declared_into_cycle_head
-            {
-                self.emit_report(var_meta.span, &id.0);
-            }
+            self.emit_report(var_meta.span, &id.0, fix);
         });
     }
 }
@@ -225,9 +278,14 @@ impl Visit for PreferConst {
 
     fn visit_var_decl(&mut self, var_decl: &VarDecl) {
         if let VarDeclKind::Let = var_decl.kind {
+            // `declare let` is not fixed, as the span does not start with `let`.
+            self.decl_span = (!var_decl.declare).then_some(var_decl.span);
+
             var_decl.decls.iter().for_each(|var_decl| {
                 self.collect_decl_pat(var_decl.init.is_some(), &var_decl.name);
-            })
+            });
+
+            self.decl_span = None;
         }
 
         var_decl.visit_children_with(self);
@@ -5,8 +5,9 @@ use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
-    rules::utils::{resolve_string_quote_type, QuotesType},
+    rules::utils::{requote, resolve_string_quote_type, QuotesType},
 };
 
 const MUST_USE_SINGLE_QUOTES_MESSAGE: &str = "String must use singlequotes";
@@ -50,24 +51,39 @@ impl Quotes {
         }
     }
 
-    fn emit_report(&self, span: Span) {
+    fn emit_report(&self, span: Span, fix: Option<Fix>) {
         let message = match &self.prefer {
             QuotesType::Backtick => MUST_USE_BACKTICK_QUOTES_MESSAGE,
             QuotesType::Single => MUST_USE_SINGLE_QUOTES_MESSAGE,
             QuotesType::Double => MUST_USE_DOUBLE_QUOTES_MESSAGE,
         };
 
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, message).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, message).emit();
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, message),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, message),
+                _ => return,
+            };
+
+            if let Some(fix) = fix {
+                fix.attach_to(&mut builder, message);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
 
+    /// Rewrites a string with the preferred quotes.
+    fn build_str_fix(&self, lit_str: &Str, found_quote_type: QuotesType) -> Option<Fix> {
+        let raw = lit_str.raw.as_deref()?;
+        let content = raw.get(1..raw.len() - 1)?;
+
+        Some(Fix::replace(
+            lit_str.span,
+            requote(content, found_quote_type, self.prefer),
+        ))
+    }
+
     fn is_mirroring_escape(&self, value: &str) -> bool {
         let quote = self.prefer.get_char();
 
@@ -91,14 +107,14 @@ impl Quotes {
                     return;
                 }
 
-                self.emit_report(*span);
+                self.emit_report(*span, self.build_str_fix(lit_str, found_quote_type));
             }
             (QuotesType::Single, QuotesType::Double) => {
                 if self.avoid_escape && self.is_mirroring_escape(value) {
                     return;
                 }
 
-                self.emit_report(*span);
+                self.emit_report(*span, self.build_str_fix(lit_str, found_quote_type));
             }
             (QuotesType::Backtick, _) => {
                 if is_method_key_check {
@@ -109,14 +125,18 @@ impl Quotes {
                     return;
                 }
 
-                self.emit_report(*span);
+                self.emit_report(*span, self.build_str_fix(lit_str, found_quote_type));
             }
             _ => {}
         }
     }
 
     fn check_tpl_str(&self, tpl_str: &Tpl) {
-        let Tpl { span, exprs, .. } = tpl_str;
+        let Tpl {
+            span,
+            exprs,
+            quasis,
+        } = tpl_str;
 
         if self.allow_template_literals {
             return;
@@ -130,7 +150,14 @@ impl Quotes {
             return;
         }
 
-        self.emit_report(*span);
+        let fix = quasis.first().map(|quasi| {
+            Fix::replace(
+                *span,
+                requote(&quasi.raw, QuotesType::Backtick, self.prefer),
+            )
+        });
+
+        self.emit_report(*span, fix);
     }
 }
 
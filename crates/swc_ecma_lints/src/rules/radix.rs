@@ -2,13 +2,14 @@ use serde::{Deserialize, Serialize};
 use swc_atoms::JsWord;
 use swc_common::{
     errors::{DiagnosticBuilder, HANDLER},
-    Span, SyntaxContext,
+    Span, Spanned, SyntaxContext,
 };
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
     rules::utils::{extract_arg_val, unwrap_seqs_and_parens, ArgValue},
 };
@@ -82,7 +83,7 @@ impl Radix {
         }
     }
 
-    fn emit_report(&self, span: Span, message: &str, note: Option<&str>) {
+    fn emit_report(&self, span: Span, message: &str, note: Option<&str>, fix: Option<Fix>) {
         HANDLER.with(|handler| {
             let mut builder: Option<DiagnosticBuilder> = None;
 
@@ -101,6 +102,10 @@ impl Radix {
                     builder.span_note(span, note);
                 }
 
+                if let Some(fix) = fix {
+                    fix.attach_to(&mut builder, message);
+                }
+
                 builder.emit();
             }
         });
@@ -132,7 +137,7 @@ impl Radix {
         }
 
         if call_expr.args.get(0).is_none() {
-            self.emit_report(call_expr.span, MISSING_PARAMS_MESSAGE, None);
+            self.emit_report(call_expr.span, MISSING_PARAMS_MESSAGE, None, None);
 
             return;
         }
@@ -149,28 +154,50 @@ impl Radix {
                     ArgValue::Ident => {}
                     ArgValue::Number(radix) => {
                         if radix.fract() != 0.0 || !(2f64..=36f64).contains(radix) {
-                            self.emit_report(call_expr.span, INVALID_RADIX_MESSAGE, None);
+                            self.emit_report(call_expr.span, INVALID_RADIX_MESSAGE, None, None);
 
                             return;
                         }
 
                         if let RadixMode::AsNeeded = self.radix_mode {
                             if *radix == 10f64 {
-                                self.emit_report(call_expr.span, REDUNDANT_RADIX_MESSAGE, None);
+                                // Removes everything from the end of the first argument.
+                                let fix = (call_expr.args.len() == 2).then(|| {
+                                    Fix::remove(
+                                        call_expr.args[1]
+                                            .span()
+                                            .with_lo(call_expr.args[0].span().hi),
+                                    )
+                                });
+
+                                self.emit_report(
+                                    call_expr.span,
+                                    REDUNDANT_RADIX_MESSAGE,
+                                    None,
+                                    fix,
+                                );
                             }
                         }
                     }
                     _ => {
-                        self.emit_report(call_expr.span, INVALID_RADIX_MESSAGE, None);
+                        self.emit_report(call_expr.span, INVALID_RADIX_MESSAGE, None, None);
                     }
                 };
             }
             None => {
                 if let RadixMode::Always = self.radix_mode {
+                    let arg = &call_expr.args[0];
+                    // The radix of spread arguments is unknown.
+                    let fix = arg
+                        .spread
+                        .is_none()
+                        .then(|| Fix::insert(arg.span().hi, ", 10"));
+
                     self.emit_report(
                         call_expr.span,
                         MISSING_RADIX_MESSAGE,
                         Some(ADD_10_RADIX_MESSAGE),
+                        fix,
                     );
                 }
             }
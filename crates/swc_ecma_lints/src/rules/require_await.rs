@@ -0,0 +1,172 @@
+use swc_common::{errors::HANDLER, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::{
+    config::{LintRuleReaction, RuleConfig},
+    rule::{visitor_rule, Rule},
+};
+
+pub fn require_await(config: &RuleConfig<()>) -> Option<Box<dyn Rule>> {
+    let rule_reaction = config.get_rule_reaction();
+
+    match rule_reaction {
+        LintRuleReaction::Off => None,
+        _ => Some(visitor_rule(RequireAwait::new(rule_reaction))),
+    }
+}
+
+#[derive(Debug, Default)]
+struct RequireAwait {
+    expected_reaction: LintRuleReaction,
+}
+
+impl RequireAwait {
+    fn new(expected_reaction: LintRuleReaction) -> Self {
+        Self { expected_reaction }
+    }
+
+    fn emit_report(&self, span: Span, name: Option<&str>) {
+        let message = match name {
+            Some(name) => format!("Async function `{}` has no `await` expression", name),
+            None => "Async function has no `await` expression".to_string(),
+        };
+
+        HANDLER.with(|handler| match self.expected_reaction {
+            LintRuleReaction::Error => {
+                handler.struct_span_err(span, &message).emit();
+            }
+            LintRuleReaction::Warning => {
+                handler.struct_span_warn(span, &message).emit();
+            }
+            _ => {}
+        });
+    }
+
+    /// `span` is the span of the name of the function, if any.
+    fn check_function(&self, span: Span, name: Option<&str>, function: &Function) {
+        if let Some(body) = &function.body {
+            self.check(
+                span,
+                name,
+                function.is_async && !function.is_generator,
+                body,
+            );
+        }
+    }
+
+    fn check<N>(&self, span: Span, name: Option<&str>, is_async: bool, body: &N)
+    where
+        N: VisitWith<AwaitFinder>,
+    {
+        if !is_async {
+            return;
+        }
+
+        let mut finder = AwaitFinder { found: false };
+        body.visit_with(&mut finder);
+
+        if !finder.found {
+            self.emit_report(span, name);
+        }
+    }
+}
+
+fn prop_name(key: &PropName) -> Option<&str> {
+    match key {
+        PropName::Ident(ident) => Some(&ident.sym),
+        PropName::Str(s) => Some(&s.value),
+        _ => None,
+    }
+}
+
+impl Visit for RequireAwait {
+    noop_visit_type!();
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.check_function(
+            fn_decl.ident.span,
+            Some(&fn_decl.ident.sym),
+            &fn_decl.function,
+        );
+
+        fn_decl.function.visit_children_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, fn_expr: &FnExpr) {
+        match &fn_expr.ident {
+            Some(ident) => self.check_function(ident.span, Some(&ident.sym), &fn_expr.function),
+            None => self.check_function(fn_expr.function.span, None, &fn_expr.function),
+        }
+
+        fn_expr.function.visit_children_with(self);
+    }
+
+    fn visit_class_method(&mut self, class_method: &ClassMethod) {
+        self.check_function(
+            class_method.key.span(),
+            prop_name(&class_method.key),
+            &class_method.function,
+        );
+
+        class_method.visit_children_with(self);
+    }
+
+    fn visit_private_method(&mut self, private_method: &PrivateMethod) {
+        self.check_function(
+            private_method.key.span,
+            Some(&format!("#{}", private_method.key.id.sym)),
+            &private_method.function,
+        );
+
+        private_method.visit_children_with(self);
+    }
+
+    fn visit_method_prop(&mut self, method_prop: &MethodProp) {
+        self.check_function(
+            method_prop.key.span(),
+            prop_name(&method_prop.key),
+            &method_prop.function,
+        );
+
+        method_prop.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+        self.check(
+            arrow_expr.span,
+            None,
+            arrow_expr.is_async && !arrow_expr.is_generator,
+            &arrow_expr.body,
+        );
+
+        arrow_expr.visit_children_with(self);
+    }
+}
+
+/// Finds `await` of a function body, ignoring nested functions.
+struct AwaitFinder {
+    found: bool,
+}
+
+impl Visit for AwaitFinder {
+    noop_visit_type!();
+
+    fn visit_await_expr(&mut self, _: &AwaitExpr) {
+        self.found = true;
+    }
+
+    fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt) {
+        if for_of_stmt.is_await {
+            self.found = true;
+        }
+
+        for_of_stmt.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
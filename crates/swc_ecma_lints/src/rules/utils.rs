@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use swc_atoms::Atom;
 use swc_common::SyntaxContext;
+#[cfg(feature = "non_critical_lints")]
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+#[cfg(feature = "non_critical_lints")]
+use swc_ecma_ast::BinExpr;
 use swc_ecma_ast::{
     Expr, Lit, MemberExpr, MemberProp, Number, ParenExpr, Regex, SeqExpr, Str, TaggedTpl, Tpl,
 };
@@ -101,3 +105,70 @@ pub fn unwrap_seqs_and_parens(expr: &Expr) -> &Expr {
         _ => expr,
     }
 }
+
+/// Finds the span of the operator of a binary expression, for fixes which
+/// replace it. Returns [None] if there is anything but whitespace between the
+/// operands and the operator, e.g. a comment.
+#[cfg(feature = "non_critical_lints")]
+pub fn bin_expr_op_span(source_map: &SourceMap, bin_expr: &BinExpr) -> Option<Span> {
+    let span = Span::new(
+        bin_expr.left.span().hi,
+        bin_expr.right.span().lo,
+        Default::default(),
+    );
+    let snippet = source_map
+        .with_snippet_of_span(span, |s| s.to_string())
+        .ok()?;
+    let op = bin_expr.op.as_str();
+
+    let offset = snippet.find(op)?;
+    if !snippet[..offset].trim().is_empty() || !snippet[offset + op.len()..].trim().is_empty() {
+        return None;
+    }
+
+    let lo = span.lo + BytePos(offset as u32);
+
+    Some(Span::new(
+        lo,
+        lo + BytePos(op.len() as u32),
+        Default::default(),
+    ))
+}
+
+/// Converts the content of a string or template literal without the
+/// enclosing quotes, from `from` quotes to `to` quotes. Escapes of the old
+/// quote are removed, and occurrences of the new quote are escaped.
+#[cfg(feature = "non_critical_lints")]
+pub fn requote(content: &str, from: QuotesType, to: QuotesType) -> String {
+    let from = from.get_char();
+    let to_char = to.get_char();
+
+    let mut result = String::with_capacity(content.len() + 2);
+    result.push(to_char);
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(next) if next == from && next != to_char => result.push(next),
+                Some(next) => {
+                    result.push(c);
+                    result.push(next);
+                }
+                None => result.push(c),
+            },
+            c if c == to_char => {
+                result.push('\\');
+                result.push(c);
+            }
+            '$' if to_char == '`' && chars.peek() == Some(&'{') => result.push_str("\\$"),
+            // Only templates contain line breaks, which are not allowed in strings.
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            _ => result.push(c),
+        }
+    }
+
+    result.push(to_char);
+    result
+}
@@ -1,11 +1,18 @@
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
 use serde::{Deserialize, Serialize};
-use swc_common::{errors::HANDLER, Span};
+use swc_common::{errors::HANDLER, SourceMap, Span, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use crate::{
     config::{LintRuleReaction, RuleConfig},
+    fix::Fix,
     rule::{visitor_rule, Rule},
+    rules::utils::bin_expr_op_span,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -32,35 +39,48 @@ pub struct YodaConfig {
     only_equality: bool,
 }
 
-pub fn yoda(config: &RuleConfig<YodaConfig>) -> Option<Box<dyn Rule>> {
+pub fn yoda(source_map: &Arc<SourceMap>, config: &RuleConfig<YodaConfig>) -> Option<Box<dyn Rule>> {
     match config.get_rule_reaction() {
         LintRuleReaction::Off => None,
-        _ => Some(visitor_rule(Yoda::new(config))),
+        _ => Some(visitor_rule(Yoda::new(source_map.clone(), config))),
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 struct Yoda {
     expected_reaction: LintRuleReaction,
+    source_map: Arc<SourceMap>,
 
     mode: YodaConfigMode,
     only_equality: bool,
     except_range: bool,
 }
 
+impl Debug for Yoda {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Yoda")
+            .field("expected_reaction", &self.expected_reaction)
+            .field("mode", &self.mode)
+            .field("only_equality", &self.only_equality)
+            .field("except_range", &self.except_range)
+            .finish()
+    }
+}
+
 impl Yoda {
-    fn new(config: &RuleConfig<YodaConfig>) -> Self {
+    fn new(source_map: Arc<SourceMap>, config: &RuleConfig<YodaConfig>) -> Self {
         let rule_config = config.get_rule_config();
 
         Self {
             expected_reaction: config.get_rule_reaction(),
+            source_map,
             mode: rule_config.mode,
             only_equality: rule_config.only_equality,
             except_range: rule_config.except_range,
         }
     }
 
-    fn emit_report(&self, span: Span, op: BinaryOp) {
+    fn emit_report(&self, span: Span, op: BinaryOp, fix: Option<Fix>) {
         let side = match self.mode {
             YodaConfigMode::Always => "left",
             YodaConfigMode::Never => "right",
@@ -68,17 +88,73 @@ impl Yoda {
 
         let message = format!("Expected literal to be on the {} side of {}", side, op);
 
-        HANDLER.with(|handler| match self.expected_reaction {
-            LintRuleReaction::Error => {
-                handler.struct_span_err(span, &message).emit();
-            }
-            LintRuleReaction::Warning => {
-                handler.struct_span_warn(span, &message).emit();
+        HANDLER.with(|handler| {
+            let mut builder = match self.expected_reaction {
+                LintRuleReaction::Error => handler.struct_span_err(span, &message),
+                LintRuleReaction::Warning => handler.struct_span_warn(span, &message),
+                _ => return,
+            };
+
+            if let Some(fix) = fix {
+                fix.attach_to(&mut builder, &message);
             }
-            _ => {}
+
+            builder.emit();
         });
     }
 
+    /// Swaps the operands of a comparison, flipping the operator if needed.
+    fn build_fix(&self, bin_expr: &BinExpr) -> Option<Fix> {
+        let flipped = match bin_expr.op {
+            op!("==") | op!("===") | op!("!=") | op!("!==") => bin_expr.op,
+            op!("<") => op!(">"),
+            op!("<=") => op!(">="),
+            op!(">") => op!("<"),
+            op!(">=") => op!("<="),
+            _ => return None,
+        };
+
+        // Operands which bind tighter than any binary operator can be swapped
+        // without changing the precedence.
+        let can_swap = |expr: &Expr| {
+            matches!(
+                expr,
+                Expr::Ident(..)
+                    | Expr::Lit(..)
+                    | Expr::Member(..)
+                    | Expr::SuperProp(..)
+                    | Expr::Call(..)
+                    | Expr::OptChain(..)
+                    | Expr::New(..)
+                    | Expr::Paren(..)
+                    | Expr::Tpl(..)
+                    | Expr::TaggedTpl(..)
+                    | Expr::This(..)
+                    | Expr::Array(..)
+                    | Expr::Unary(..)
+            )
+        };
+        if !can_swap(&bin_expr.left) || !can_swap(&bin_expr.right) {
+            return None;
+        }
+
+        let op_span = bin_expr_op_span(&self.source_map, bin_expr)?;
+        let left = self
+            .source_map
+            .with_snippet_of_span(bin_expr.left.span(), |s| s.to_string())
+            .ok()?;
+        let right = self
+            .source_map
+            .with_snippet_of_span(bin_expr.right.span(), |s| s.to_string())
+            .ok()?;
+
+        Some(
+            Fix::replace(bin_expr.left.span(), right)
+                .and_replace(op_span, flipped.as_str())
+                .and_replace(bin_expr.right.span(), left),
+        )
+    }
+
     fn is_yoda_style(&self, bin_expr: &BinExpr) -> bool {
         let left_expr = bin_expr.left.as_ref();
         match left_expr {
@@ -105,7 +181,7 @@ impl Yoda {
             }
         }
 
-        self.emit_report(bin_expr.span, bin_expr.op);
+        self.emit_report(bin_expr.span, bin_expr.op, self.build_fix(bin_expr));
     }
 }
 
@@ -0,0 +1,318 @@
+//! Inline suppression of lint rules using comments.
+//!
+//! - `// swc-lint-disable-next-line no-console, no-alert` silences the listed
+//!   rules for the next line.
+//! - `/* swc-lint-disable no-console */` silences the listed rules until a
+//!   matching `/* swc-lint-enable no-console */`, or the end of the file. A
+//!   directive at the top of a file silences the rules for the whole file.
+//!
+//! Without rule names, a directive applies to all rules. A description may
+//! follow `--`, e.g. `// swc-lint-disable-next-line no-console -- debug only`.
+//!
+//! Problems of critical rules like `const-assign` are errors of the program
+//! itself, so they cannot be suppressed.
+
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+use swc_common::{
+    comments::{Comment, Comments},
+    errors::{Diagnostic, DiagnosticBuilder, HANDLER},
+    BytePos, SourceMap, Span,
+};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    config::{LintRuleReaction, RuleConfig},
+    rule::{capture_diagnostics, Rule},
+};
+
+const DISABLE_NEXT_LINE: &str = "swc-lint-disable-next-line";
+const DISABLE: &str = "swc-lint-disable";
+const ENABLE: &str = "swc-lint-enable";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectiveKind {
+    DisableNextLine,
+    Disable,
+    Enable,
+}
+
+/// Parses the text of a comment, returning the kind of the directive and the
+/// names of the rules.
+fn parse_directive(text: &str) -> Option<(DirectiveKind, Vec<&str>)> {
+    let text = text.trim();
+
+    let (kind, rest) = [
+        (DirectiveKind::DisableNextLine, DISABLE_NEXT_LINE),
+        (DirectiveKind::Disable, DISABLE),
+        (DirectiveKind::Enable, ENABLE),
+    ]
+    .into_iter()
+    .find_map(|(kind, prefix)| {
+        let rest = text.strip_prefix(prefix)?;
+
+        (rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some((kind, rest))
+    })?;
+
+    let rules = rest
+        .split("--")
+        .next()
+        .unwrap_or_default()
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|rule| !rule.is_empty())
+        .collect();
+
+    Some((kind, rules))
+}
+
+#[derive(Debug)]
+struct Suppression {
+    /// Span of the comment of the directive.
+    directive: Span,
+    /// [None] if the directive applies to all rules.
+    rule: Option<String>,
+    lo: BytePos,
+    /// [None] if the suppression lasts until the end of the file.
+    hi: Option<BytePos>,
+    used: bool,
+}
+
+impl Suppression {
+    fn matches(&self, rule: &str, pos: BytePos) -> bool {
+        self.rule.as_deref().map_or(true, |r| r == rule)
+            && self.lo <= pos
+            && self.hi.map_or(true, |hi| pos < hi)
+    }
+}
+
+/// Suppressions of a file, read from the directive comments.
+#[derive(Debug)]
+pub(crate) struct Suppressions {
+    suppressions: Vec<Suppression>,
+    unused_reaction: LintRuleReaction,
+}
+
+impl Suppressions {
+    pub fn new(
+        program: &Program,
+        comments: Option<&dyn Comments>,
+        source_map: &SourceMap,
+        report_unused: &RuleConfig<()>,
+    ) -> Self {
+        let mut suppressions: Vec<Suppression> = vec![];
+
+        if let Some(comments) = comments {
+            let mut collector = CommentCollector {
+                comments,
+                found: Default::default(),
+            };
+            program.visit_with(&mut collector);
+
+            for comment in collector.found.into_values() {
+                let (kind, rules) = match parse_directive(&comment.text) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                let rules = if rules.is_empty() {
+                    vec![None]
+                } else {
+                    rules
+                        .into_iter()
+                        .map(|rule| Some(rule.to_string()))
+                        .collect()
+                };
+
+                match kind {
+                    DirectiveKind::DisableNextLine => {
+                        let next_line = source_map.lookup_line(comment.span.hi).ok().and_then(
+                            |file_and_line| {
+                                let line = file_and_line.line + 1;
+
+                                (line < file_and_line.sf.count_lines())
+                                    .then(|| file_and_line.sf.line_bounds(line))
+                            },
+                        );
+
+                        // A directive on the last line suppresses nothing, but
+                        // it is still reported as unused.
+                        let (lo, hi) = next_line.unwrap_or((comment.span.hi, comment.span.hi));
+
+                        suppressions.extend(rules.into_iter().map(|rule| Suppression {
+                            directive: comment.span,
+                            rule,
+                            lo,
+                            hi: Some(hi),
+                            used: false,
+                        }));
+                    }
+                    DirectiveKind::Disable => {
+                        suppressions.extend(rules.into_iter().map(|rule| Suppression {
+                            directive: comment.span,
+                            rule,
+                            lo: comment.span.hi,
+                            hi: None,
+                            used: false,
+                        }));
+                    }
+                    DirectiveKind::Enable => {
+                        for suppression in &mut suppressions {
+                            if suppression.hi.is_none()
+                                && rules
+                                    .iter()
+                                    .any(|rule| rule.is_none() || *rule == suppression.rule)
+                            {
+                                suppression.hi = Some(comment.span.lo);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            suppressions,
+            unused_reaction: report_unused.get_rule_reaction(),
+        }
+    }
+
+    /// Returns true if the problem reported by `rule` at `span` is suppressed,
+    /// and marks the matching suppressions as used.
+    fn is_suppressed(&mut self, rule: &str, span: Span) -> bool {
+        let mut suppressed = false;
+
+        for suppression in &mut self.suppressions {
+            if suppression.matches(rule, span.lo) {
+                suppression.used = true;
+                suppressed = true;
+            }
+        }
+
+        suppressed
+    }
+
+    fn report_unused(&self) {
+        let mut unused: Vec<(Span, Vec<Option<&str>>)> = vec![];
+
+        for suppression in self.suppressions.iter().filter(|s| !s.used) {
+            match unused.last_mut() {
+                Some((directive, rules)) if *directive == suppression.directive => {
+                    rules.push(suppression.rule.as_deref());
+                }
+                _ => unused.push((suppression.directive, vec![suppression.rule.as_deref()])),
+            }
+        }
+
+        for (directive, rules) in unused {
+            let message = if rules.iter().any(Option::is_none) {
+                "Unused swc-lint-disable directive (no problems were reported)".to_string()
+            } else {
+                format!(
+                    "Unused swc-lint-disable directive (no problems were reported from {})",
+                    rules
+                        .iter()
+                        .flatten()
+                        .map(|rule| format!("`{}`", rule))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+
+            HANDLER.with(|handler| match self.unused_reaction {
+                LintRuleReaction::Error => {
+                    handler.struct_span_err(directive, &message).emit();
+                }
+                LintRuleReaction::Warning => {
+                    handler.struct_span_warn(directive, &message).emit();
+                }
+                _ => {}
+            });
+        }
+    }
+}
+
+/// Collects the comments attached to the nodes of a program, in source order.
+struct CommentCollector<'a> {
+    comments: &'a dyn Comments,
+    found: BTreeMap<BytePos, Comment>,
+}
+
+impl Visit for CommentCollector<'_> {
+    fn visit_span(&mut self, span: &Span) {
+        if span.is_dummy() {
+            return;
+        }
+
+        for comment in self
+            .comments
+            .get_leading(span.lo)
+            .into_iter()
+            .chain(self.comments.get_trailing(span.hi))
+            .flatten()
+        {
+            self.found.insert(comment.span.lo, comment);
+        }
+    }
+}
+
+/// Runs rules which can be silenced with directive comments. Each rule is
+/// paired with the name it is configured with.
+#[derive(Debug)]
+pub(crate) struct SuppressibleRules {
+    rules: Vec<(&'static str, Box<dyn Rule>)>,
+    suppressions: Suppressions,
+}
+
+impl SuppressibleRules {
+    pub fn new(rules: Vec<(&'static str, Box<dyn Rule>)>, suppressions: Suppressions) -> Self {
+        Self {
+            rules,
+            suppressions,
+        }
+    }
+
+    /// This preserves the order of errors, like `Vec<R>`.
+    fn lint<F>(&mut self, op: F)
+    where
+        F: Fn(&mut Box<dyn Rule>) + Sync,
+    {
+        let run = |(name, rule): &mut (&'static str, Box<dyn Rule>)| {
+            (*name, capture_diagnostics(|| op(rule)))
+        };
+
+        let diagnostics: Vec<(&str, Vec<Diagnostic>)> = if cfg!(target_arch = "wasm32") {
+            self.rules.iter_mut().map(run).collect()
+        } else {
+            self.rules.par_iter_mut().map(run).collect()
+        };
+
+        HANDLER.with(|handler| {
+            for (name, diagnostics) in diagnostics {
+                for diagnostic in diagnostics {
+                    let suppressed = diagnostic
+                        .span
+                        .primary_span()
+                        .map_or(false, |span| self.suppressions.is_suppressed(name, span));
+
+                    if !suppressed {
+                        DiagnosticBuilder::new_diagnostic(handler, diagnostic).emit();
+                    }
+                }
+            }
+        });
+
+        self.suppressions.report_unused();
+    }
+}
+
+impl Rule for SuppressibleRules {
+    fn lint_module(&mut self, program: &Module) {
+        self.lint(|rule| rule.lint_module(program));
+    }
+
+    fn lint_script(&mut self, program: &Script) {
+        self.lint(|rule| rule.lint_script(program));
+    }
+}
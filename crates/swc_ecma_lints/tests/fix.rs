@@ -0,0 +1,124 @@
+#![cfg(feature = "non_critical_lints")]
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use swc_common::{
+    errors::{Applicability, Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
+    input::SourceFileInput,
+    BytePos, FileName, Mark, Span, SyntaxContext,
+};
+use swc_ecma_ast::{EsVersion, Program};
+use swc_ecma_lints::{
+    config::LintConfig,
+    fix::apply_fixes,
+    rule::Rule,
+    rules::{all, LintParams},
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, Syntax};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_visit::VisitMutWith;
+use testing::NormalizedOutput;
+
+/// Fixes may cause other problems, which are fixed in the next pass.
+const MAX_PASSES: usize = 10;
+
+#[derive(Default, Clone)]
+struct Collector(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((**db).clone());
+    }
+}
+
+#[testing::fixture("tests/fix/**/input.js")]
+#[testing::fixture("tests/fix/**/input.ts")]
+fn fix(input: PathBuf) {
+    let is_ts = input.extension().map_or(false, |ext| ext == "ts");
+    let output = input
+        .parent()
+        .unwrap()
+        .join(if is_ts { "output.ts" } else { "output.js" });
+    let config_path = input.parent().unwrap().join("config.json");
+    let config =
+        serde_json::from_str::<LintConfig>(&fs::read_to_string(config_path).unwrap()).unwrap();
+
+    testing::run_test(false, |cm, _handler| {
+        let es_version = EsVersion::latest();
+        let mut fm = cm.load_file(&input).unwrap();
+
+        for _ in 0..MAX_PASSES {
+            let syntax = if is_ts {
+                Syntax::Typescript(Default::default())
+            } else {
+                Syntax::Es(Default::default())
+            };
+            let lexer = Lexer::new(syntax, es_version, SourceFileInput::from(&*fm), None);
+            let mut parser = Parser::new_from(lexer);
+            let mut program = parser.parse_program().expect("fixed code should be valid");
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            program.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+
+            let mut rules = all(LintParams {
+                program: &program,
+                lint_config: &config,
+                unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+                top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                es_version,
+                source_map: cm.clone(),
+                comments: None,
+            });
+
+            let collector = Collector::default();
+            let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+            HANDLER.set(&handler, || match &program {
+                Program::Module(m) => rules.lint_module(m),
+                Program::Script(s) => rules.lint_script(s),
+            });
+
+            let diagnostics = collector.0.lock().unwrap();
+            match apply_fixes(&fm, &diagnostics) {
+                Some(src) => fm = cm.new_source_file(FileName::Anon, src),
+                None => break,
+            }
+        }
+
+        NormalizedOutput::from(fm.src.to_string())
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn no_op_fix() {
+    testing::run_test(false, |cm, _handler| {
+        let fm = cm.new_source_file(FileName::Anon, "var a = 1;".into());
+        let span = Span::new(fm.start_pos, fm.start_pos + BytePos(3), Default::default());
+
+        let collector = Collector::default();
+        let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+        handler
+            .struct_span_warn(span, "Unexpected var")
+            .multipart_suggestion_with_applicability(
+                "Replace `var`",
+                vec![(span, "var".into())],
+                Applicability::MachineApplicable,
+            )
+            .emit();
+
+        // Otherwise `--fix` would lint and write the file again and again.
+        assert_eq!(apply_fixes(&fm, &collector.0.lock().unwrap()), None);
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,6 @@
+a['b'];
+a["c"].d["e"];
+a?.["f"];
+a["g-h"];
+a['class'];
+1["foo"];
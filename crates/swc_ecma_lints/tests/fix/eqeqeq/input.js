@@ -0,0 +1,7 @@
+if (typeof a == "string") {}
+if ("object" != typeof b) {}
+if (1 == 1) {}
+if ("a" != "b") {}
+if (x == y) {}
+if (x == null) {}
+if (1 == "1") {}
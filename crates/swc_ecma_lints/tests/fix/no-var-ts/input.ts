@@ -0,0 +1,11 @@
+declare var x: number;
+var y: string = "y";
+
+declare global {
+    var z: number;
+}
+
+export function foo() {
+    var a: number = x;
+    return a + y;
+}
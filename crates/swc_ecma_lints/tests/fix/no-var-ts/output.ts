@@ -0,0 +1,11 @@
+declare var x: number;
+const y: string = "y";
+
+declare global {
+    var z: number;
+}
+
+export function foo() {
+    const a: number = x;
+    return a + y;
+}
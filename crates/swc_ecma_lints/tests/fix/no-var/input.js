@@ -0,0 +1,23 @@
+export var a = 1;
+var b = 2, c = 3;
+c = 4;
+
+function foo() {
+    var d = 1;
+    var e;
+    e = d;
+    return e;
+}
+
+function bar() {
+    g;
+    var g = 1;
+
+    if (g) {
+        var h = 2;
+    }
+    return h;
+}
+
+var f = 1;
+var f = 2;
@@ -0,0 +1,23 @@
+export const a = 1;
+let b = 2, c = 3;
+c = 4;
+
+function foo() {
+    const d = 1;
+    let e;
+    e = d;
+    return e;
+}
+
+function bar() {
+    g;
+    var g = 1;
+
+    if (g) {
+        var h = 2;
+    }
+    return h;
+}
+
+var f = 1;
+var f = 2;
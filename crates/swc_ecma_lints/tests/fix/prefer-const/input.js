@@ -0,0 +1,13 @@
+let a = 1;
+let b = 1, c = 2;
+let d = 1, e = 2;
+e = 3;
+let f;
+f = 1;
+let [g, h] = [1, 2];
+let { i } = {};
+let j = 1;
+({ j } = {});
+
+for (let k of []) {}
+for (let l = 0; l < 10; l++) {}
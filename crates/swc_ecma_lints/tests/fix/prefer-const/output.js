@@ -0,0 +1,13 @@
+const a = 1;
+const b = 1, c = 2;
+let d = 1, e = 2;
+e = 3;
+let f;
+f = 1;
+const [g, h] = [1, 2];
+const { i } = {};
+let j = 1;
+({ j } = {});
+
+for (const k of []) {}
+for (let l = 0; l < 10; l++) {}
@@ -0,0 +1,5 @@
+const a = 'foo';
+const b = 'say "hi"';
+const c = 'it\'s';
+const d = `bar`;
+const e = `baz ${a}`;
@@ -0,0 +1,3 @@
+parseInt("10", 10);
+Number.parseInt(value, 10,);
+parseInt(value, 16);
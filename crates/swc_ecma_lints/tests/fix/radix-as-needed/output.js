@@ -0,0 +1,3 @@
+parseInt("10");
+Number.parseInt(value,);
+parseInt(value, 16);
@@ -0,0 +1,6 @@
+if ("red" === color) {}
+if (1 < x) {}
+if (5 >= foo.bar) {}
+if (null != value) {}
+if (-1 <= a) {}
+if (1 + 2 < x) {}
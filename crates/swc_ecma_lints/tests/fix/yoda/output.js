@@ -0,0 +1,6 @@
+if (color === "red") {}
+if (x > 1) {}
+if (foo.bar <= 5) {}
+if (value != null) {}
+if (a >= -1) {}
+if (1 + 2 < x) {}
@@ -56,6 +56,7 @@ fn pass(input: PathBuf) {
             top_level_ctxt,
             es_version,
             source_map: cm,
+            comments: None,
         });
 
         HANDLER.set(handler, || match &program {
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use swc_common::input::SourceFileInput;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_lints::metrics::function_metrics;
+use swc_ecma_parser::{lexer::Lexer, Parser, Syntax};
+use testing::NormalizedOutput;
+
+#[testing::fixture("tests/metrics/**/input.js")]
+fn metrics(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.json");
+
+    testing::run_test(false, |cm, _handler| {
+        let fm = cm.load_file(&input).unwrap();
+
+        let lexer = Lexer::new(
+            Syntax::Es(Default::default()),
+            EsVersion::latest(),
+            SourceFileInput::from(&*fm),
+            None,
+        );
+        let program = Parser::new_from(lexer).parse_program().unwrap();
+
+        let metrics = function_metrics(&program, &cm);
+
+        NormalizedOutput::from(serde_json::to_string_pretty(&metrics).unwrap())
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,27 @@
+export function load(path, options = {}) {
+    if (!path) {
+        throw new Error("path is required");
+    }
+
+    for (const entry of options.entries ?? []) {
+        if (entry.skip) {
+            continue;
+        }
+    }
+
+    return function parse(text) {
+        return text && JSON.parse(text);
+    };
+}
+
+export const format = (value) => `${value}`;
+
+class Reader {
+    read(stream) {
+        try {
+            return stream.read();
+        } catch {
+            return null;
+        }
+    }
+}
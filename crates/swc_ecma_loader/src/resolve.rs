@@ -8,6 +8,22 @@ use swc_common::{
 
 pub trait Resolve: Send + Sync {
     fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error>;
+
+    /// Same as [Resolve::resolve], but for `require()` calls.
+    ///
+    /// Resolvers which support the `exports` and `imports` fields of
+    /// package.json should use the `require` condition instead of `import`.
+    fn resolve_require(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        self.resolve(base, module_specifier)
+    }
+
+    /// Returns the address `module_specifier` is mapped to before it's
+    /// resolved, e.g. by an import map.
+    ///
+    /// Returns [None] if the specifier is resolved as-is.
+    fn map_specifier(&self, _base: &FileName, _module_specifier: &str) -> Option<String> {
+        None
+    }
 }
 
 macro_rules! impl_ref {
@@ -19,6 +35,14 @@ macro_rules! impl_ref {
             fn resolve(&self, base: &FileName, src: &str) -> Result<FileName, Error> {
                 (**self).resolve(base, src)
             }
+
+            fn resolve_require(&self, base: &FileName, src: &str) -> Result<FileName, Error> {
+                (**self).resolve_require(base, src)
+            }
+
+            fn map_specifier(&self, base: &FileName, src: &str) -> Option<String> {
+                (**self).map_specifier(base, src)
+            }
         }
     };
 }
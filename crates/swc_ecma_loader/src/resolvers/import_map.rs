@@ -0,0 +1,322 @@
+use std::{
+    collections::BTreeMap,
+    env::current_dir,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Error};
+use serde::Deserialize;
+use swc_common::FileName;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::resolve::Resolve;
+
+/// Specifier keys and their addresses, sorted by keys in descending order so
+/// that longer prefixes are checked first.
+///
+/// [None] means the key is blocked by a `null` or an invalid address.
+type SpecifierMap = Vec<(String, Option<Url>)>;
+
+#[derive(Deserialize)]
+struct RawImportMap {
+    #[serde(default)]
+    imports: BTreeMap<String, serde_json::Value>,
+
+    #[serde(default)]
+    scopes: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+
+/// Support for [import maps](https://github.com/WICG/import-maps).
+///
+/// Specifiers mapped to `file:` urls are resolved using the inner resolver,
+/// and specifiers mapped to other urls are resolved as [FileName::Custom].
+/// Specifiers which are not mapped are passed to the inner resolver as-is.
+#[derive(Debug)]
+pub struct ImportMapResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    imports: SpecifierMap,
+    /// Sorted by scope prefixes in descending order.
+    scopes: Vec<(String, SpecifierMap)>,
+}
+
+impl<R> ImportMapResolver<R>
+where
+    R: Resolve,
+{
+    /// Parses an import map.
+    ///
+    /// `base_url` is the url of the import map, which is used to resolve
+    /// relative addresses and scopes.
+    ///
+    /// Invalid entries are ignored with a warning, like browsers do.
+    pub fn new(inner: R, base_url: &Url, json: &str) -> Result<Self, Error> {
+        let raw: RawImportMap = serde_json::from_str(json).context("failed to parse import map")?;
+
+        let imports = parse_specifier_map(raw.imports, base_url);
+
+        let mut scopes = raw
+            .scopes
+            .into_iter()
+            .filter_map(|(prefix, map)| match base_url.join(&prefix) {
+                Ok(url) => Some((url.to_string(), parse_specifier_map(map, base_url))),
+                Err(err) => {
+                    warn!("import map: ignoring invalid scope `{}`: {}", prefix, err);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        scopes.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Ok(Self {
+            inner,
+            imports,
+            scopes,
+        })
+    }
+
+    /// Reads an import map from a file, whose `file:` url is used as the base
+    /// url.
+    pub fn from_file(inner: R, path: &Path) -> Result<Self, Error> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read import map `{}`", path.display()))?;
+        let base_url = file_url(path)
+            .with_context(|| format!("failed to create url of `{}`", path.display()))?;
+
+        Self::new(inner, &base_url, &json)
+            .with_context(|| format!("failed to load import map `{}`", path.display()))
+    }
+
+    /// Returns `Ok(None)` if `specifier` is not mapped.
+    ///
+    /// See https://html.spec.whatwg.org/multipage/webappapis.html#resolve-a-module-specifier
+    fn resolve_url(&self, base: &FileName, specifier: &str) -> Result<Option<Url>, Error> {
+        let base_url = match base {
+            FileName::Real(path) => file_url(path),
+            FileName::Custom(url) => Url::parse(url).ok(),
+            _ => None,
+        };
+
+        let as_url = parse_url_like(specifier, base_url.as_ref());
+        let normalized = match &as_url {
+            Some(url) => url.as_str(),
+            None => specifier,
+        };
+
+        if let Some(base_url) = &base_url {
+            for (prefix, map) in &self.scopes {
+                if prefix == base_url.as_str()
+                    || (prefix.ends_with('/') && base_url.as_str().starts_with(&**prefix))
+                {
+                    if let Some(url) = resolve_imports_match(normalized, as_url.as_ref(), map)? {
+                        return Ok(Some(url));
+                    }
+                }
+            }
+        }
+
+        resolve_imports_match(normalized, as_url.as_ref(), &self.imports)
+    }
+
+    fn resolve_inner(
+        &self,
+        base: &FileName,
+        module_specifier: &str,
+        require: bool,
+    ) -> Result<FileName, Error> {
+        if require {
+            self.inner.resolve_require(base, module_specifier)
+        } else {
+            self.inner.resolve(base, module_specifier)
+        }
+    }
+
+    fn resolve_with(
+        &self,
+        base: &FileName,
+        module_specifier: &str,
+        require: bool,
+    ) -> Result<FileName, Error> {
+        let url = self.resolve_url(base, module_specifier).with_context(|| {
+            format!("failed to resolve `{}` using import map", module_specifier)
+        })?;
+
+        let url = match url {
+            Some(v) => v,
+            None => return self.resolve_inner(base, module_specifier, require),
+        };
+
+        debug!("import map: mapped `{}` to `{}`", module_specifier, url);
+
+        if url.scheme() != "file" {
+            return Ok(FileName::Custom(url.to_string()));
+        }
+
+        let path = match url.to_file_path() {
+            Ok(v) => v,
+            Err(()) => bail!("`{}` is not a valid file path", url),
+        };
+
+        match self.resolve_inner(base, &path.to_string_lossy(), require) {
+            Ok(v) => Ok(v),
+            // The inner resolver fails if the address does not exist yet, but
+            // the address is the result of an import map.
+            Err(err) => match fs::symlink_metadata(&path) {
+                Err(io_err) if io_err.kind() == io::ErrorKind::NotFound => Ok(FileName::Real(path)),
+                _ => Err(err.context(format!(
+                    "failed to resolve `{}` mapped from `{}`",
+                    url, module_specifier
+                ))),
+            },
+        }
+    }
+}
+
+impl<R> Resolve for ImportMapResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, module_specifier, false)
+    }
+
+    fn resolve_require(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, module_specifier, true)
+    }
+
+    fn map_specifier(&self, base: &FileName, module_specifier: &str) -> Option<String> {
+        match self.resolve_url(base, module_specifier) {
+            Ok(Some(url)) => Some(url.to_string()),
+            Ok(None) => self.inner.map_specifier(base, module_specifier),
+            Err(..) => None,
+        }
+    }
+}
+
+fn parse_specifier_map(map: BTreeMap<String, serde_json::Value>, base_url: &Url) -> SpecifierMap {
+    let mut normalized = map
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = match parse_url_like(&key, Some(base_url)) {
+                Some(url) => url.to_string(),
+                None if key.is_empty() => {
+                    warn!("import map: ignoring empty specifier key");
+                    return None;
+                }
+                None => key,
+            };
+
+            let address = match &value {
+                serde_json::Value::String(address) => {
+                    match parse_url_like(address, Some(base_url)) {
+                        Some(url) => Some(url),
+                        None => {
+                            warn!("import map: address `{}` of `{}` is invalid", address, key);
+                            None
+                        }
+                    }
+                }
+                _ => {
+                    warn!("import map: address of `{}` is not a string", key);
+                    None
+                }
+            };
+
+            let address = address.filter(|address| {
+                if key.ends_with('/') && !address.as_str().ends_with('/') {
+                    warn!(
+                        "import map: address `{}` of `{}` should end with a slash",
+                        address, key
+                    );
+                    return false;
+                }
+
+                true
+            });
+
+            Some((key, address))
+        })
+        .collect::<Vec<_>>();
+
+    normalized.sort_by(|a, b| b.0.cmp(&a.0));
+    normalized.dedup_by(|a, b| a.0 == b.0);
+    normalized
+}
+
+/// See https://html.spec.whatwg.org/multipage/webappapis.html#resolving-an-imports-match
+fn resolve_imports_match(
+    normalized: &str,
+    as_url: Option<&Url>,
+    map: &SpecifierMap,
+) -> Result<Option<Url>, Error> {
+    for (key, address) in map {
+        if key == normalized {
+            return match address {
+                Some(v) => Ok(Some(v.clone())),
+                None => bail!("`{}` is blocked by a null entry of the import map", key),
+            };
+        }
+
+        if key.ends_with('/') && normalized.starts_with(&**key) && as_url.map_or(true, is_special) {
+            let address = match address {
+                Some(v) => v,
+                None => bail!("`{}` is blocked by a null entry of the import map", key),
+            };
+
+            let after_prefix = &normalized[key.len()..];
+            let url = address.join(after_prefix).with_context(|| {
+                format!(
+                    "failed to resolve `{}` against `{}` of the import map",
+                    after_prefix, address
+                )
+            })?;
+
+            if !url.as_str().starts_with(address.as_str()) {
+                bail!(
+                    "`{}` backtracks above its prefix `{}` of the import map",
+                    normalized,
+                    key
+                )
+            }
+
+            return Ok(Some(url));
+        }
+    }
+
+    Ok(None)
+}
+
+/// See https://html.spec.whatwg.org/multipage/webappapis.html#resolving-a-url-like-module-specifier
+fn parse_url_like(specifier: &str, base_url: Option<&Url>) -> Option<Url> {
+    if specifier.starts_with('/') || specifier.starts_with("./") || specifier.starts_with("../") {
+        return base_url?.join(specifier).ok();
+    }
+
+    // Drive letters of windows paths are not schemes.
+    if Path::new(specifier).is_absolute() {
+        return file_url(Path::new(specifier));
+    }
+
+    Url::parse(specifier).ok()
+}
+
+fn file_url(path: &Path) -> Option<Url> {
+    let path = if path.is_absolute() {
+        PathBuf::from(path)
+    } else {
+        current_dir().ok()?.join(path)
+    };
+
+    Url::from_file_path(path).ok()
+}
+
+fn is_special(url: &Url) -> bool {
+    matches!(
+        url.scheme(),
+        "ftp" | "file" | "http" | "https" | "ws" | "wss"
+    )
+}
@@ -12,7 +12,8 @@ pub struct CachingResolver<R>
 where
     R: Resolve,
 {
-    cache: Mutex<LruCache<(FileName, String), FileName>>,
+    /// The last element of keys is `true` for `require()` calls.
+    cache: Mutex<LruCache<(FileName, String, bool), FileName>>,
     inner: R,
 }
 
@@ -37,27 +38,43 @@ where
             inner,
         }
     }
-}
 
-impl<R> Resolve for CachingResolver<R>
-where
-    R: Resolve,
-{
-    fn resolve(&self, base: &FileName, src: &str) -> Result<FileName, Error> {
+    fn resolve_cached(&self, base: &FileName, src: &str, require: bool) -> Result<FileName, Error> {
         {
             let mut lock = self.cache.lock();
             //
-            if let Some(v) = lock.get(&(base.clone(), src.to_string())) {
+            if let Some(v) = lock.get(&(base.clone(), src.to_string(), require)) {
                 return Ok(v.clone());
             }
         }
 
-        let resolved = self.inner.resolve(base, src)?;
+        let resolved = if require {
+            self.inner.resolve_require(base, src)?
+        } else {
+            self.inner.resolve(base, src)?
+        };
         {
             let mut lock = self.cache.lock();
-            lock.put((base.clone(), src.to_string()), resolved.clone());
+            lock.put((base.clone(), src.to_string(), require), resolved.clone());
         }
 
         Ok(resolved)
     }
 }
+
+impl<R> Resolve for CachingResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, src: &str) -> Result<FileName, Error> {
+        self.resolve_cached(base, src, false)
+    }
+
+    fn resolve_require(&self, base: &FileName, src: &str) -> Result<FileName, Error> {
+        self.resolve_cached(base, src, true)
+    }
+
+    fn map_specifier(&self, base: &FileName, src: &str) -> Option<String> {
+        self.inner.map_specifier(base, src)
+    }
+}
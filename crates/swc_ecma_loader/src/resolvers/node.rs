@@ -4,25 +4,32 @@
 
 use std::{
     env::current_dir,
+    fmt,
     fs::File,
     io::BufReader,
     path::{Component, Path, PathBuf},
+    sync::Arc,
 };
 
-use anyhow::{bail, Context, Error};
+use anyhow::{anyhow, bail, Context, Error};
 use dashmap::DashMap;
 #[cfg(windows)]
 use normpath::BasePath;
 use once_cell::sync::Lazy;
 use path_clean::PathClean;
 use pathdiff::diff_paths;
-use serde::Deserialize;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
 use swc_common::{
     collections::{AHashMap, AHashSet, ARandomState},
     FileName,
 };
 use tracing::{debug, trace, Level};
 
+#[cfg(feature = "pnp")]
+use super::pnp::PnpManifest;
 use crate::{resolve::Resolve, TargetEnv, NODE_BUILTINS};
 
 static PACKAGE: &str = "package.json";
@@ -50,7 +57,7 @@ fn find_package_root(path: &Path) -> Option<PathBuf> {
     let mut parent = path.parent();
     while let Some(p) = parent {
         let pkg = p.join(PACKAGE);
-        if pkg.is_file() {
+        if is_file(&pkg) {
             return Some(p.to_path_buf());
         }
         parent = p.parent();
@@ -73,6 +80,11 @@ pub(crate) fn is_core_module(s: &str) -> bool {
     NODE_BUILTINS.contains(&s)
 }
 
+/// Map of deserialized package.json files. The key is the directory
+/// containing the package.json file.
+static PACKAGE_CACHE: Lazy<DashMap<PathBuf, Arc<PackageJson>, ARandomState>> =
+    Lazy::new(Default::default);
+
 #[derive(Deserialize)]
 struct PackageJson {
     #[serde(default)]
@@ -81,6 +93,169 @@ struct PackageJson {
     browser: Option<Browser>,
     #[serde(default)]
     module: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    exports: Option<Exports>,
+    #[serde(default)]
+    imports: Option<Exports>,
+}
+
+/// Reads a package.json, which is cached by the directory containing it.
+fn read_package_json(pkg_path: &Path) -> Result<Arc<PackageJson>, Error> {
+    let pkg_dir = to_absolute_path(pkg_path.parent().unwrap_or(pkg_path))?;
+    if let Some(pkg) = PACKAGE_CACHE.get(&pkg_dir) {
+        return Ok(pkg.clone());
+    }
+
+    let pkg = Arc::new(parse_package_json(pkg_path)?);
+    PACKAGE_CACHE.insert(pkg_dir, pkg.clone());
+
+    Ok(pkg)
+}
+
+fn parse_package_json(pkg_path: &Path) -> Result<PackageJson, Error> {
+    #[cfg(feature = "pnp")]
+    if super::pnp::zip::is_in_archive(pkg_path) {
+        let json = super::pnp::read_to_string(pkg_path)?;
+
+        return serde_json::from_str(&json)
+            .context(format!("failed to deserialize {}", pkg_path.display()));
+    }
+
+    let file = File::open(pkg_path)?;
+    let reader = BufReader::new(file);
+
+    serde_json::from_reader(reader).context(format!("failed to deserialize {}", pkg_path.display()))
+}
+
+/// Same as [Path::is_file], but supports zip archives of Yarn Plug'n'Play.
+fn is_file(path: &Path) -> bool {
+    #[cfg(feature = "pnp")]
+    {
+        super::pnp::zip::is_file(path)
+    }
+    #[cfg(not(feature = "pnp"))]
+    {
+        path.is_file()
+    }
+}
+
+/// Same as [Path::is_dir], but supports zip archives of Yarn Plug'n'Play.
+fn is_dir(path: &Path) -> bool {
+    #[cfg(feature = "pnp")]
+    {
+        super::pnp::zip::is_dir(path)
+    }
+    #[cfg(not(feature = "pnp"))]
+    {
+        path.is_dir()
+    }
+}
+
+/// A value of the `exports` or `imports` field of a package.json.
+///
+/// Keys of objects are kept in order, because the first matching condition
+/// wins.
+#[derive(Debug)]
+enum Exports {
+    Null,
+    Str(String),
+    Array(Vec<Exports>),
+    Map(Vec<(String, Exports)>),
+}
+
+impl<'de> Deserialize<'de> for Exports {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExportsVisitor;
+
+        impl<'de> Visitor<'de> for ExportsVisitor {
+            type Value = Exports;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string, an array, an object or null")
+            }
+
+            fn visit_unit<E>(self) -> Result<Exports, E> {
+                Ok(Exports::Null)
+            }
+
+            // Other values are invalid targets, which are ignored like `null`.
+
+            fn visit_bool<E>(self, _: bool) -> Result<Exports, E> {
+                Ok(Exports::Null)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Exports, E> {
+                Ok(Exports::Null)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Exports, E> {
+                Ok(Exports::Null)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Exports, E> {
+                Ok(Exports::Null)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Exports, E> {
+                Ok(Exports::Str(v.to_string()))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Exports, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut items = vec![];
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Exports::Array(items))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Exports, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Exports::Map(entries))
+            }
+        }
+
+        deserializer.deserialize_any(ExportsVisitor)
+    }
+}
+
+/// Splits a bare specifier like `@scope/pkg/sub/path` into the name of the
+/// package and the subpath used as a key of `exports`, like `./sub/path`.
+fn parse_package_specifier(target: &str) -> (&str, String) {
+    let mut separator = target.find('/');
+    if target.starts_with('@') {
+        separator =
+            separator.and_then(|scope| target[scope + 1..].find('/').map(|i| scope + 1 + i));
+    }
+
+    match separator {
+        Some(i) => (&target[..i], format!(".{}", &target[i..])),
+        None => (target, ".".into()),
+    }
+}
+
+/// Returns true if the pattern key `a` is more specific than `b`, following
+/// `PATTERN_KEY_COMPARE` of node.
+fn is_more_specific_pattern(a: &str, b: &str) -> bool {
+    let (base_a, base_b) = (
+        a.find('*').unwrap_or(a.len()),
+        b.find('*').unwrap_or(b.len()),
+    );
+
+    base_a > base_b || (base_a == base_b && a.len() > b.len())
 }
 
 #[derive(Deserialize)]
@@ -104,6 +279,11 @@ pub struct NodeModulesResolver {
     // if true do not resolve symlink
     preserve_symlinks: bool,
     ignore_node_modules: bool,
+    /// Conditions of `exports` and `imports`. [None] means the defaults of
+    /// `target_env`.
+    conditions: Option<Vec<String>>,
+    #[cfg(feature = "pnp")]
+    pnp: Option<Arc<PnpManifest>>,
 }
 
 static EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "json", "node"];
@@ -120,6 +300,9 @@ impl NodeModulesResolver {
             alias,
             preserve_symlinks,
             ignore_node_modules: false,
+            conditions: None,
+            #[cfg(feature = "pnp")]
+            pnp: None,
         }
     }
 
@@ -134,11 +317,55 @@ impl NodeModulesResolver {
             alias,
             preserve_symlinks,
             ignore_node_modules: true,
+            conditions: None,
+            #[cfg(feature = "pnp")]
+            pnp: None,
+        }
+    }
+
+    /// Set the conditions used to resolve the `exports` and `imports` fields
+    /// of package.json, e.g. `node`, `development` or custom ones.
+    ///
+    /// Defaults to `node` for [TargetEnv::Node] and `browser` for
+    /// [TargetEnv::Browser]. `default` always matches, and `import` or
+    /// `require` matches depending on whether [Resolve::resolve] or
+    /// [Resolve::resolve_require] is used.
+    pub fn with_conditions(mut self, conditions: Vec<String>) -> Self {
+        self.conditions = Some(conditions);
+        self
+    }
+
+    /// Resolve packages using the manifest of Yarn Plug'n'Play instead of
+    /// `node_modules`, for modules in the dependency tree of the manifest.
+    #[cfg(feature = "pnp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pnp")))]
+    pub fn with_pnp(mut self, manifest: Arc<PnpManifest>) -> Self {
+        self.pnp = Some(manifest);
+        self
+    }
+
+    fn matches_condition(&self, condition: &str, require: bool) -> bool {
+        match condition {
+            "default" => true,
+            "import" => !require,
+            "require" => require,
+            _ => match &self.conditions {
+                Some(conditions) => conditions.iter().any(|c| c == condition),
+                None => match self.target_env {
+                    TargetEnv::Node => condition == "node",
+                    TargetEnv::Browser => condition == "browser",
+                },
+            },
         }
     }
 
     fn wrap(&self, path: Option<PathBuf>) -> Result<FileName, Error> {
         if let Some(path) = path {
+            #[cfg(feature = "pnp")]
+            if super::pnp::zip::is_in_archive(&path) {
+                return Ok(FileName::Real(to_absolute_path(&path)?));
+            }
+
             if self.preserve_symlinks {
                 return Ok(FileName::Real(path.clean()));
             } else {
@@ -170,20 +397,20 @@ impl NodeModulesResolver {
 
         let try_exact = path.extension().is_some();
         if try_exact {
-            if path.is_file() {
+            if is_file(path) {
                 return Ok(Some(path.to_path_buf()));
             }
         } else {
             // We try `.js` first.
             let mut path = path.to_path_buf();
             path.set_extension("js");
-            if path.is_file() {
+            if is_file(&path) {
                 return Ok(Some(path));
             }
         }
 
         // Try exact file after checking .js, for performance
-        if !try_exact && path.is_file() {
+        if !try_exact && is_file(path) {
             return Ok(Some(path.to_path_buf()));
         }
 
@@ -192,7 +419,7 @@ impl NodeModulesResolver {
             let name = name.to_string_lossy();
             for ext in EXTENSIONS {
                 ext_path.set_file_name(format!("{}.{}", name, ext));
-                if ext_path.is_file() {
+                if is_file(&ext_path) {
                     return Ok(Some(ext_path));
                 }
             }
@@ -216,7 +443,7 @@ impl NodeModulesResolver {
                 for ext in extensions {
                     ext_path.set_extension(ext);
 
-                    if ext_path.is_file() {
+                    if is_file(&ext_path) {
                         return Ok(Some(ext_path));
                     }
                 }
@@ -251,7 +478,7 @@ impl NodeModulesResolver {
         }
 
         let pkg_path = path.join(PACKAGE);
-        if allow_package_entry && pkg_path.is_file() {
+        if allow_package_entry && is_file(&pkg_path) {
             if let Some(main) = self.resolve_package_entry(path, &pkg_path)? {
                 return Ok(Some(main));
             }
@@ -260,7 +487,7 @@ impl NodeModulesResolver {
         // Try to resolve to an index file.
         for ext in EXTENSIONS {
             let ext_path = path.join(format!("index.{}", ext));
-            if ext_path.is_file() {
+            if is_file(&ext_path) {
                 return Ok(Some(ext_path));
             }
         }
@@ -287,10 +514,7 @@ impl NodeModulesResolver {
             None
         };
 
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson = serde_json::from_reader(reader)
-            .context(format!("failed to deserialize {}", pkg_path.display()))?;
+        let pkg = read_package_json(pkg_path)?;
 
         let main_fields = match self.target_env {
             TargetEnv::Node => {
@@ -379,21 +603,303 @@ impl NodeModulesResolver {
         Ok(None)
     }
 
+    /// Resolve `subpath` of a package using the "exports" key of its
+    /// package.json.
+    fn resolve_package_exports(
+        &self,
+        pkg_dir: &Path,
+        pkg_path: &Path,
+        subpath: &str,
+        exports: &Exports,
+        require: bool,
+    ) -> Result<PathBuf, Error> {
+        // `"exports": "./index.js"` and `"exports": { "import": "./index.mjs" }` are
+        // shorthands for the main entry `"."`.
+        let subpaths = match exports {
+            Exports::Map(map) => {
+                let relative = map.iter().filter(|(key, _)| key.starts_with('.')).count();
+                if relative != 0 && relative != map.len() {
+                    bail!(
+                        "invalid \"exports\" in {}: either all keys or none of them should start \
+                         with \".\"",
+                        pkg_path.display()
+                    )
+                }
+                (relative != 0).then_some(map)
+            }
+            _ => None,
+        };
+
+        let resolved = match subpaths {
+            Some(map) => self.resolve_imports_exports(pkg_dir, subpath, map, false, require)?,
+            None if subpath == "." => {
+                self.resolve_package_target(pkg_dir, exports, None, false, require)?
+            }
+            None => None,
+        };
+
+        resolved.ok_or_else(|| {
+            anyhow!(
+                "Package subpath '{}' is not defined by \"exports\" in {}",
+                subpath,
+                pkg_path.display()
+            )
+        })
+    }
+
+    /// Resolve a `#internal` specifier using the "imports" key of the nearest
+    /// package.json.
+    fn resolve_package_imports(
+        &self,
+        base_dir: &Path,
+        target: &str,
+        require: bool,
+    ) -> Result<PathBuf, Error> {
+        if target == "#" || target.starts_with("#/") {
+            bail!("invalid package import specifier '{}'", target)
+        }
+
+        let base_dir = to_absolute_path(base_dir)?;
+        let pkg_dir = base_dir
+            .ancestors()
+            .find(|dir| is_file(&dir.join(PACKAGE)))
+            .with_context(|| format!("failed to find package.json for '{}'", target))?;
+        let pkg_path = pkg_dir.join(PACKAGE);
+
+        if let Some(Exports::Map(imports)) = &read_package_json(&pkg_path)?.imports {
+            if let Some(path) =
+                self.resolve_imports_exports(pkg_dir, target, imports, true, require)?
+            {
+                return Ok(path);
+            }
+        }
+
+        bail!(
+            "Package import specifier '{}' is not defined in {}",
+            target,
+            pkg_path.display()
+        )
+    }
+
+    /// Find the entry of `key` in a map of "exports" subpaths or "imports",
+    /// including patterns like `./features/*.js`, and resolve its target.
+    fn resolve_imports_exports(
+        &self,
+        pkg_dir: &Path,
+        key: &str,
+        map: &[(String, Exports)],
+        is_imports: bool,
+        require: bool,
+    ) -> Result<Option<PathBuf>, Error> {
+        if let Some((_, target)) = map.iter().find(|(k, _)| k == key && !k.contains('*')) {
+            return self.resolve_package_target(pkg_dir, target, None, is_imports, require);
+        }
+
+        let mut best: Option<(&str, &Exports, &str)> = None;
+        for (pattern, target) in map {
+            let (prefix, suffix) = match pattern.split_once('*') {
+                Some((prefix, suffix)) if !suffix.contains('*') => (prefix, suffix),
+                _ => continue,
+            };
+
+            if key != prefix
+                && key.len() >= pattern.len()
+                && key.starts_with(prefix)
+                && key.ends_with(suffix)
+                && best.map_or(true, |(best, ..)| is_more_specific_pattern(pattern, best))
+            {
+                let pattern_match = &key[prefix.len()..key.len() - suffix.len()];
+                best = Some((pattern, target, pattern_match));
+            }
+        }
+
+        match best {
+            Some((_, target, pattern_match)) => self.resolve_package_target(
+                pkg_dir,
+                target,
+                Some(pattern_match),
+                is_imports,
+                require,
+            ),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve a target of "exports" or "imports". [None] means the target
+    /// does not match the conditions.
+    fn resolve_package_target(
+        &self,
+        pkg_dir: &Path,
+        target: &Exports,
+        pattern_match: Option<&str>,
+        is_imports: bool,
+        require: bool,
+    ) -> Result<Option<PathBuf>, Error> {
+        match target {
+            Exports::Str(target) => {
+                let target = match pattern_match {
+                    Some(pattern_match) => target.replace('*', pattern_match),
+                    None => target.clone(),
+                };
+
+                let relative = match target.strip_prefix("./") {
+                    Some(relative) => relative,
+                    // "imports" may map to other packages.
+                    None if is_imports
+                        && !target.starts_with("../")
+                        && !target.starts_with('/') =>
+                    {
+                        return self
+                            .resolve_node_modules(pkg_dir, &target, require)?
+                            .with_context(|| format!("failed to resolve '{}'", target))
+                            .map(Some);
+                    }
+                    None => bail!(
+                        "invalid target '{}' in {}",
+                        target,
+                        pkg_dir.join(PACKAGE).display()
+                    ),
+                };
+
+                // Targets must stay inside of the package.
+                if Path::new(relative).components().any(|c| match c {
+                    Component::Normal(name) => name == "node_modules",
+                    _ => true,
+                }) {
+                    bail!(
+                        "invalid target '{}' in {}",
+                        target,
+                        pkg_dir.join(PACKAGE).display()
+                    )
+                }
+
+                self.resolve_as_file(&pkg_dir.join(relative))
+            }
+            Exports::Array(targets) => {
+                let mut last_error = None;
+                for target in targets {
+                    match self.resolve_package_target(
+                        pkg_dir,
+                        target,
+                        pattern_match,
+                        is_imports,
+                        require,
+                    ) {
+                        Ok(Some(path)) => return Ok(Some(path)),
+                        Ok(None) => {}
+                        Err(err) => last_error = Some(err),
+                    }
+                }
+
+                match last_error {
+                    Some(err) => Err(err),
+                    None => Ok(None),
+                }
+            }
+            Exports::Map(conditions) => {
+                for (condition, target) in conditions {
+                    if !self.matches_condition(condition, require) {
+                        continue;
+                    }
+
+                    if let Some(path) = self.resolve_package_target(
+                        pkg_dir,
+                        target,
+                        pattern_match,
+                        is_imports,
+                        require,
+                    )? {
+                        return Ok(Some(path));
+                    }
+                }
+
+                Ok(None)
+            }
+            Exports::Null => Ok(None),
+        }
+    }
+
     /// Resolve by walking up node_modules folders.
     fn resolve_node_modules(
         &self,
         base_dir: &Path,
         target: &str,
+        require: bool,
     ) -> Result<Option<PathBuf>, Error> {
+        let absolute_path = to_absolute_path(base_dir)?;
+        let (pkg_name, subpath) = parse_package_specifier(target);
+
+        // A package with "exports" can import itself by its name.
+        let self_pkg_dir = if self.ignore_node_modules {
+            None
+        } else {
+            absolute_path
+                .ancestors()
+                .find(|dir| is_file(&dir.join(PACKAGE)))
+        };
+        if let Some(pkg_dir) = self_pkg_dir {
+            let pkg_path = pkg_dir.join(PACKAGE);
+            if let Ok(PackageJson {
+                name: Some(name),
+                exports: Some(exports),
+                ..
+            }) = read_package_json(&pkg_path).as_deref()
+            {
+                if name == pkg_name {
+                    return self
+                        .resolve_package_exports(pkg_dir, &pkg_path, &subpath, exports, require)
+                        .map(Some);
+                }
+            }
+        }
+
+        #[cfg(feature = "pnp")]
+        if let Some(pnp) = &self.pnp {
+            if let Some(pkg_dir) = pnp.resolve_package(pkg_name, &absolute_path)? {
+                let pkg_path = pkg_dir.join(PACKAGE);
+                if let Ok(PackageJson {
+                    exports: Some(exports),
+                    ..
+                }) = read_package_json(&pkg_path).as_deref()
+                {
+                    return self
+                        .resolve_package_exports(pkg_dir, &pkg_path, &subpath, exports, require)
+                        .map(Some);
+                }
+
+                let path = pkg_dir.join(target[pkg_name.len()..].trim_start_matches('/'));
+                return self
+                    .resolve_as_file(&path)
+                    .or_else(|_| self.resolve_as_directory(&path, true));
+            }
+        }
+
+        // Plug'n'Play projects do not have `node_modules`, so the manifest is used
+        // even if `node_modules` are ignored.
         if self.ignore_node_modules {
             return Ok(None);
         }
 
-        let absolute_path = to_absolute_path(base_dir)?;
         let mut path = Some(&*absolute_path);
         while let Some(dir) = path {
             let node_modules = dir.join("node_modules");
-            if node_modules.is_dir() {
+            if is_dir(&node_modules) {
+                let pkg_dir = node_modules.join(pkg_name);
+                let pkg_path = pkg_dir.join(PACKAGE);
+                if is_file(&pkg_path) {
+                    if let Ok(PackageJson {
+                        exports: Some(exports),
+                        ..
+                    }) = read_package_json(&pkg_path).as_deref()
+                    {
+                        return self
+                            .resolve_package_exports(
+                                &pkg_dir, &pkg_path, &subpath, exports, require,
+                            )
+                            .map(Some);
+                    }
+                }
+
                 let path = node_modules.join(target);
                 if let Some(result) = self
                     .resolve_as_file(&path)
@@ -413,6 +919,21 @@ impl NodeModulesResolver {
 
 impl Resolve for NodeModulesResolver {
     fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, target, false)
+    }
+
+    fn resolve_require(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, target, true)
+    }
+}
+
+impl NodeModulesResolver {
+    fn resolve_with(
+        &self,
+        base: &FileName,
+        target: &str,
+        require: bool,
+    ) -> Result<FileName, Error> {
         debug!(
             "Resolving {} from {:#?} for {:#?}",
             target, base, self.target_env
@@ -423,7 +944,7 @@ impl Resolve for NodeModulesResolver {
             _ => bail!("node-resolver supports only files"),
         };
 
-        let base_dir = if base.is_file() {
+        let base_dir = if is_file(base) {
             let cwd = &Path::new(".");
             base.parent().unwrap_or(cwd)
         } else {
@@ -468,7 +989,10 @@ impl Resolve for NodeModulesResolver {
         let target_path = Path::new(target);
 
         let file_name = {
-            if target_path.is_absolute() {
+            if target.starts_with('#') {
+                self.resolve_package_imports(base_dir, target, require)
+                    .and_then(|p| self.wrap(Some(p)))
+            } else if target_path.is_absolute() {
                 let path = PathBuf::from(target_path);
                 self.resolve_as_file(&path)
                     .or_else(|_| self.resolve_as_directory(&path, true))
@@ -492,7 +1016,7 @@ impl Resolve for NodeModulesResolver {
                         .or_else(|_| self.resolve_as_directory(&path, true))
                         .and_then(|p| self.wrap(p))
                 } else {
-                    self.resolve_node_modules(base_dir, target)
+                    self.resolve_node_modules(base_dir, target, require)
                         .and_then(|path| {
                             let file_path = path.context("failed to get the node_modules path");
                             let current_directory = current_dir()?;
@@ -0,0 +1,356 @@
+//! Support for [Yarn Plug'n'Play](https://yarnpkg.com/advanced/pnp-spec).
+//!
+//! Use [PnpManifest] with [NodeModulesResolver::with_pnp] to resolve packages
+//! without `node_modules`.
+//!
+//! [NodeModulesResolver::with_pnp]: super::node::NodeModulesResolver::with_pnp
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Error};
+use path_clean::PathClean;
+use regex::Regex;
+use serde::Deserialize;
+use swc_common::collections::{AHashMap, AHashSet};
+
+pub use self::zip::read_to_string;
+use super::node::to_absolute_path;
+
+pub(crate) mod zip;
+
+/// A package in the dependency tree. The top-level package has neither name
+/// nor reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Locator {
+    name: Option<String>,
+    reference: Option<String>,
+}
+
+#[derive(Debug)]
+struct PackageInfo {
+    /// Absolute path of the package, which may be in a zip archive.
+    location: PathBuf,
+    /// [None] for peer dependencies which are not provided.
+    dependencies: AHashMap<String, Option<Locator>>,
+}
+
+/// The data of `.pnp.cjs` or `.pnp.data.json`.
+#[derive(Debug)]
+pub struct PnpManifest {
+    /// The directory containing the manifest.
+    dir: PathBuf,
+    packages: AHashMap<Locator, PackageInfo>,
+    /// Locations of packages, sorted by length in descending order.
+    locations: Vec<(PathBuf, Locator)>,
+    enable_top_level_fallback: bool,
+    fallback_pool: AHashMap<String, Option<Locator>>,
+    fallback_exclusions: AHashSet<Locator>,
+    ignore_pattern: Option<Regex>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawManifest {
+    #[serde(default)]
+    enable_top_level_fallback: bool,
+    #[serde(default)]
+    ignore_pattern_data: Option<String>,
+    #[serde(default)]
+    fallback_exclusion_list: Vec<(String, Vec<String>)>,
+    #[serde(default)]
+    fallback_pool: Vec<(String, Option<RawDependency>)>,
+    package_registry_data: Vec<(Option<String>, Vec<(Option<String>, RawPackageInfo)>)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPackageInfo {
+    package_location: String,
+    #[serde(default)]
+    package_dependencies: Vec<(String, Option<RawDependency>)>,
+    #[serde(default)]
+    discard_from_lookup: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDependency {
+    Reference(String),
+    /// Dependencies installed using an alias, like `"lodash": "npm:lodash-es"`.
+    Alias(String, String),
+}
+
+impl RawDependency {
+    fn into_locator(self, name: &str) -> Locator {
+        match self {
+            RawDependency::Reference(reference) => Locator {
+                name: Some(name.to_string()),
+                reference: Some(reference),
+            },
+            RawDependency::Alias(name, reference) => Locator {
+                name: Some(name),
+                reference: Some(reference),
+            },
+        }
+    }
+}
+
+fn into_dependencies(
+    deps: Vec<(String, Option<RawDependency>)>,
+) -> AHashMap<String, Option<Locator>> {
+    deps.into_iter()
+        .map(|(name, dep)| {
+            let locator = dep.map(|dep| dep.into_locator(&name));
+            (name, locator)
+        })
+        .collect()
+}
+
+impl PnpManifest {
+    /// Finds the closest `.pnp.data.json` or `.pnp.cjs` from `dir`.
+    ///
+    /// Returns `Ok(None)` if `dir` is not in a Plug'n'Play project.
+    pub fn find(dir: &Path) -> Result<Option<Self>, Error> {
+        let dir = to_absolute_path(dir)?;
+
+        for dir in dir.ancestors() {
+            for name in [".pnp.data.json", ".pnp.cjs"] {
+                let path = dir.join(name);
+                if path.is_file() {
+                    return Self::load(&path).map(Some);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loads `.pnp.data.json`, or the data inlined in `.pnp.cjs`.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let path = to_absolute_path(path)?;
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let json = if path.extension().map_or(false, |ext| ext == "json") {
+            content
+        } else {
+            extract_runtime_state(&content).with_context(|| {
+                format!(
+                    "failed to find the inlined data of {}. Is `pnpEnableInlining` disabled?",
+                    path.display()
+                )
+            })?
+        };
+
+        let dir = path.parent().unwrap_or(&path).to_path_buf();
+        let raw = serde_json::from_str(&json)
+            .with_context(|| format!("failed to parse data of {}", path.display()))?;
+
+        Ok(Self::new(dir, raw))
+    }
+
+    fn new(dir: PathBuf, raw: RawManifest) -> Self {
+        let mut packages = AHashMap::default();
+        // Later packages win, like `.pnp.cjs`.
+        let mut locations = AHashMap::default();
+
+        for (name, references) in raw.package_registry_data {
+            for (reference, info) in references {
+                let locator = Locator {
+                    name: name.clone(),
+                    reference,
+                };
+                let location = dir.join(&info.package_location).clean();
+
+                if !info.discard_from_lookup {
+                    locations.insert(location.clone(), locator.clone());
+                }
+
+                packages.insert(
+                    locator,
+                    PackageInfo {
+                        location,
+                        dependencies: into_dependencies(info.package_dependencies),
+                    },
+                );
+            }
+        }
+
+        let mut locations = locations.into_iter().collect::<Vec<_>>();
+        locations.sort_by(|a, b| {
+            b.0.components()
+                .count()
+                .cmp(&a.0.components().count())
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        let fallback_exclusions = raw
+            .fallback_exclusion_list
+            .into_iter()
+            .flat_map(|(name, references)| {
+                references.into_iter().map(move |reference| Locator {
+                    name: Some(name.clone()),
+                    reference: Some(reference),
+                })
+            })
+            .collect();
+
+        let ignore_pattern =
+            raw.ignore_pattern_data
+                .and_then(|pattern| match Regex::new(&pattern) {
+                    Ok(v) => Some(v),
+                    Err(err) => {
+                        tracing::warn!("ignoring invalid `ignorePatternData` of pnp: {}", err);
+                        None
+                    }
+                });
+
+        Self {
+            dir,
+            packages,
+            locations,
+            enable_top_level_fallback: raw.enable_top_level_fallback,
+            fallback_pool: into_dependencies(raw.fallback_pool),
+            fallback_exclusions,
+            ignore_pattern,
+        }
+    }
+
+    /// Finds the package which contains `path`.
+    fn find_locator(&self, path: &Path) -> Option<&Locator> {
+        let relative = path.strip_prefix(&self.dir).ok()?;
+
+        if let Some(pattern) = &self.ignore_pattern {
+            if pattern.is_match(&relative.to_string_lossy().replace('\\', "/")) {
+                return None;
+            }
+        }
+
+        self.locations
+            .iter()
+            .find(|(location, _)| path.starts_with(location))
+            .map(|(_, locator)| locator)
+    }
+
+    /// Returns the directory of the package `name` as seen from `issuer`.
+    ///
+    /// Returns `Ok(None)` if `issuer` is not a part of the dependency tree, so
+    /// the package should be resolved using `node_modules`.
+    pub fn resolve_package(&self, name: &str, issuer: &Path) -> Result<Option<&Path>, Error> {
+        let issuer = to_absolute_path(issuer)?;
+        let issuer_locator = match self.find_locator(&issuer) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let issuer_info = match self.packages.get(issuer_locator) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let dependency = match issuer_info.dependencies.get(name) {
+            Some(v) => v.as_ref(),
+            None => self.fallback(issuer_locator, name).with_context(|| {
+                format!(
+                    "{} tried to access {}, but it isn't declared in its dependencies",
+                    display_locator(issuer_locator),
+                    name
+                )
+            })?,
+        };
+
+        let dependency = dependency.ok_or_else(|| {
+            anyhow!(
+                "{} tried to access {} (a peer dependency), but it isn't provided by its ancestors",
+                display_locator(issuer_locator),
+                name
+            )
+        })?;
+
+        match self.packages.get(dependency) {
+            Some(info) => Ok(Some(&info.location)),
+            None => bail!(
+                "{} is not in the package registry of {}",
+                display_locator(dependency),
+                self.dir.display()
+            ),
+        }
+    }
+
+    /// Looks up dependencies of the top-level package and the fallback pool.
+    fn fallback(&self, issuer: &Locator, name: &str) -> Option<Option<&Locator>> {
+        if !self.enable_top_level_fallback || self.fallback_exclusions.contains(issuer) {
+            return None;
+        }
+
+        let top_level = Locator {
+            name: None,
+            reference: None,
+        };
+
+        self.packages
+            .get(&top_level)
+            .and_then(|info| info.dependencies.get(name))
+            .or_else(|| self.fallback_pool.get(name))
+            .map(|v| v.as_ref())
+    }
+}
+
+fn display_locator(locator: &Locator) -> String {
+    match (&locator.name, &locator.reference) {
+        (Some(name), Some(reference)) => format!("{}@{}", name, reference),
+        _ => "the top-level package".into(),
+    }
+}
+
+/// Extracts the JSON of `RAW_RUNTIME_STATE` from `.pnp.cjs`, which is a string
+/// literal.
+fn extract_runtime_state(content: &str) -> Option<String> {
+    let start = content.find("RAW_RUNTIME_STATE")?;
+    let rest = content[start..].split_once('=')?.1.trim_start();
+
+    let mut chars = rest.chars();
+    let quote = chars.next().filter(|&c| c == '\'' || c == '"')?;
+
+    let mut json = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                // Line continuation
+                '\n' => {}
+                '\r' => {
+                    if chars.clone().next() == Some('\n') {
+                        chars.next();
+                    }
+                }
+                'n' => json.push('\n'),
+                'r' => json.push('\r'),
+                't' => json.push('\t'),
+                c => json.push(c),
+            },
+            c if c == quote => return Some(json),
+            c => json.push(c),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_state_of_pnp_cjs() {
+        let content = "#!/usr/bin/env node\n/* eslint-disable */\n\"use strict\";\n\nconst \
+                       RAW_RUNTIME_STATE =\n'{\\\n  \"__info\": [\"it\\'s generated\"],\\\n  \
+                       \"path\": \"a\\\\\\\\b\"\\\n}';\n\nfunction $$SETUP_STATE() {}\n";
+
+        assert_eq!(
+            extract_runtime_state(content).unwrap(),
+            "{  \"__info\": [\"it's generated\"],  \"path\": \"a\\\\b\"}"
+        );
+    }
+}
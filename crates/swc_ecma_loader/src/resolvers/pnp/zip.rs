@@ -0,0 +1,154 @@
+//! Read-only access to files in zip archives, which are used by the cache of
+//! Yarn Plug'n'Play.
+//!
+//! Files in archives are addressed like
+//! `/.yarn/cache/pkg.zip/node_modules/pkg/index.js`.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Read},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use swc_common::collections::{AHashSet, ARandomState};
+use zip::ZipArchive;
+
+/// Opened archives. [None] means the archive cannot be read.
+static ARCHIVES: Lazy<DashMap<PathBuf, Option<Arc<Archive>>, ARandomState>> =
+    Lazy::new(Default::default);
+
+struct Archive {
+    entries: Entries,
+    zip: Mutex<ZipArchive<BufReader<File>>>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    files: AHashSet<String>,
+    dirs: AHashSet<String>,
+}
+
+/// Splits `path` into the path of an archive and the path of an entry in it.
+fn split(path: &Path) -> Option<(PathBuf, String)> {
+    let mut components = path.components();
+    let mut archive = PathBuf::new();
+
+    while let Some(c) = components.next() {
+        archive.push(c);
+
+        if let Component::Normal(name) = c {
+            if name.to_string_lossy().ends_with(".zip") && archive.is_file() {
+                let entry = components
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                return Some((archive, entry));
+            }
+        }
+    }
+
+    None
+}
+
+fn open(archive: &Path) -> Option<Arc<Archive>> {
+    if let Some(v) = ARCHIVES.get(archive) {
+        return v.clone();
+    }
+
+    let opened = open_archive(archive)
+        .map_err(|err| tracing::warn!("failed to read zip archive {}: {}", archive.display(), err))
+        .ok()
+        .map(Arc::new);
+
+    ARCHIVES.insert(archive.to_path_buf(), opened.clone());
+    opened
+}
+
+fn open_archive(archive: &Path) -> Result<Archive, zip::result::ZipError> {
+    let zip = ZipArchive::new(BufReader::new(File::open(archive)?))?;
+    let mut entries = Entries::default();
+
+    for name in zip.file_names() {
+        let name = name.trim_end_matches('/');
+
+        // Some archives do not have entries for directories.
+        let mut dir = name;
+        while let Some((parent, _)) = dir.rsplit_once('/') {
+            if !entries.dirs.insert(parent.to_string()) {
+                break;
+            }
+            dir = parent;
+        }
+
+        if !name.is_empty() {
+            entries.files.insert(name.to_string());
+        }
+    }
+
+    // Directories may be listed as files if they have their own entries.
+    entries.files.retain(|name| !entries.dirs.contains(name));
+
+    Ok(Archive {
+        entries,
+        zip: Mutex::new(zip),
+    })
+}
+
+/// Same as [Path::is_file], but supports files in zip archives.
+pub(crate) fn is_file(path: &Path) -> bool {
+    match split(path) {
+        Some((archive, entry)) => {
+            open(&archive).map_or(false, |a| a.entries.files.contains(&entry))
+        }
+        None => path.is_file(),
+    }
+}
+
+/// Same as [Path::is_dir], but supports directories in zip archives.
+pub(crate) fn is_dir(path: &Path) -> bool {
+    match split(path) {
+        Some((archive, entry)) => {
+            entry.is_empty() || open(&archive).map_or(false, |a| a.entries.dirs.contains(&entry))
+        }
+        None => path.is_dir(),
+    }
+}
+
+/// Returns true if `path` points to an entry of a zip archive.
+pub(crate) fn is_in_archive(path: &Path) -> bool {
+    split(path).is_some()
+}
+
+/// Same as [fs::read_to_string], but supports files in zip archives.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    let (archive, entry) = match split(path) {
+        Some(v) => v,
+        None => return fs::read_to_string(path),
+    };
+
+    let opened = open(&archive).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("failed to read zip archive {}", archive.display()),
+        )
+    })?;
+    // A poisoned lock does not leave the archive in an invalid state, because
+    // each read seeks to its entry.
+    let mut zip = opened.zip.lock().unwrap_or_else(|err| err.into_inner());
+    let mut file = zip.by_name(&entry).map_err(|err| match err {
+        zip::result::ZipError::Io(err) => err,
+        zip::result::ZipError::FileNotFound => io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not in {}", entry, archive.display()),
+        ),
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    })?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(content)
+}
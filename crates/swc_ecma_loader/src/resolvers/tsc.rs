@@ -104,8 +104,14 @@ where
         &self,
         base: &FileName,
         module_specifier: &str,
+        require: bool,
     ) -> Result<FileName, Error> {
-        let res = self.inner.resolve(base, module_specifier).with_context(|| {
+        let res = if require {
+            self.inner.resolve_require(base, module_specifier)
+        } else {
+            self.inner.resolve(base, module_specifier)
+        };
+        let res = res.with_context(|| {
             format!(
                 "failed to resolve `{module_specifier}` from `{base}` using inner \
                  resolver\nbase_url={}",
@@ -151,13 +157,13 @@ where
             }
         }
     }
-}
 
-impl<R> Resolve for TsConfigResolver<R>
-where
-    R: Resolve,
-{
-    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+    fn resolve_with(
+        &self,
+        base: &FileName,
+        module_specifier: &str,
+        require: bool,
+    ) -> Result<FileName, Error> {
         let _tracing = if cfg!(debug_assertions) {
             Some(
                 tracing::span!(
@@ -179,7 +185,7 @@ where
                 || module_specifier.starts_with("../"))
         {
             return self
-                .invoke_inner_resolver(base, module_specifier)
+                .invoke_inner_resolver(base, module_specifier, require)
                 .context("not processed by tsc resolver because it's relative import");
         }
 
@@ -188,9 +194,11 @@ where
                 Component::Normal(v) => v == "node_modules",
                 _ => false,
             }) {
-                return self.invoke_inner_resolver(base, module_specifier).context(
-                    "not processed by tsc resolver because base module is in node_modules",
-                );
+                return self
+                    .invoke_inner_resolver(base, module_specifier, require)
+                    .context(
+                        "not processed by tsc resolver because base module is in node_modules",
+                    );
             }
         }
 
@@ -237,12 +245,20 @@ where
                         let relative = format!("./{}", replaced);
 
                         let res = self
-                            .invoke_inner_resolver(base, module_specifier)
+                            .invoke_inner_resolver(base, module_specifier, require)
                             .or_else(|_| {
-                                self.invoke_inner_resolver(&self.base_url_filename, &relative)
+                                self.invoke_inner_resolver(
+                                    &self.base_url_filename,
+                                    &relative,
+                                    require,
+                                )
                             })
                             .or_else(|_| {
-                                self.invoke_inner_resolver(&self.base_url_filename, &replaced)
+                                self.invoke_inner_resolver(
+                                    &self.base_url_filename,
+                                    &replaced,
+                                    require,
+                                )
                             });
 
                         errors.push(match res {
@@ -292,10 +308,29 @@ where
             }
         }
 
-        if let Ok(v) = self.invoke_inner_resolver(&self.base_url_filename, module_specifier) {
+        if let Ok(v) =
+            self.invoke_inner_resolver(&self.base_url_filename, module_specifier, require)
+        {
             return Ok(v);
         }
 
-        self.invoke_inner_resolver(base, module_specifier)
+        self.invoke_inner_resolver(base, module_specifier, require)
+    }
+}
+
+impl<R> Resolve for TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, module_specifier, false)
+    }
+
+    fn resolve_require(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        self.resolve_with(base, module_specifier, true)
+    }
+
+    fn map_specifier(&self, base: &FileName, module_specifier: &str) -> Option<String> {
+        self.inner.map_specifier(base, module_specifier)
     }
 }
@@ -0,0 +1,219 @@
+#![cfg(feature = "import_map")]
+
+use anyhow::{anyhow, Error};
+use swc_common::FileName;
+use swc_ecma_loader::{resolve::Resolve, resolvers::import_map::ImportMapResolver};
+use url::Url;
+
+/// Resolves only `fallback` and absolute paths.
+struct TestResolver;
+
+impl Resolve for TestResolver {
+    fn resolve(&self, _: &FileName, src: &str) -> Result<FileName, Error> {
+        if src == "fallback" || src.starts_with('/') {
+            Ok(FileName::Custom(format!("inner:{}", src)))
+        } else {
+            Err(anyhow!("failed to resolve `{}`", src))
+        }
+    }
+}
+
+fn resolver(json: &str) -> ImportMapResolver<TestResolver> {
+    ImportMapResolver::new(
+        TestResolver,
+        &Url::parse("https://example.com/app/import-map.json").unwrap(),
+        json,
+    )
+    .unwrap()
+}
+
+fn resolve(r: &ImportMapResolver<TestResolver>, base: &str, src: &str) -> Result<String, Error> {
+    r.resolve(&FileName::Custom(base.into()), src)
+        .map(|v| match v {
+            FileName::Custom(v) => v,
+            _ => v.to_string(),
+        })
+}
+
+const MAIN: &str = "https://example.com/app/main.js";
+
+#[test]
+fn bare_specifiers() {
+    let r = resolver(
+        r#"{
+            "imports": {
+                "react": "https://cdn.example.com/react@18/index.js",
+                "lodash": "./vendor/lodash.js",
+                "lodash/": "./vendor/lodash/",
+                "blocked": null
+            }
+        }"#,
+    );
+
+    assert_eq!(
+        resolve(&r, MAIN, "react").unwrap(),
+        "https://cdn.example.com/react@18/index.js"
+    );
+    assert_eq!(
+        resolve(&r, MAIN, "lodash").unwrap(),
+        "https://example.com/app/vendor/lodash.js"
+    );
+    assert_eq!(
+        resolve(&r, MAIN, "lodash/fp/map.js").unwrap(),
+        "https://example.com/app/vendor/lodash/fp/map.js"
+    );
+    assert_eq!(resolve(&r, MAIN, "fallback").unwrap(), "inner:fallback");
+
+    resolve(&r, MAIN, "blocked").expect_err("should be blocked");
+    resolve(&r, MAIN, "unknown").expect_err("should not be resolved");
+}
+
+#[test]
+fn url_prefixes() {
+    let r = resolver(
+        r#"{
+            "imports": {
+                "https://cdn.example.com/old/": "https://cdn.example.com/new/",
+                "/app/legacy/": "/app/modern/"
+            }
+        }"#,
+    );
+
+    assert_eq!(
+        resolve(&r, MAIN, "https://cdn.example.com/old/a.js").unwrap(),
+        "https://cdn.example.com/new/a.js"
+    );
+    assert_eq!(
+        resolve(&r, MAIN, "./legacy/b.js").unwrap(),
+        "https://example.com/app/modern/b.js"
+    );
+}
+
+#[test]
+fn backtracking() {
+    let r = resolver(r#"{ "imports": { "pkg/": "./vendor/pkg/" } }"#);
+
+    resolve(&r, MAIN, "pkg/../../secret.js").expect_err("should not backtrack above the prefix");
+}
+
+#[test]
+fn scopes() {
+    let r = resolver(
+        r#"{
+            "imports": {
+                "dep": "./dep-v1.js"
+            },
+            "scopes": {
+                "./legacy/": {
+                    "dep": "./dep-v0.js"
+                },
+                "./legacy/nested/": {
+                    "dep": "./dep-v-1.js"
+                }
+            }
+        }"#,
+    );
+
+    assert_eq!(
+        resolve(&r, MAIN, "dep").unwrap(),
+        "https://example.com/app/dep-v1.js"
+    );
+    assert_eq!(
+        resolve(&r, "https://example.com/app/legacy/a.js", "dep").unwrap(),
+        "https://example.com/app/dep-v0.js"
+    );
+    assert_eq!(
+        resolve(&r, "https://example.com/app/legacy/nested/b.js", "dep").unwrap(),
+        "https://example.com/app/dep-v-1.js"
+    );
+}
+
+#[test]
+fn invalid_entries_are_ignored() {
+    let r = resolver(
+        r#"{
+            "imports": {
+                "number": 1,
+                "dir/": "./no-trailing-slash",
+                "ok": "./ok.js"
+            }
+        }"#,
+    );
+
+    resolve(&r, MAIN, "number").expect_err("should be blocked");
+    resolve(&r, MAIN, "dir/a.js").expect_err("should be blocked");
+    assert_eq!(
+        resolve(&r, MAIN, "ok").unwrap(),
+        "https://example.com/app/ok.js"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn file_urls() {
+    let r = ImportMapResolver::new(
+        TestResolver,
+        &Url::parse("file:///project/import-map.json").unwrap(),
+        r#"{ "imports": { "utils": "./src/utils.js" } }"#,
+    )
+    .unwrap();
+
+    let base = FileName::Real("/project/src/index.js".into());
+
+    assert_eq!(
+        r.resolve(&base, "utils").unwrap(),
+        FileName::Custom("inner:/project/src/utils.js".into())
+    );
+}
+
+#[test]
+fn map_specifier() {
+    let r = resolver(
+        r#"{
+            "imports": {
+                "@app/utils": "./src/utils.js"
+            }
+        }"#,
+    );
+    let base = FileName::Custom(MAIN.into());
+
+    assert_eq!(
+        r.map_specifier(&base, "@app/utils").as_deref(),
+        Some("https://example.com/app/src/utils.js")
+    );
+    assert_eq!(r.map_specifier(&base, "fallback"), None);
+}
+
+/// Fails for every specifier.
+struct FailingResolver;
+
+impl Resolve for FailingResolver {
+    fn resolve(&self, _: &FileName, src: &str) -> Result<FileName, Error> {
+        Err(anyhow!("failed to read `{}`", src))
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn only_missing_addresses_fall_back() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let r = ImportMapResolver::new(
+        FailingResolver,
+        &Url::from_file_path(dir.join("import-map.json")).unwrap(),
+        r#"{
+            "imports": {
+                "existing": "./Cargo.toml",
+                "missing": "./missing.js"
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let base = FileName::Real(dir.join("src/lib.rs"));
+
+    assert_eq!(
+        r.resolve(&base, "missing").unwrap(),
+        FileName::Real(dir.join("missing.js"))
+    );
+    assert!(r.resolve(&base, "existing").is_err());
+}
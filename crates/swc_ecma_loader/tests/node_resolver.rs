@@ -105,3 +105,195 @@ fn browser_overwrite() {
         );
     });
 }
+
+#[test]
+fn exports_conditions() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+
+        // When
+        let resolved = node_resolver
+            .resolve(&FileName::Real(PathBuf::from("src/index.js")), "pkg")
+            .expect("should resolve");
+        let required = node_resolver
+            .resolve_require(&FileName::Real(PathBuf::from("src/index.js")), "pkg")
+            .expect("should resolve");
+
+        // Expect
+        assert_eq!(
+            resolved,
+            FileName::Real(PathBuf::from("node_modules/pkg/esm/index.js"))
+        );
+        assert_eq!(
+            required,
+            FileName::Real(PathBuf::from("node_modules/pkg/cjs/index.js"))
+        );
+    });
+}
+
+#[test]
+fn exports_custom_conditions() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+        let browser_resolver =
+            NodeModulesResolver::new(TargetEnv::Browser, Default::default(), true);
+        let development_resolver =
+            NodeModulesResolver::new(TargetEnv::Node, Default::default(), true)
+                .with_conditions(vec!["development".into()]);
+
+        // When
+        let base = FileName::Real(PathBuf::from("src/index.js"));
+        let resolved = node_resolver
+            .resolve(&base, "pkg/feature")
+            .expect("should resolve");
+        let browser = browser_resolver
+            .resolve(&base, "pkg/feature")
+            .expect("should resolve");
+        let development = development_resolver
+            .resolve(&base, "pkg/feature")
+            .expect("should resolve");
+        let development_main = development_resolver
+            .resolve(&base, "pkg")
+            .expect("should resolve");
+
+        // Expect
+        assert_eq!(
+            resolved,
+            FileName::Real(PathBuf::from("node_modules/pkg/feature.js"))
+        );
+        assert_eq!(
+            browser,
+            FileName::Real(PathBuf::from("node_modules/pkg/feature-browser.js"))
+        );
+        assert_eq!(
+            development,
+            FileName::Real(PathBuf::from("node_modules/pkg/feature-development.js"))
+        );
+        assert_eq!(
+            development_main,
+            FileName::Real(PathBuf::from("node_modules/pkg/esm/index.js"))
+        );
+    });
+}
+
+#[test]
+fn exports_sugar() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+        let browser_resolver =
+            NodeModulesResolver::new(TargetEnv::Browser, Default::default(), true);
+
+        // When
+        let base = FileName::Real(PathBuf::from("src/index.js"));
+        let resolved = node_resolver
+            .resolve(&base, "@scope/pkg")
+            .expect("should resolve");
+        let browser = browser_resolver
+            .resolve(&base, "@scope/pkg")
+            .expect("should resolve");
+
+        // Expect
+        assert_eq!(
+            resolved,
+            FileName::Real(PathBuf::from("node_modules/@scope/pkg/node.js"))
+        );
+        assert_eq!(
+            browser,
+            FileName::Real(PathBuf::from("node_modules/@scope/pkg/default.js"))
+        );
+    });
+}
+
+#[test]
+fn exports_patterns() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+
+        // When
+        let base = FileName::Real(PathBuf::from("src/index.js"));
+        let resolved = node_resolver
+            .resolve(&base, "pkg/utils/a")
+            .expect("should resolve");
+        let private = node_resolver.resolve(&base, "pkg/utils/private/b");
+
+        // Expect
+        assert_eq!(
+            resolved,
+            FileName::Real(PathBuf::from("node_modules/pkg/lib/utils/a.js"))
+        );
+        assert!(private.is_err());
+    });
+}
+
+#[test]
+fn exports_not_exported() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+
+        // When
+        let err = node_resolver
+            .resolve(
+                &FileName::Real(PathBuf::from("src/index.js")),
+                "pkg/index.js",
+            )
+            .expect_err("should not resolve");
+
+        // Expect
+        assert!(err
+            .to_string()
+            .starts_with("Package subpath './index.js' is not defined by \"exports\""));
+    });
+}
+
+#[test]
+fn exports_self_reference() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+
+        // When
+        let resolved = node_resolver
+            .resolve(
+                &FileName::Real(PathBuf::from("src/internal/util.js")),
+                "app",
+            )
+            .expect("should resolve");
+
+        // Expect
+        assert_eq!(resolved, FileName::Real(PathBuf::from("src/index.js")));
+    });
+}
+
+#[test]
+fn imports() {
+    inside_directory("/tests/exports", || {
+        // Given
+        let node_resolver = NodeModulesResolver::new(TargetEnv::Node, Default::default(), true);
+
+        // When
+        let base = FileName::Real(PathBuf::from("src/index.js"));
+        let resolved = node_resolver
+            .resolve(&base, "#internal/util")
+            .expect("should resolve");
+        let dep = node_resolver
+            .resolve(&base, "#dep")
+            .expect("should resolve");
+        let missing = node_resolver.resolve(&base, "#missing");
+
+        // Expect
+        assert_eq!(
+            resolved,
+            FileName::Real(current_dir().unwrap().join("src/internal/util.js"))
+        );
+        assert_eq!(
+            dep,
+            FileName::Real(current_dir().unwrap().join("node_modules/dep/index.js"))
+        );
+        assert!(missing.is_err());
+    });
+}
@@ -0,0 +1,152 @@
+#!/usr/bin/env node
+/* eslint-disable */
+"use strict";
+
+const RAW_RUNTIME_STATE =
+'{\
+  "__info": [\
+    "This file is automatically generated. Do not touch it, or risk",\
+    "your modifications being lost."\
+  ],\
+  "dependencyTreeRoots": [\
+    {\
+      "name": "app",\
+      "reference": "workspace:."\
+    }\
+  ],\
+  "enableTopLevelFallback": true,\
+  "ignorePatternData": null,\
+  "fallbackExclusionList": [\
+    [\
+      "app",\
+      [\
+        "workspace:."\
+      ]\
+    ]\
+  ],\
+  "fallbackPool": [],\
+  "packageRegistryData": [\
+    [\
+      null,\
+      [\
+        [\
+          null,\
+          {\
+            "packageLocation": "./",\
+            "packageDependencies": [\
+              [\
+                "exported",\
+                "npm:1.0.0"\
+              ],\
+              [\
+                "lodash",\
+                "npm:4.17.21"\
+              ],\
+              [\
+                "native",\
+                "npm:1.0.0"\
+              ]\
+            ],\
+            "linkType": "SOFT"\
+          }\
+        ]\
+      ]\
+    ],\
+    [\
+      "app",\
+      [\
+        [\
+          "workspace:.",\
+          {\
+            "packageLocation": "./",\
+            "packageDependencies": [\
+              [\
+                "app",\
+                "workspace:."\
+              ],\
+              [\
+                "exported",\
+                "npm:1.0.0"\
+              ],\
+              [\
+                "lodash",\
+                "npm:4.17.21"\
+              ],\
+              [\
+                "native",\
+                "npm:1.0.0"\
+              ]\
+            ],\
+            "linkType": "SOFT"\
+          }\
+        ]\
+      ]\
+    ],\
+    [\
+      "exported",\
+      [\
+        [\
+          "npm:1.0.0",\
+          {\
+            "packageLocation": "./.yarn/cache/exported-npm-1.0.0-2c7a0d8b1e-f1e2d3c4b5.zip/node_modules/exported/",\
+            "packageDependencies": [\
+              [\
+                "exported",\
+                "npm:1.0.0"\
+              ],\
+              [\
+                "lodash",\
+                "npm:4.17.21"\
+              ],\
+              [\
+                "react",\
+                null\
+              ]\
+            ],\
+            "linkType": "HARD"\
+          }\
+        ]\
+      ]\
+    ],\
+    [\
+      "lodash",\
+      [\
+        [\
+          "npm:4.17.21",\
+          {\
+            "packageLocation": "./.yarn/cache/lodash-npm-4.17.21-6382451519-eb835a2e51.zip/node_modules/lodash/",\
+            "packageDependencies": [\
+              [\
+                "lodash",\
+                "npm:4.17.21"\
+              ]\
+            ],\
+            "linkType": "HARD"\
+          }\
+        ]\
+      ]\
+    ],\
+    [\
+      "native",\
+      [\
+        [\
+          "npm:1.0.0",\
+          {\
+            "packageLocation": "./.yarn/unplugged/native-npm-1.0.0-4f2b1c/node_modules/native/",\
+            "packageDependencies": [\
+              [\
+                "native",\
+                "npm:1.0.0"\
+              ]\
+            ],\
+            "linkType": "HARD"\
+          }\
+        ]\
+      ]\
+    ]\
+  ]\
+}';
+
+function $$SETUP_STATE(hydrateRuntimeState, basePath) {
+  return hydrateRuntimeState(JSON.parse(RAW_RUNTIME_STATE), {basePath: basePath || __dirname});
+}
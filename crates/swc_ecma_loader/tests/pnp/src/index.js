@@ -0,0 +1,3 @@
+import lodash from "lodash";
+import exported from "exported";
+import native from "native";
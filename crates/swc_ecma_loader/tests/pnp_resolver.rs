@@ -0,0 +1,114 @@
+#![cfg(feature = "pnp")]
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use swc_common::FileName;
+use swc_ecma_loader::{
+    resolve::Resolve,
+    resolvers::{node::NodeModulesResolver, pnp::PnpManifest},
+    TargetEnv,
+};
+
+fn project_dir() -> PathBuf {
+    Path::new("tests/pnp").canonicalize().unwrap()
+}
+
+fn resolver() -> NodeModulesResolver {
+    let manifest = PnpManifest::find(&project_dir().join("src"))
+        .unwrap()
+        .expect("should find .pnp.cjs");
+
+    NodeModulesResolver::new(TargetEnv::Node, Default::default(), false)
+        .with_pnp(Arc::new(manifest))
+}
+
+fn resolve(base: &Path, specifier: &str) -> PathBuf {
+    match resolver().resolve(&FileName::Real(base.to_path_buf()), specifier) {
+        Ok(FileName::Real(path)) => path,
+        other => panic!("failed to resolve `{}`: {:?}", specifier, other),
+    }
+}
+
+#[test]
+fn package_in_zip_archive() {
+    let dir = project_dir();
+    let index = dir.join("src").join("index.js");
+    let archive = dir
+        .join(".yarn")
+        .join("cache")
+        .join("lodash-npm-4.17.21-6382451519-eb835a2e51.zip");
+
+    assert_eq!(
+        resolve(&index, "lodash"),
+        archive.join("node_modules/lodash/lodash.js")
+    );
+    assert_eq!(
+        resolve(&index, "lodash/fp"),
+        archive.join("node_modules/lodash/fp.js")
+    );
+
+    let code = swc_ecma_loader::resolvers::pnp::read_to_string(&resolve(&index, "lodash")).unwrap();
+    assert_eq!(code, "module.exports = \"lodash\";\n");
+}
+
+#[test]
+fn exports_in_zip_archive() {
+    let dir = project_dir();
+    let index = dir.join("src").join("index.js");
+    let exported = resolve(&index, "exported");
+
+    assert_eq!(
+        exported,
+        dir.join(".yarn/cache/exported-npm-1.0.0-2c7a0d8b1e-f1e2d3c4b5.zip")
+            .join("node_modules/exported/esm/index.js")
+    );
+
+    // Dependencies of a package in an archive are resolved using its own
+    // dependencies.
+    assert_eq!(
+        resolve(&exported, "lodash"),
+        dir.join(".yarn/cache/lodash-npm-4.17.21-6382451519-eb835a2e51.zip")
+            .join("node_modules/lodash/lodash.js")
+    );
+}
+
+#[test]
+fn unplugged_package() {
+    let dir = project_dir();
+
+    assert_eq!(
+        resolve(&dir.join("src").join("index.js"), "native"),
+        dir.join(".yarn/unplugged/native-npm-1.0.0-4f2b1c/node_modules/native/index.js")
+    );
+}
+
+#[test]
+fn undeclared_dependencies() {
+    let dir = project_dir();
+    let index = FileName::Real(dir.join("src").join("index.js"));
+    let exported = FileName::Real(
+        dir.join(".yarn/cache/exported-npm-1.0.0-2c7a0d8b1e-f1e2d3c4b5.zip")
+            .join("node_modules/exported/esm/index.js"),
+    );
+
+    let err = resolver()
+        .resolve(&index, "react")
+        .expect_err("should not resolve undeclared dependencies");
+    assert!(
+        format!("{:?}", err).contains("isn't declared in its dependencies"),
+        "{:?}",
+        err
+    );
+
+    let err = resolver()
+        .resolve(&exported, "react")
+        .expect_err("should not resolve missing peer dependencies");
+    assert!(
+        format!("{:?}", err).contains("peer dependency"),
+        "{:?}",
+        err
+    );
+}
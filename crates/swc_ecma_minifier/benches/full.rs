@@ -92,6 +92,8 @@ fn run(src: &str) {
                 &ExtraOptions {
                     unresolved_mark,
                     top_level_mark,
+                    mangle_name_cache: None,
+                    optimization_trace: None,
                 },
             )
             .expect_module();
@@ -70,6 +70,8 @@ fn main() {
                                 &ExtraOptions {
                                     unresolved_mark,
                                     top_level_mark,
+                                    mangle_name_cache: None,
+                                    optimization_trace: None,
                                 },
                             )
                             .expect_module();
@@ -68,6 +68,8 @@ fuzz_target!(|module: Module| {
             &ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module();
@@ -29,7 +29,8 @@ use crate::{
     debug::{dump, AssertValid},
     mode::Mode,
     option::{CompressOptions, MangleOptions},
-    program_data::{analyze, ProgramData},
+    program_data::{analyze_with_options, ProgramData},
+    trace::OptimizationTrace,
     util::{now, unit::CompileUnit},
 };
 
@@ -43,6 +44,7 @@ pub(crate) fn compressor<'a, M>(
     options: &'a CompressOptions,
     mangle_options: Option<&'a MangleOptions>,
     mode: &'a M,
+    trace: Option<&'a OptimizationTrace>,
 ) -> impl 'a + VisitMut
 where
     M: Mode,
@@ -55,6 +57,7 @@ where
         pass: 1,
         dump_for_infinite_loop: Default::default(),
         mode,
+        trace,
     };
 
     chain!(
@@ -79,6 +82,8 @@ struct Compressor<'a> {
     dump_for_infinite_loop: Vec<String>,
 
     mode: &'a dyn Mode,
+
+    trace: Option<&'a OptimizationTrace>,
 }
 
 impl CompilerPass for Compressor<'_> {
@@ -101,7 +106,7 @@ impl Compressor<'_> {
         );
 
         if self.options.hoist_vars || self.options.hoist_fns {
-            let data = analyze(&*n, Some(self.marks));
+            let data = analyze_with_options(&*n, self.marks, self.options);
 
             let mut v = decl_hoister(
                 DeclHoisterConfig {
@@ -263,7 +268,7 @@ impl Compressor<'_> {
         {
             let _timer = timer!("apply full optimizer");
 
-            let mut data = analyze(&*n, Some(self.marks));
+            let mut data = analyze_with_options(&*n, self.marks, self.options);
 
             // TODO: reset_opt_flags
             //
@@ -276,6 +281,7 @@ impl Compressor<'_> {
                 &mut data,
                 self.mode,
                 !self.dump_for_infinite_loop.is_empty(),
+                self.trace,
             );
             n.apply(&mut visitor);
 
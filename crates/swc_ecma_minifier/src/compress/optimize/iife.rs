@@ -13,6 +13,7 @@ use super::{util::NormalMultiReplacer, Optimizer};
 use crate::debug::dump;
 use crate::{
     compress::optimize::Ctx,
+    trace::{BlockReason, Transform::InlineIife},
     util::{idents_captured_by, idents_used_by, make_number},
 };
 
@@ -434,8 +435,11 @@ impl Optimizer<'_> {
 
         trace_op!("iife: Checking noinline");
 
-        if self.has_noinline(call.span) {
+        let span = call.span;
+
+        if self.has_noinline(span) {
             log_abort!("iife: Has no inline mark");
+            self.trace_span(InlineIife, span, Some(BlockReason::NoInline));
             return;
         }
 
@@ -455,11 +459,13 @@ impl Optimizer<'_> {
             Expr::Arrow(f) => {
                 if f.is_async {
                     log_abort!("iife: Cannot inline async fn");
+                    self.trace_span(InlineIife, span, Some(BlockReason::AsyncOrGenerator));
                     return;
                 }
 
                 if f.is_generator {
                     log_abort!("iife: Cannot inline generator");
+                    self.trace_span(InlineIife, span, Some(BlockReason::AsyncOrGenerator));
                     return;
                 }
 
@@ -477,6 +483,7 @@ impl Optimizer<'_> {
                 }
 
                 if f.params.iter().any(|param| !param.is_ident()) {
+                    self.trace_span(InlineIife, span, Some(BlockReason::ComplexParams));
                     return;
                 }
 
@@ -492,8 +499,11 @@ impl Optimizer<'_> {
                         if let Some(new) = new {
                             self.changed = true;
                             report_change!("inline: Inlining a function call (arrow)");
+                            self.trace_span(InlineIife, span, None);
 
                             *e = new;
+                        } else {
+                            self.trace_span(InlineIife, span, Some(BlockReason::ComplexBody));
                         }
                     }
                     BlockStmtOrExpr::Expr(body) => {
@@ -574,6 +584,7 @@ impl Optimizer<'_> {
                         exprs.push(body.take());
 
                         report_change!("inline: Inlining a call to an arrow function");
+                        self.trace_span(InlineIife, span, None);
                         *e = *Expr::from_exprs(exprs);
                         e.visit_mut_with(self);
                     }
@@ -593,11 +604,13 @@ impl Optimizer<'_> {
 
                 if f.function.is_async {
                     log_abort!("iife: [x] Cannot inline async fn");
+                    self.trace_span(InlineIife, span, Some(BlockReason::AsyncOrGenerator));
                     return;
                 }
 
                 if f.function.is_generator {
                     log_abort!("iife: [x] Cannot inline generator");
+                    self.trace_span(InlineIife, span, Some(BlockReason::AsyncOrGenerator));
                     return;
                 }
 
@@ -609,6 +622,7 @@ impl Optimizer<'_> {
                     )
                 }) {
                     log_abort!("iife: [x] Found complex pattern");
+                    self.trace_span(InlineIife, span, Some(BlockReason::ComplexParams));
                     return;
                 }
 
@@ -623,6 +637,7 @@ impl Optimizer<'_> {
                         .is_some()
                     {
                         log_abort!("iife: [x] Recursive?");
+                        self.trace_span(InlineIife, span, Some(BlockReason::Recursive));
                         return;
                     }
                 }
@@ -630,6 +645,7 @@ impl Optimizer<'_> {
                 for arg in &call.args {
                     if arg.spread.is_some() {
                         log_abort!("iife: Found spread argument");
+                        self.trace_span(InlineIife, span, Some(BlockReason::SpreadArgs));
                         return;
                     }
                 }
@@ -640,6 +656,7 @@ impl Optimizer<'_> {
                 if body.stmts.is_empty() && call.args.is_empty() {
                     self.changed = true;
                     report_change!("iife: Inlining an empty function call as `undefined`");
+                    self.trace_span(InlineIife, span, None);
                     *e = *undefined(f.function.span);
                     return;
                 }
@@ -653,6 +670,7 @@ impl Optimizer<'_> {
 
                 if !self.can_inline_fn_like(&param_ids, body) {
                     log_abort!("iife: [x] Body is not inlinable");
+                    self.trace_span(InlineIife, span, Some(BlockReason::ComplexBody));
                     return;
                 }
 
@@ -660,10 +678,13 @@ impl Optimizer<'_> {
                 if let Some(new) = new {
                     self.changed = true;
                     report_change!("inline: Inlining a function call");
+                    self.trace_span(InlineIife, span, None);
 
                     dump_change_detail!("{}", dump(&new, false));
 
                     *e = new;
+                } else {
+                    self.trace_span(InlineIife, span, Some(BlockReason::ComplexBody));
                 }
 
                 //
@@ -9,6 +9,10 @@ use super::Optimizer;
 use crate::{
     compress::optimize::util::is_valid_for_lhs,
     program_data::VarUsageInfo,
+    trace::{
+        BlockReason,
+        Transform::{InlineFn, InlineVar},
+    },
     util::{
         idents_captured_by, idents_used_by, idents_used_by_ignoring_nested, size::SizeWithCtxt,
     },
@@ -35,6 +39,7 @@ impl Optimizer<'_> {
         );
 
         if self.data.top.has_eval_call {
+            self.trace(InlineVar, ident, Some(BlockReason::EvalScope));
             return;
         }
 
@@ -56,6 +61,7 @@ impl Optimizer<'_> {
                 return;
             }
             if usage.inline_prevented {
+                self.trace(InlineVar, ident, Some(BlockReason::InlinePrevented));
                 return;
             }
 
@@ -64,11 +70,13 @@ impl Optimizer<'_> {
                     "inline: [x] Preserving non-const variable `{}` because it's top-level",
                     crate::debug::dump(ident, false)
                 );
+                self.trace(InlineVar, ident, Some(BlockReason::TopLevel));
                 return;
             }
 
             if usage.used_above_decl {
                 log_abort!("inline: [x] It's cond init or used before decl",);
+                self.trace(InlineVar, ident, Some(BlockReason::UsedBeforeDecl));
                 return;
             }
 
@@ -76,6 +84,7 @@ impl Optimizer<'_> {
             if ref_count == 0 {
                 if init.may_have_side_effects(&self.expr_ctx) {
                     // TODO: Inline partially
+                    self.trace(InlineVar, ident, Some(BlockReason::SideEffects));
                     return;
                 }
 
@@ -140,11 +149,13 @@ impl Optimizer<'_> {
                         {
                         } else {
                             log_abort!("inline: [x] It's not fn-local");
+                            self.trace(InlineVar, ident, Some(BlockReason::CrossesFnBoundary));
                             return;
                         }
                     }
                     _ => {
                         log_abort!("inline: [x] It's not fn-local");
+                        self.trace(InlineVar, ident, Some(BlockReason::CrossesFnBoundary));
                         return;
                     }
                 }
@@ -167,6 +178,7 @@ impl Optimizer<'_> {
             }
 
             if usage.used_recursively {
+                self.trace(InlineVar, ident, Some(BlockReason::Recursive));
                 return;
             }
 
@@ -300,6 +312,8 @@ impl Optimizer<'_> {
 
                     inc_usage();
 
+                    self.trace(InlineVar, ident, None);
+
                     self.vars.lits.insert(id.clone(), init.take().into());
 
                     ident.take();
@@ -340,15 +354,24 @@ impl Optimizer<'_> {
                                 }
                         ) =>
                     {
-                        return
+                        self.trace(InlineVar, ident, Some(BlockReason::AsyncOrGenerator));
+                        return;
                     }
                     Expr::Arrow(ArrowExpr { is_async: true, .. })
                     | Expr::Arrow(ArrowExpr {
                         is_generator: true, ..
-                    }) => return,
+                    }) => {
+                        self.trace(InlineVar, ident, Some(BlockReason::AsyncOrGenerator));
+                        return;
+                    }
 
                     Expr::Lit(Lit::Regex(..)) => {
-                        if !usage.is_fn_local || usage.executed_multiple_time {
+                        if !usage.is_fn_local {
+                            self.trace(InlineVar, ident, Some(BlockReason::CrossesFnBoundary));
+                            return;
+                        }
+                        if usage.executed_multiple_time {
+                            self.trace(InlineVar, ident, Some(BlockReason::UsedInLoop));
                             return;
                         }
                     }
@@ -356,6 +379,7 @@ impl Optimizer<'_> {
                     Expr::This(..) => {
                         // Don't inline this if it passes function boundaries.
                         if !usage.is_fn_local {
+                            self.trace(InlineVar, ident, Some(BlockReason::CrossesFnBoundary));
                             return;
                         }
                     }
@@ -363,6 +387,7 @@ impl Optimizer<'_> {
                     Expr::Lit(..) => {}
 
                     Expr::Fn(_) if !usage.can_inline_fn_once() => {
+                        self.trace(InlineVar, ident, Some(BlockReason::InlinePrevented));
                         return;
                     }
 
@@ -375,6 +400,11 @@ impl Optimizer<'_> {
                             }
                             if let Some(v_usage) = self.data.vars.get(&id) {
                                 if v_usage.reassigned {
+                                    self.trace(
+                                        InlineVar,
+                                        ident,
+                                        Some(BlockReason::DependsOnReassigned),
+                                    );
                                     return;
                                 }
                             } else {
@@ -392,6 +422,11 @@ impl Optimizer<'_> {
                             }
                             if let Some(v_usage) = self.data.vars.get(&id) {
                                 if v_usage.reassigned {
+                                    self.trace(
+                                        InlineVar,
+                                        ident,
+                                        Some(BlockReason::DependsOnReassigned),
+                                    );
                                     return;
                                 }
                             } else {
@@ -404,6 +439,11 @@ impl Optimizer<'_> {
                         for id in idents_used_by_ignoring_nested(init) {
                             if let Some(v_usage) = self.data.vars.get(&id) {
                                 if v_usage.reassigned {
+                                    self.trace(
+                                        InlineVar,
+                                        ident,
+                                        Some(BlockReason::DependsOnReassigned),
+                                    );
                                     return;
                                 }
                             }
@@ -434,6 +474,11 @@ impl Optimizer<'_> {
                         for id in idents_used_by(init) {
                             if let Some(v_usage) = self.data.vars.get(&id) {
                                 if v_usage.reassigned || v_usage.has_property_mutation {
+                                    self.trace(
+                                        InlineVar,
+                                        ident,
+                                        Some(BlockReason::DependsOnReassigned),
+                                    );
                                     return;
                                 }
                             }
@@ -443,6 +488,7 @@ impl Optimizer<'_> {
 
                 if usage.used_as_arg && !usage.is_fn_local {
                     if let Expr::Fn(..) | Expr::Arrow(..) = init {
+                        self.trace(InlineVar, ident, Some(BlockReason::UsedAsArg));
                         return;
                     }
                 }
@@ -462,18 +508,21 @@ impl Optimizer<'_> {
 
                                 for param in params {
                                     if captured.contains(&param) {
+                                        self.trace(InlineVar, ident, Some(BlockReason::UsedInLoop));
                                         return;
                                     }
                                 }
                             }
                         }
                         _ => {
+                            self.trace(InlineVar, ident, Some(BlockReason::UsedInLoop));
                             return;
                         }
                     }
                 }
 
                 if init.may_have_side_effects(&self.expr_ctx) {
+                    self.trace(InlineVar, ident, Some(BlockReason::SideEffects));
                     return;
                 }
 
@@ -482,10 +531,21 @@ impl Optimizer<'_> {
                     ident
                 );
                 self.changed = true;
+                self.trace(InlineVar, ident, None);
 
                 self.vars
                     .vars_for_inlining
                     .insert(ident.take().to_id(), init.take().into());
+            } else if !self.vars.lits.contains_key(&id) {
+                if self.ctx.is_exported {
+                    self.trace(InlineVar, ident, Some(BlockReason::Exported));
+                } else if usage.reassigned || usage.assign_count != 1 {
+                    self.trace(InlineVar, ident, Some(BlockReason::Reassigned));
+                } else if ref_count > 1 {
+                    self.trace(InlineVar, ident, Some(BlockReason::UsedMultipleTimes));
+                } else if !may_remove {
+                    self.trace(InlineVar, ident, Some(BlockReason::TopLevel));
+                }
             }
         }
     }
@@ -567,6 +627,7 @@ impl Optimizer<'_> {
             Decl::Class(v) => v.ident.clone(),
             Decl::Fn(f) => {
                 if f.function.is_async {
+                    self.trace(InlineFn, &f.ident, Some(BlockReason::AsyncOrGenerator));
                     return;
                 }
 
@@ -584,20 +645,24 @@ impl Optimizer<'_> {
 
         if !self.may_remove_ident(&i) {
             log_abort!("inline: [x] Top level");
+            self.trace(InlineFn, &i, Some(BlockReason::TopLevel));
             return;
         }
 
         if self.has_noinline(decl.span()) {
             log_abort!("inline: [x] Has noinline");
+            self.trace(InlineFn, &i, Some(BlockReason::NoInline));
             return;
         }
 
         if self.ctx.is_exported {
             log_abort!("inline: [x] exported");
+            self.trace(InlineFn, &i, Some(BlockReason::Exported));
             return;
         }
 
         if self.data.top.has_eval_call || self.data.top.has_with_stmt {
+            self.trace(InlineFn, &i, Some(BlockReason::EvalScope));
             return;
         }
 
@@ -609,6 +674,7 @@ impl Optimizer<'_> {
 
             if usage.used_as_arg && usage.ref_count > 1 {
                 log_abort!("inline: Used as an arugment");
+                self.trace(InlineFn, &i, Some(BlockReason::UsedAsArg));
                 return;
             }
 
@@ -618,6 +684,15 @@ impl Optimizer<'_> {
                     usage.reassigned,
                     usage.inline_prevented
                 );
+                self.trace(
+                    InlineFn,
+                    &i,
+                    Some(if usage.reassigned {
+                        BlockReason::Reassigned
+                    } else {
+                        BlockReason::InlinePrevented
+                    }),
+                );
                 return;
             }
 
@@ -642,6 +717,7 @@ impl Optimizer<'_> {
                                 if f.function.params.iter().any(|param| {
                                     matches!(param.pat, Pat::Rest(..) | Pat::Assign(..))
                                 }) {
+                                    self.trace(InlineFn, &i, Some(BlockReason::ComplexParams));
                                     return;
                                 }
                                 trace_op!(
@@ -650,6 +726,7 @@ impl Optimizer<'_> {
                                     f.ident.sym,
                                     f.ident.span.ctxt
                                 );
+                                self.trace(InlineFn, &i, None);
 
                                 for i in collect_infects_from(
                                     &f.function,
@@ -702,6 +779,7 @@ impl Optimizer<'_> {
             {
                 if let Decl::Class(ClassDecl { class, .. }) = decl {
                     if class_has_side_effect(&self.expr_ctx, class) {
+                        self.trace(InlineFn, &i, Some(BlockReason::SideEffects));
                         return;
                     }
                 }
@@ -713,6 +791,7 @@ impl Optimizer<'_> {
                             || self.mangle_options.map_or(false, |v| v.keep_class_names)
                         {
                             log_abort!("inline: [x] Keep fn names");
+                            self.trace(InlineFn, &i, Some(BlockReason::KeepNames));
                             return;
                         }
 
@@ -728,6 +807,7 @@ impl Optimizer<'_> {
                             || self.mangle_options.map_or(false, |v| v.keep_fn_names)
                         {
                             log_abort!("inline: [x] Keep fn names");
+                            self.trace(InlineFn, &i, Some(BlockReason::KeepNames));
                             return;
                         }
 
@@ -759,9 +839,19 @@ impl Optimizer<'_> {
                     }
                 };
 
+                self.trace(InlineFn, &i, None);
                 self.vars.vars_for_inlining.insert(i.to_id(), e);
-            } else {
+            } else if !self.vars.simple_functions.contains_key(&i.to_id()) {
                 log_abort!("inline: [x] Usage: {:?}", usage);
+
+                let reason = if usage.ref_count > 1 {
+                    BlockReason::UsedMultipleTimes
+                } else if usage.used_above_decl {
+                    BlockReason::UsedBeforeDecl
+                } else {
+                    BlockReason::InlinePrevented
+                };
+                self.trace(InlineFn, &i, Some(reason));
             }
         }
     }
@@ -32,6 +32,7 @@ use crate::{
     mode::Mode,
     option::{CompressOptions, MangleOptions},
     program_data::ProgramData,
+    trace::OptimizationTrace,
     util::{
         contains_eval, contains_leaping_continue_with_label, make_number, ExprOptExt, ModuleItemExt,
     },
@@ -63,6 +64,7 @@ pub(super) fn optimizer<'a>(
     data: &'a mut ProgramData,
     mode: &'a dyn Mode,
     debug_infinite_loop: bool,
+    trace: Option<&'a OptimizationTrace>,
 ) -> impl 'a + VisitMut + Repeated {
     assert!(
         options.top_retain.iter().all(|s| s.trim() != ""),
@@ -95,6 +97,7 @@ pub(super) fn optimizer<'a>(
         mode,
         debug_infinite_loop,
         functions: Default::default(),
+        trace,
     }
 }
 
@@ -225,6 +228,8 @@ struct Optimizer<'a> {
     debug_infinite_loop: bool,
 
     functions: Box<FxHashMap<Id, FnMetadata>>,
+
+    trace: Option<&'a OptimizationTrace>,
 }
 
 #[derive(Default)]
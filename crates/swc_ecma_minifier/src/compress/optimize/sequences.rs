@@ -23,6 +23,7 @@ use crate::{
         util::{is_directive, is_ident_used_by, replace_expr},
     },
     option::CompressOptions,
+    trace::{BlockReason, Transform::CollapseVars},
     util::{idents_used_by, idents_used_by_ignoring_nested, ExprOptExt, ModuleItemExt},
 };
 
@@ -2210,6 +2211,11 @@ impl Optimizer<'_> {
 
                         if let Some(usage) = self.data.vars.get(&left_id.to_id()) {
                             if usage.inline_prevented {
+                                self.trace(
+                                    CollapseVars,
+                                    left_id,
+                                    Some(BlockReason::InlinePrevented),
+                                );
                                 return Ok(false);
                             }
 
@@ -2261,6 +2267,14 @@ impl Optimizer<'_> {
                         if is_lit {
                             can_take_init = false
                         } else {
+                            let reason = if usage.reassigned {
+                                BlockReason::Reassigned
+                            } else if !usage.is_fn_local {
+                                BlockReason::CrossesFnBoundary
+                            } else {
+                                BlockReason::UsedMultipleTimes
+                            };
+                            self.trace(CollapseVars, &left, Some(reason));
                             return Ok(false);
                         }
                     } else {
@@ -2453,6 +2467,10 @@ impl Optimizer<'_> {
                     v.pat_usage
                 );
 
+                if v.expr_usage > 1 {
+                    self.trace(CollapseVars, &left_id, Some(BlockReason::UsedMultipleTimes));
+                }
+
                 return Ok(false);
             }
         }
@@ -2463,6 +2481,7 @@ impl Optimizer<'_> {
             left_id.sym,
             left_id.span.ctxt
         );
+        self.trace(CollapseVars, &left_id, None);
 
         let to = take_a!(false, false);
 
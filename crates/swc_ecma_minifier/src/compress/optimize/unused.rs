@@ -9,7 +9,22 @@ use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 use super::Optimizer;
 #[cfg(feature = "debug")]
 use crate::debug::dump;
-use crate::{compress::optimize::util::extract_class_side_effect, option::PureGetterOption};
+use crate::{
+    compress::optimize::util::extract_class_side_effect,
+    option::PureGetterOption,
+    program_data::VarUsageInfo,
+    trace::{BlockReason, Transform::DropUnused},
+};
+
+fn drop_blocked_by(usage: &VarUsageInfo) -> BlockReason {
+    if usage.reassigned {
+        BlockReason::Reassigned
+    } else if usage.has_property_mutation {
+        BlockReason::PropertyMutation
+    } else {
+        BlockReason::Used
+    }
+}
 
 #[derive(Debug, Default, Clone, Copy)]
 pub(crate) struct PropertyAccessOpts {
@@ -116,6 +131,9 @@ impl Optimizer<'_> {
                     "unused: Preserving `{}` because of usages",
                     dump(&*name, false)
                 );
+                if let Pat::Ident(i) = &*name {
+                    self.trace(DropUnused, &i.id, Some(BlockReason::EvalScope));
+                }
                 return;
             }
         }
@@ -144,6 +162,7 @@ impl Optimizer<'_> {
 
         if !self.may_remove_ident(i) {
             log_abort!("unused: Preserving var `{:#?}` because it's top-level", i);
+            self.trace(DropUnused, i, Some(BlockReason::TopLevel));
             return;
         }
 
@@ -160,6 +179,7 @@ impl Optimizer<'_> {
                     i.sym,
                     i.span.ctxt
                 );
+                self.trace(DropUnused, i, None);
                 // This will remove variable.
                 i.take();
                 return;
@@ -190,6 +210,7 @@ impl Optimizer<'_> {
                 "unused: Cannot drop ({}) because it's used",
                 dump(&*i, false)
             );
+            self.trace(DropUnused, i, Some(drop_blocked_by(&v)));
         }
     }
 
@@ -381,23 +402,35 @@ impl Optimizer<'_> {
         }
     }
 
+    fn trace_decl(&self, decl: &Decl, reason: BlockReason) {
+        match decl {
+            Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+                self.trace(DropUnused, ident, Some(reason))
+            }
+            _ => {}
+        }
+    }
+
     /// Creates an empty [VarDecl] if `decl` should be removed.
     #[cfg_attr(feature = "debug", tracing::instrument(skip_all))]
     pub(super) fn drop_unused_decl(&mut self, decl: &mut Decl) {
-        if self.ctx.is_exported {
+        if !self.options.unused {
             return;
         }
 
-        if !self.options.top_level() && self.ctx.is_top_level_for_block_level_vars() {
+        if self.ctx.is_exported {
+            self.trace_decl(decl, BlockReason::Exported);
             return;
         }
 
-        if !self.options.unused {
+        if !self.options.top_level() && self.ctx.is_top_level_for_block_level_vars() {
+            self.trace_decl(decl, BlockReason::TopLevel);
             return;
         }
 
         if let Some(scope) = self.data.scopes.get(&self.ctx.scope) {
             if scope.has_eval_call || scope.has_with_stmt {
+                self.trace_decl(decl, BlockReason::EvalScope);
                 return;
             }
         }
@@ -427,6 +460,7 @@ impl Optimizer<'_> {
                     _ => false,
                 });
                 if may_have_side_effect {
+                    self.trace(DropUnused, ident, Some(BlockReason::SideEffects));
                     return;
                 }
 
@@ -444,6 +478,7 @@ impl Optimizer<'_> {
                         ident.sym,
                         ident.span.ctxt
                     );
+                    self.trace(DropUnused, ident, None);
                     // This will remove the declaration.
                     let class = decl.take().class().unwrap();
                     let mut side_effects = extract_class_side_effect(&self.expr_ctx, *class.class);
@@ -462,6 +497,8 @@ impl Optimizer<'_> {
                             },
                         }))
                     }
+                } else if let Some(v) = self.data.vars.get(&ident.to_id()) {
+                    self.trace(DropUnused, ident, Some(drop_blocked_by(v)));
                 }
             }
             Decl::Fn(FnDecl { ident, .. }) => {
@@ -484,8 +521,11 @@ impl Optimizer<'_> {
                         ident.sym,
                         ident.span.ctxt
                     );
+                    self.trace(DropUnused, ident, None);
                     // This will remove the declaration.
                     decl.take();
+                } else if let Some(v) = self.data.vars.get(&ident.to_id()) {
+                    self.trace(DropUnused, ident, Some(drop_blocked_by(v)));
                 }
             }
 
@@ -13,7 +13,10 @@ use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
 use tracing::debug;
 
 use super::{Ctx, Optimizer};
-use crate::HEAVY_TASK_PARALLELS;
+use crate::{
+    trace::{BlockReason, OptimizationEvent, Transform},
+    HEAVY_TASK_PARALLELS,
+};
 
 impl<'b> Optimizer<'b> {
     pub(super) fn normalize_expr(&mut self, e: &mut Expr) {
@@ -77,6 +80,38 @@ impl<'b> Optimizer<'b> {
         span.has_mark(self.marks.noinline)
     }
 
+    /// Records a decision about `i` to the optimization trace.
+    ///
+    /// `blocked_by` is [None] if the optimization is applied.
+    pub(super) fn trace(&self, transform: Transform, i: &Ident, blocked_by: Option<BlockReason>) {
+        if let Some(trace) = self.trace {
+            trace.record(OptimizationEvent {
+                span: i.span,
+                transform,
+                target: Some(i.sym.clone()),
+                blocked_by,
+            });
+        }
+    }
+
+    /// Records a decision about a node without a name to the optimization
+    /// trace.
+    pub(super) fn trace_span(
+        &self,
+        transform: Transform,
+        span: Span,
+        blocked_by: Option<BlockReason>,
+    ) {
+        if let Some(trace) = self.trace {
+            trace.record(OptimizationEvent {
+                span,
+                transform,
+                target: None,
+                blocked_by,
+            });
+        }
+    }
+
     /// RAII guard to change context temporarically
     pub(super) fn with_ctx(&mut self, mut ctx: Ctx) -> WithCtx<'_, 'b> {
         let mut scope_ctxt = ctx.scope;
@@ -89,6 +89,7 @@ impl Evaluator {
                 },
                 None,
                 &data,
+                None,
             ));
         }
     }
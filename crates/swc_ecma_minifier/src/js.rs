@@ -1,11 +1,16 @@
 //! NOT A PUBLIC API
 
-use serde::{Deserialize, Serialize};
-use swc_config::config_types::BoolOrDataConfig;
+use std::{io, path::Path};
 
-use crate::option::{
-    terser::{TerserCompressorOptions, TerserEcmaVersion},
-    MangleOptions,
+use serde::{Deserialize, Serialize};
+use swc_config::config_types::{BoolOr, BoolOrDataConfig};
+
+use crate::{
+    option::{
+        terser::{TerserCompressorOptions, TerserEcmaVersion},
+        MangleOptions, NameCache,
+    },
+    size::SizeBudget,
 };
 
 /// Second argument of `minify`.
@@ -53,6 +58,41 @@ pub struct JsMinifyOptions {
 
     #[serde(default = "true_by_default")]
     pub emit_source_map_columns: bool,
+
+    /// Names chosen by the mangler for previous files. The updated cache is
+    /// returned as `nameCache` of the extra output.
+    #[serde(default)]
+    pub name_cache: Option<NameCache>,
+
+    /// Records why the compressor kept code. The trace is returned as
+    /// `optimizationTrace` of the extra output.
+    #[serde(default)]
+    pub optimization_trace: bool,
+
+    /// Reports raw, gzip and brotli sizes of the output, and sizes of
+    /// top-level declarations.
+    ///
+    /// Requires the `size-report` feature.
+    #[serde(default)]
+    pub report_size: bool,
+
+    /// Fails if the output is larger than this.
+    ///
+    /// Requires the `size-report` feature.
+    #[serde(default)]
+    pub size_budget: Option<SizeBudget>,
+}
+
+impl JsMinifyOptions {
+    /// Reads `pure_libraries` of `compress` given as a path, which is resolved
+    /// against `base`.
+    pub fn load_pure_libraries(&mut self, base: &Path) -> io::Result<()> {
+        if let Some(BoolOr::Data(compress)) = self.compress.as_mut().into_inner() {
+            compress.load_pure_libraries(base)?;
+        }
+
+        Ok(())
+    }
 }
 
 fn true_by_default() -> bool {
@@ -75,10 +75,13 @@ pub mod eval;
 pub mod js;
 mod metadata;
 mod mode;
+pub mod module_graph;
 pub mod option;
 mod pass;
 mod program_data;
+pub mod size;
 pub mod timing;
+pub mod trace;
 mod util;
 
 pub mod marks {
@@ -137,7 +140,7 @@ pub fn optimize(
             options.compress.as_ref(),
             comments,
             marks,
-            // extra.unresolved_mark,
+            extra.unresolved_mark,
         ));
         debug_assert_valid(&n);
     }
@@ -187,6 +190,7 @@ pub fn optimize(
                 c,
                 options.mangle.as_ref(),
                 &Minification,
+                extra.optimization_trace.as_deref(),
             ))
         }
 
@@ -247,10 +251,17 @@ pub fn optimize(
             preserved,
             chars,
             extra.top_level_mark,
+            extra.unresolved_mark,
+            extra.mangle_name_cache.clone(),
         ));
 
         if let Some(property_mangle_options) = &mangle.props {
-            mangle_properties(&mut n, property_mangle_options.clone(), chars);
+            mangle_properties(
+                &mut n,
+                property_mangle_options.clone(),
+                chars,
+                extra.mangle_name_cache.as_deref(),
+            );
         }
     }
 
@@ -1,16 +1,19 @@
 use rustc_hash::FxHashSet;
 use swc_common::{
     comments::{Comment, CommentKind, Comments},
-    Span, Spanned,
+    Mark, Span, Spanned, SyntaxContext,
 };
 use swc_ecma_ast::*;
-use swc_ecma_usage_analyzer::marks::Marks;
+use swc_ecma_usage_analyzer::{
+    marks::Marks,
+    pure_globals::{global_path, is_pure_builtin_call, is_pure_builtin_new},
+};
 use swc_ecma_utils::NodeIgnoringSpan;
 use swc_ecma_visit::{
     noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith,
 };
 
-use crate::option::CompressOptions;
+use crate::option::{CompressOptions, PureLibraries};
 
 #[cfg(test)]
 mod tests;
@@ -20,7 +23,7 @@ pub(crate) fn info_marker<'a>(
     options: Option<&'a CompressOptions>,
     comments: Option<&'a dyn Comments>,
     marks: Marks,
-    // unresolved_mark: Mark,
+    unresolved_mark: Mark,
 ) -> impl 'a + VisitMut {
     let pure_funcs = options.map(|options| {
         options
@@ -34,9 +37,10 @@ pub(crate) fn info_marker<'a>(
         comments,
         marks,
         pure_funcs,
-        // unresolved_mark,
+        unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
         state: Default::default(),
         pure_callee: Default::default(),
+        pure_namespaces: Default::default(),
     }
 }
 
@@ -47,17 +51,69 @@ struct State {
 }
 
 struct InfoMarker<'a> {
-    #[allow(dead_code)]
     options: Option<&'a CompressOptions>,
     pure_funcs: Option<FxHashSet<NodeIgnoringSpan<'a, Expr>>>,
     pure_callee: FxHashSet<Id>,
+    /// Default and namespace imports of [PureLibraries].
+    pure_namespaces: FxHashSet<Id>,
 
     comments: Option<&'a dyn Comments>,
     marks: Marks,
-    // unresolved_mark: Mark,
+    unresolved_ctxt: SyntaxContext,
     state: State,
 }
 
+impl InfoMarker<'_> {
+    /// Checks the built-in list of pure globals, if enabled by
+    /// `pure_builtins`, and [PureLibraries].
+    fn is_known_pure_call(&self, callee: &Expr, args: &[ExprOrSpread]) -> bool {
+        let options = match self.options {
+            Some(v) => v,
+            None => return false,
+        };
+
+        if options.pure_builtins
+            && options.pristine_globals
+            && is_pure_builtin_call(
+                callee,
+                args,
+                options.ecma,
+                self.unresolved_ctxt,
+                options.unsafe_passes,
+            )
+        {
+            return true;
+        }
+
+        if let Expr::Member(MemberExpr { obj, .. }) = callee {
+            if let Expr::Ident(obj) = &**obj {
+                if self.pure_namespaces.contains(&obj.to_id()) {
+                    return true;
+                }
+            }
+        }
+
+        !options.pure_libraries.globals.is_empty()
+            && global_path(callee, self.unresolved_ctxt)
+                .map_or(false, |path| options.pure_libraries.globals.contains(&path))
+    }
+
+    fn is_known_pure_new(&self, callee: &Expr, args: Option<&[ExprOrSpread]>) -> bool {
+        match self.options {
+            Some(options) if options.pure_builtins && options.pristine_globals => {
+                is_pure_builtin_new(
+                    callee,
+                    args,
+                    options.ecma,
+                    self.unresolved_ctxt,
+                    options.unsafe_passes,
+                )
+            }
+            _ => false,
+        }
+    }
+}
+
 impl VisitMut for InfoMarker<'_> {
     noop_visit_mut_type!();
 
@@ -72,8 +128,8 @@ impl VisitMut for InfoMarker<'_> {
         // See https://github.com/swc-project/swc/issues/7241
         if match &n.callee {
             Callee::Expr(e) => match &**e {
-                Expr::Ident(callee) => self.pure_callee.contains(&callee.to_id()),
-                _ => false,
+                Expr::Ident(callee) if self.pure_callee.contains(&callee.to_id()) => true,
+                callee => self.is_known_pure_call(callee, &n.args),
             },
             _ => false,
         } || has_pure(self.comments, n.span)
@@ -146,7 +202,12 @@ impl VisitMut for InfoMarker<'_> {
     fn visit_mut_module(&mut self, n: &mut Module) {
         n.visit_with(&mut InfoCollector {
             comments: self.comments,
+            pure_libraries: self
+                .options
+                .map(|options| &options.pure_libraries)
+                .filter(|libs| !libs.is_empty()),
             pure_callees: &mut self.pure_callee,
+            pure_namespaces: &mut self.pure_namespaces,
         });
 
         n.visit_mut_children_with(self);
@@ -162,7 +223,7 @@ impl VisitMut for InfoMarker<'_> {
     fn visit_mut_new_expr(&mut self, n: &mut NewExpr) {
         n.visit_mut_children_with(self);
 
-        if has_pure(self.comments, n.span) {
+        if has_pure(self.comments, n.span) || self.is_known_pure_new(&n.callee, n.args.as_deref()) {
             n.span = n.span.apply_mark(self.marks.pure);
         }
     }
@@ -170,7 +231,12 @@ impl VisitMut for InfoMarker<'_> {
     fn visit_mut_script(&mut self, n: &mut Script) {
         n.visit_with(&mut InfoCollector {
             comments: self.comments,
+            pure_libraries: self
+                .options
+                .map(|options| &options.pure_libraries)
+                .filter(|libs| !libs.is_empty()),
             pure_callees: &mut self.pure_callee,
+            pure_namespaces: &mut self.pure_namespaces,
         });
 
         n.visit_mut_children_with(self);
@@ -203,8 +269,10 @@ const NO_SIDE_EFFECTS_FLAG: &str = "NO_SIDE_EFFECTS";
 
 struct InfoCollector<'a> {
     comments: Option<&'a dyn Comments>,
+    pure_libraries: Option<&'a PureLibraries>,
 
     pure_callees: &'a mut FxHashSet<Id>,
+    pure_namespaces: &'a mut FxHashSet<Id>,
 }
 
 impl Visit for InfoCollector<'_> {
@@ -220,6 +288,31 @@ impl Visit for InfoCollector<'_> {
         }
     }
 
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only
+            || !self
+                .pure_libraries
+                .map_or(false, |libs| libs.matches_module(&n.src.value))
+        {
+            return;
+        }
+
+        for s in &n.specifiers {
+            match s {
+                ImportSpecifier::Named(s) => {
+                    self.pure_callees.insert(s.local.to_id());
+                }
+                ImportSpecifier::Default(s) => {
+                    self.pure_callees.insert(s.local.to_id());
+                    self.pure_namespaces.insert(s.local.to_id());
+                }
+                ImportSpecifier::Namespace(s) => {
+                    self.pure_namespaces.insert(s.local.to_id());
+                }
+            }
+        }
+    }
+
     fn visit_fn_decl(&mut self, f: &FnDecl) {
         f.visit_children_with(self);
 
@@ -0,0 +1,740 @@
+//! Minification of a graph of ES modules.
+//!
+//! Each module is still emitted as a separate file, but the import edges
+//! between them are used to
+//!
+//!  - propagate constant exports (e.g. `export const DEBUG = false`) into the
+//!    importers,
+//!  - drop exports which are not used by any module of the graph, according to
+//!    the usage analysis of the importers, and
+//!  - name imported bindings after the exported ones, and share mangled
+//!    property names.
+//!
+//! Other top-level names are not shared, as top-level bindings of ES modules
+//! are not globals.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet;
+use swc_atoms::JsWord;
+use swc_common::{
+    collections::AHashMap, comments::Comments, sync::Lrc, util::take::Take, SourceMap,
+};
+use swc_ecma_ast::*;
+use swc_ecma_utils::find_pat_ids;
+use swc_ecma_visit::{
+    noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+use crate::{
+    optimize,
+    option::{ExtraOptions, MangleCache, MinifyOptions, SimpleMangleCache, TopLevelOptions},
+    program_data::{analyze, ProgramData},
+};
+
+/// A module of the graph passed to [optimize_module_graph].
+#[derive(Debug)]
+pub struct GraphModule {
+    /// It should be processed by `resolver` with the marks of [ExtraOptions].
+    pub module: Module,
+
+    /// Resolved import edges, from import sources of `module` to indices of
+    /// modules in the graph.
+    ///
+    /// Sources which are not in this map are treated as external modules.
+    pub deps: AHashMap<JsWord, usize>,
+
+    /// `true` if the exports of this module are used from outside of the
+    /// graph, so they should be preserved.
+    pub is_entry: bool,
+}
+
+/// Minifies modules of a graph, and returns them in the same order.
+///
+/// Top-level bindings of ES modules are not globals, so `top_level` of both
+/// compress and mangle options is enabled.
+pub fn optimize_module_graph(
+    mut modules: Vec<GraphModule>,
+    cm: Lrc<SourceMap>,
+    comments: Option<&dyn Comments>,
+    options: &MinifyOptions,
+    extra: &ExtraOptions,
+) -> Vec<Module> {
+    let props_cache = extra
+        .mangle_name_cache
+        .clone()
+        .unwrap_or_else(|| Arc::new(SimpleMangleCache::default()) as Arc<dyn MangleCache>);
+    let compress_extra = ExtraOptions {
+        unresolved_mark: extra.unresolved_mark,
+        top_level_mark: extra.top_level_mark,
+        mangle_name_cache: None,
+        optimization_trace: extra.optimization_trace.clone(),
+    };
+
+    let mut compress_options = options.clone();
+    compress_options.mangle = None;
+    if let Some(c) = &mut compress_options.compress {
+        c.module = true;
+        c.top_level = Some(TopLevelOptions { functions: true });
+    }
+
+    let mut mangle_options = options.clone();
+    mangle_options.compress = None;
+    if let Some(m) = &mut mangle_options.mangle {
+        m.top_level = Some(true);
+    }
+
+    // Optimizing a module may remove the last usage of an import, which makes
+    // an export of another module unused.
+    let mut dirty = vec![true; modules.len()];
+    loop {
+        shake_exports(&mut modules, &mut dirty);
+
+        if !dirty.iter().any(|v| *v) {
+            break;
+        }
+
+        for (module, dirty) in modules.iter_mut().zip(dirty.iter_mut()) {
+            if !*dirty {
+                continue;
+            }
+            *dirty = false;
+
+            if compress_options.compress.is_some() {
+                module.module = optimize(
+                    Program::Module(module.module.take()),
+                    cm.clone(),
+                    comments,
+                    None,
+                    &compress_options,
+                    &compress_extra,
+                )
+                .expect_module();
+            }
+
+            drop_unused_imports(&mut module.module);
+        }
+    }
+
+    if mangle_options.mangle.is_none() {
+        return modules.into_iter().map(|m| m.module).collect();
+    }
+
+    // Dependencies are mangled first, so the importers can reuse the names of
+    // their exports.
+    let mut local_names = modules.iter().map(|_| None).collect::<Vec<_>>();
+    for idx in dependency_order(&modules) {
+        let m = &mut modules[idx];
+
+        let cache = GraphNameCache {
+            imports: imported_names(m, &local_names),
+            props: props_cache.clone(),
+        };
+        let extra = ExtraOptions {
+            unresolved_mark: extra.unresolved_mark,
+            top_level_mark: extra.top_level_mark,
+            mangle_name_cache: Some(Arc::new(cache)),
+            optimization_trace: extra.optimization_trace.clone(),
+        };
+
+        m.module = optimize(
+            Program::Module(m.module.take()),
+            cm.clone(),
+            comments,
+            None,
+            &mangle_options,
+            &extra,
+        )
+        .expect_module();
+
+        local_names[idx] = Some(exported_locals(&m.module));
+    }
+
+    modules.into_iter().map(|m| m.module).collect()
+}
+
+/// Returns indices of modules, where dependencies come before their
+/// importers unless there's a cycle.
+fn dependency_order(modules: &[GraphModule]) -> Vec<usize> {
+    fn visit(modules: &[GraphModule], idx: usize, done: &mut [bool], order: &mut Vec<usize>) {
+        if done[idx] {
+            return;
+        }
+        done[idx] = true;
+
+        let mut deps = modules[idx].deps.values().copied().collect::<Vec<_>>();
+        deps.sort_unstable();
+        for dep in deps {
+            visit(modules, dep, done, order);
+        }
+
+        order.push(idx);
+    }
+
+    let mut done = vec![false; modules.len()];
+    let mut order = Vec::with_capacity(modules.len());
+    for idx in 0..modules.len() {
+        visit(modules, idx, &mut done, &mut order);
+    }
+    order
+}
+
+/// [MangleCache] for a module of the graph.
+#[derive(Debug)]
+struct GraphNameCache {
+    /// Names for imported bindings, by their original names.
+    imports: AHashMap<JsWord, JsWord>,
+
+    /// Shared by all modules, as objects are passed across modules.
+    props: Arc<dyn MangleCache>,
+}
+
+impl MangleCache for GraphNameCache {
+    fn vars_cache(&self) -> AHashMap<JsWord, JsWord> {
+        self.imports.clone()
+    }
+
+    fn props_cache(&self) -> AHashMap<JsWord, JsWord> {
+        self.props.props_cache()
+    }
+
+    fn update_vars_cache(&self, _: &AHashMap<JsWord, JsWord>) {}
+
+    fn update_props_cache(&self, new_data: &AHashMap<JsWord, JsWord>) {
+        self.props.update_props_cache(new_data)
+    }
+}
+
+/// Returns the names of the local bindings of exports, by export names.
+fn exported_locals(m: &Module) -> AHashMap<JsWord, JsWord> {
+    let mut names = AHashMap::default();
+
+    for item in &m.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                let ids: Vec<Id> = match decl {
+                    Decl::Class(c) => vec![c.ident.to_id()],
+                    Decl::Fn(f) => vec![f.ident.to_id()],
+                    Decl::Var(var) => find_pat_ids(&var.decls),
+                    _ => continue,
+                };
+
+                names.extend(ids.into_iter().map(|id| (id.0.clone(), id.0)));
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                src: None,
+                specifiers,
+                ..
+            })) => {
+                for s in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(orig),
+                        exported,
+                        ..
+                    }) = s
+                    {
+                        let name = exported.as_ref().map_or(&orig.sym, export_name);
+                        names.insert(name.clone(), orig.sym.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+/// Returns names for the named imports of `m`, which are the local names of
+/// the exports in the dependencies mangled so far.
+fn imported_names(
+    m: &GraphModule,
+    local_names: &[Option<AHashMap<JsWord, JsWord>>],
+) -> AHashMap<JsWord, JsWord> {
+    let mut names = AHashMap::default();
+    let mut taken = FxHashSet::default();
+
+    for item in &m.module.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+            let exports = match m
+                .deps
+                .get(&import.src.value)
+                .and_then(|&dep| local_names[dep].as_ref())
+            {
+                Some(v) => v,
+                None => continue,
+            };
+
+            for s in &import.specifiers {
+                if let ImportSpecifier::Named(s) = s {
+                    let export = s.imported.as_ref().map_or(&s.local.sym, export_name);
+
+                    if let Some(name) = exports.get(export) {
+                        // Two imports cannot have the same name.
+                        if taken.insert(name.clone()) {
+                            names.insert(s.local.sym.clone(), name.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// Propagates constant exports and drops unused exports until nothing
+/// changes. Changed modules are marked as `dirty`.
+fn shake_exports(modules: &mut [GraphModule], dirty: &mut [bool]) {
+    loop {
+        let mut changed = false;
+
+        let constants = modules
+            .iter()
+            .map(|m| collect_constant_exports(&m.module))
+            .collect::<Vec<_>>();
+
+        for (idx, m) in modules.iter_mut().enumerate() {
+            if inline_constant_imports(m, &constants) {
+                changed = true;
+                dirty[idx] = true;
+            }
+        }
+
+        let used = collect_used_exports(modules);
+
+        for (idx, m) in modules.iter_mut().enumerate() {
+            if m.is_entry {
+                continue;
+            }
+
+            if drop_unused_exports(&mut m.module, &used[idx]) {
+                changed = true;
+                dirty[idx] = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct UsedExports {
+    all: bool,
+    names: FxHashSet<JsWord>,
+}
+
+impl UsedExports {
+    fn is_used(&self, name: &JsWord) -> bool {
+        self.all || self.names.contains(name)
+    }
+}
+
+fn export_name(n: &ModuleExportName) -> &JsWord {
+    match n {
+        ModuleExportName::Ident(i) => &i.sym,
+        ModuleExportName::Str(s) => &s.value,
+    }
+}
+
+/// Returns the values of exported constants which are cheap to copy.
+fn collect_constant_exports(m: &Module) -> AHashMap<JsWord, Lit> {
+    let mut local = AHashMap::<Id, Lit>::default();
+    let mut exported = AHashMap::default();
+
+    for item in &m.body {
+        let (var, is_export) = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => (var, true),
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => (var, false),
+            _ => continue,
+        };
+        if var.kind != VarDeclKind::Const {
+            continue;
+        }
+
+        for decl in &var.decls {
+            if let (Pat::Ident(name), Some(Expr::Lit(lit))) = (&decl.name, decl.init.as_deref()) {
+                if !is_cheap_to_copy(lit) {
+                    continue;
+                }
+
+                if is_export {
+                    exported.insert(name.id.sym.clone(), lit.clone());
+                } else {
+                    local.insert(name.id.to_id(), lit.clone());
+                }
+            }
+        }
+    }
+
+    for item in &m.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+            src: None,
+            specifiers,
+            ..
+        })) = item
+        {
+            for s in specifiers {
+                if let ExportSpecifier::Named(ExportNamedSpecifier {
+                    orig: ModuleExportName::Ident(orig),
+                    exported: name,
+                    ..
+                }) = s
+                {
+                    if let Some(lit) = local.get(&orig.to_id()) {
+                        let name = name.as_ref().map_or(&orig.sym, export_name);
+                        exported.insert(name.clone(), lit.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    exported
+}
+
+fn is_cheap_to_copy(lit: &Lit) -> bool {
+    match lit {
+        Lit::Bool(..) | Lit::Null(..) | Lit::Num(..) | Lit::BigInt(..) => true,
+        Lit::Str(s) => s.value.len() <= 3,
+        _ => false,
+    }
+}
+
+/// Replaces usages of imported constants with their values, and removes the
+/// import specifiers which are not used anymore.
+fn inline_constant_imports(m: &mut GraphModule, constants: &[AHashMap<JsWord, Lit>]) -> bool {
+    let mut values = AHashMap::default();
+
+    for item in &m.module.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+            let dep = match m.deps.get(&import.src.value) {
+                Some(&dep) => dep,
+                None => continue,
+            };
+
+            for s in &import.specifiers {
+                if let ImportSpecifier::Named(s) = s {
+                    let name = s.imported.as_ref().map_or(&s.local.sym, export_name);
+
+                    if let Some(lit) = constants[dep].get(name) {
+                        values.insert(s.local.to_id(), lit.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    if values.is_empty() {
+        return false;
+    }
+
+    let mut v = ConstantInliner {
+        values: &values,
+        changed: false,
+    };
+    m.module.visit_mut_with(&mut v);
+
+    v.changed | drop_unused_imports(&mut m.module)
+}
+
+struct ConstantInliner<'a> {
+    values: &'a AHashMap<Id, Lit>,
+    changed: bool,
+}
+
+impl VisitMut for ConstantInliner<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        if let Expr::Ident(i) = e {
+            if let Some(lit) = self.values.get(&i.to_id()) {
+                *e = Expr::Lit(lit.clone());
+                self.changed = true;
+                return;
+            }
+        }
+
+        e.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_prop(&mut self, p: &mut Prop) {
+        if let Prop::Shorthand(i) = p {
+            if let Some(lit) = self.values.get(&i.to_id()) {
+                *p = Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i.clone()),
+                    value: Box::new(Expr::Lit(lit.clone())),
+                });
+                self.changed = true;
+                return;
+            }
+        }
+
+        p.visit_mut_children_with(self);
+    }
+}
+
+/// Returns `true` if an imported binding is referenced, including by
+/// `export { local }`.
+fn is_import_used(data: &ProgramData, local: &Ident) -> bool {
+    data.vars
+        .get(&local.to_id())
+        .map_or(false, |v| v.ref_count > 0)
+}
+
+/// Removes import specifiers which are not referenced. An import declaration
+/// without specifiers is kept as `import 'src'` for its side effects.
+fn drop_unused_imports(m: &mut Module) -> bool {
+    let data = analyze(&*m, None);
+
+    let mut changed = false;
+
+    for item in &mut m.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+            if import.type_only {
+                continue;
+            }
+
+            let len = import.specifiers.len();
+            import.specifiers.retain(|s| {
+                let local = match s {
+                    ImportSpecifier::Named(s) => &s.local,
+                    ImportSpecifier::Default(s) => &s.local,
+                    ImportSpecifier::Namespace(s) => &s.local,
+                };
+                is_import_used(&data, local)
+            });
+            changed |= import.specifiers.len() != len;
+        }
+    }
+
+    changed
+}
+
+fn collect_used_exports(modules: &[GraphModule]) -> Vec<UsedExports> {
+    let mut used = modules
+        .iter()
+        .map(|_| UsedExports::default())
+        .collect::<Vec<_>>();
+
+    for m in modules {
+        let mut v = ExportUsageCollector {
+            data: analyze(&m.module, None),
+            deps: &m.deps,
+            used: &mut used,
+        };
+        m.module.visit_with(&mut v);
+    }
+
+    used
+}
+
+/// Marks exports of dependencies which are used by a module. Named imports
+/// are only used if the usage analysis finds a reference to them.
+struct ExportUsageCollector<'a> {
+    data: ProgramData,
+    deps: &'a AHashMap<JsWord, usize>,
+    used: &'a mut [UsedExports],
+}
+
+impl ExportUsageCollector<'_> {
+    fn dep(&mut self, src: &Str) -> Option<&mut UsedExports> {
+        let idx = *self.deps.get(&src.value)?;
+        Some(&mut self.used[idx])
+    }
+}
+
+impl Visit for ExportUsageCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only {
+            return;
+        }
+
+        let idx = match self.deps.get(&n.src.value) {
+            Some(&idx) => idx,
+            None => return,
+        };
+
+        for s in &n.specifiers {
+            let used = &mut self.used[idx];
+
+            match s {
+                ImportSpecifier::Named(s) => {
+                    if is_import_used(&self.data, &s.local) {
+                        let name = s.imported.as_ref().map_or(&s.local.sym, export_name);
+                        used.names.insert(name.clone());
+                    }
+                }
+                ImportSpecifier::Default(s) => {
+                    if is_import_used(&self.data, &s.local) {
+                        used.names.insert("default".into());
+                    }
+                }
+                ImportSpecifier::Namespace(s) => {
+                    if is_import_used(&self.data, &s.local) {
+                        used.all = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_named_export(&mut self, n: &NamedExport) {
+        let used = match n.src.as_deref().and_then(|src| self.dep(src)) {
+            Some(v) => v,
+            None => return,
+        };
+
+        for s in &n.specifiers {
+            match s {
+                ExportSpecifier::Named(s) => {
+                    used.names.insert(export_name(&s.orig).clone());
+                }
+                ExportSpecifier::Default(..) => {
+                    used.names.insert("default".into());
+                }
+                ExportSpecifier::Namespace(..) => {
+                    used.all = true;
+                }
+            }
+        }
+    }
+
+    fn visit_export_all(&mut self, n: &ExportAll) {
+        if let Some(used) = self.dep(&n.src) {
+            used.all = true;
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        n.visit_children_with(self);
+
+        // `import('./dep')`
+        if let Callee::Import(..) = n.callee {
+            if let Some(ExprOrSpread { expr, .. }) = n.args.first() {
+                if let Expr::Lit(Lit::Str(src)) = &**expr {
+                    if let Some(used) = self.dep(src) {
+                        used.all = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Removes `export` from declarations which are not used, so the compressor
+/// can drop them.
+fn drop_unused_exports(m: &mut Module, used: &UsedExports) -> bool {
+    if used.all {
+        return false;
+    }
+
+    let default: JsWord = "default".into();
+    let mut changed = false;
+
+    m.body = m
+        .body
+        .take()
+        .into_iter()
+        .filter_map(|item| {
+            let item = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    let is_used = match &export.decl {
+                        Decl::Class(c) => used.is_used(&c.ident.sym),
+                        Decl::Fn(f) => used.is_used(&f.ident.sym),
+                        Decl::Var(var) => var.decls.iter().any(|decl| match &decl.name {
+                            Pat::Ident(i) => used.is_used(&i.id.sym),
+                            // Patterns are kept as-is.
+                            _ => true,
+                        }),
+                        _ => true,
+                    };
+
+                    if is_used {
+                        return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)));
+                    }
+
+                    ModuleItem::Stmt(Stmt::Decl(export.decl))
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export))
+                    if !used.is_used(&default) =>
+                {
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                        span: export.span,
+                        expr: export.expr,
+                    }))
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export))
+                    if !used.is_used(&default) =>
+                {
+                    match export.decl {
+                        DefaultDecl::Class(ClassExpr {
+                            ident: Some(ident),
+                            class,
+                        }) => ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl {
+                            ident,
+                            declare: false,
+                            class,
+                        }))),
+                        // Static blocks may have side effects.
+                        DefaultDecl::Class(c) => ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                            span: export.span,
+                            expr: Box::new(Expr::Class(c)),
+                        })),
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident),
+                            function,
+                        }) => ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                            ident,
+                            declare: false,
+                            function,
+                        }))),
+                        DefaultDecl::Fn(..) | DefaultDecl::TsInterfaceDecl(..) => {
+                            changed = true;
+                            return None;
+                        }
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(mut export))
+                    if export.src.is_none() =>
+                {
+                    let len = export.specifiers.len();
+                    export.specifiers.retain(|s| match s {
+                        ExportSpecifier::Named(s) => {
+                            used.is_used(export_name(s.exported.as_ref().unwrap_or(&s.orig)))
+                        }
+                        _ => true,
+                    });
+
+                    if export.specifiers.len() == len {
+                        return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)));
+                    }
+                    changed = true;
+
+                    if export.specifiers.is_empty() {
+                        return None;
+                    }
+
+                    return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)));
+                }
+
+                _ => return Some(item),
+            };
+
+            changed = true;
+            Some(item)
+        })
+        .collect();
+
+    changed
+}
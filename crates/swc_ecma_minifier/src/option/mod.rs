@@ -1,5 +1,8 @@
 #![cfg_attr(not(feature = "extra-serde"), allow(unused))]
 
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
+
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use swc_atoms::JsWord;
 use swc_cached::regex::CachedRegex;
@@ -7,6 +10,8 @@ use swc_common::{collections::AHashMap, Mark};
 use swc_config::merge::Merge;
 use swc_ecma_ast::{EsVersion, Expr};
 
+use crate::trace::OptimizationTrace;
+
 /// Implement default using serde.
 macro_rules! impl_default {
     ($T:ty) => {
@@ -28,6 +33,153 @@ pub struct ExtraOptions {
 
     /// It should be the [Mark] used for `resolver`.
     pub top_level_mark: Mark,
+
+    /// Names chosen by previous runs of the mangler. This run reuses them and
+    /// adds the names it chooses.
+    pub mangle_name_cache: Option<Arc<dyn MangleCache>>,
+
+    /// Records why the compressor applied or skipped optimizations.
+    pub optimization_trace: Option<Arc<OptimizationTrace>>,
+}
+
+/// Stores names chosen by the mangler, so that separately minified files can
+/// agree on them.
+///
+/// This is `nameCache` of terser.
+pub trait MangleCache: Debug + Send + Sync {
+    /// Mangled names of top-level bindings, by their original names.
+    fn vars_cache(&self) -> AHashMap<JsWord, JsWord>;
+
+    /// Mangled names of properties, by their original names.
+    fn props_cache(&self) -> AHashMap<JsWord, JsWord>;
+
+    fn update_vars_cache(&self, new_data: &AHashMap<JsWord, JsWord>);
+
+    fn update_props_cache(&self, new_data: &AHashMap<JsWord, JsWord>);
+}
+
+/// Serializable content of a [MangleCache].
+///
+/// This is (de)serialized in the layout of `nameCache` of terser, i.e.
+/// `{ "vars": { "props": { "$name": "a" } }, "props": { "props": { .. } } }`,
+/// so a cache can be shared with terser.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "TerserNameCache", into = "TerserNameCache")]
+pub struct NameCache {
+    pub vars: BTreeMap<JsWord, JsWord>,
+
+    pub props: BTreeMap<JsWord, JsWord>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TerserNameCache {
+    #[serde(default)]
+    vars: TerserNameMap,
+
+    #[serde(default)]
+    props: TerserNameMap,
+}
+
+/// Terser prefixes original names with `$`.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TerserNameMap {
+    #[serde(default)]
+    props: BTreeMap<String, JsWord>,
+}
+
+impl From<BTreeMap<JsWord, JsWord>> for TerserNameMap {
+    fn from(names: BTreeMap<JsWord, JsWord>) -> Self {
+        Self {
+            props: names
+                .into_iter()
+                .map(|(k, v)| (format!("${}", k), v))
+                .collect(),
+        }
+    }
+}
+
+impl From<TerserNameMap> for BTreeMap<JsWord, JsWord> {
+    fn from(names: TerserNameMap) -> Self {
+        names
+            .props
+            .into_iter()
+            .map(|(k, v)| (k.strip_prefix('$').unwrap_or(&k).into(), v))
+            .collect()
+    }
+}
+
+impl From<TerserNameCache> for NameCache {
+    fn from(cache: TerserNameCache) -> Self {
+        Self {
+            vars: cache.vars.into(),
+            props: cache.props.into(),
+        }
+    }
+}
+
+impl From<NameCache> for TerserNameCache {
+    fn from(cache: NameCache) -> Self {
+        Self {
+            vars: cache.vars.into(),
+            props: cache.props.into(),
+        }
+    }
+}
+
+/// [MangleCache] backed by a [NameCache].
+#[derive(Debug, Default)]
+pub struct SimpleMangleCache {
+    cache: RwLock<NameCache>,
+}
+
+impl SimpleMangleCache {
+    pub fn new(cache: NameCache) -> Self {
+        Self {
+            cache: RwLock::new(cache),
+        }
+    }
+
+    /// Returns the current content, which can be stored and passed to
+    /// [SimpleMangleCache::new] later.
+    pub fn name_cache(&self) -> NameCache {
+        self.cache.read().clone()
+    }
+}
+
+impl MangleCache for SimpleMangleCache {
+    fn vars_cache(&self) -> AHashMap<JsWord, JsWord> {
+        self.cache
+            .read()
+            .vars
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    fn props_cache(&self) -> AHashMap<JsWord, JsWord> {
+        self.cache
+            .read()
+            .props
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    fn update_vars_cache(&self, new_data: &AHashMap<JsWord, JsWord>) {
+        self.cache
+            .write()
+            .vars
+            .extend(new_data.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    fn update_props_cache(&self, new_data: &AHashMap<JsWord, JsWord>) {
+        self.cache
+            .write()
+            .props
+            .extend(new_data.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -114,6 +266,37 @@ impl Default for PureGetterOption {
     }
 }
 
+/// Side-effect-free functions of libraries, like
+/// `{ "modules": ["lodash", "lodash/*"] }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PureLibraries {
+    /// Dotted paths of global functions, like `_.map`.
+    #[serde(default)]
+    pub globals: Vec<String>,
+
+    /// Module specifiers. All functions imported from matching modules are
+    /// pure. A specifier ending with `/*` matches all subpaths of the
+    /// package, like `lodash/map`.
+    #[serde(default)]
+    pub modules: Vec<String>,
+}
+
+impl PureLibraries {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.globals.is_empty() && self.modules.is_empty()
+    }
+
+    pub(crate) fn matches_module(&self, src: &str) -> bool {
+        self.modules.iter().any(|m| match m.strip_suffix("/*") {
+            Some(pkg) => src
+                .strip_prefix(pkg)
+                .map_or(false, |rest| rest.starts_with('/')),
+            None => m == src,
+        })
+    }
+}
+
 /// https://terser.org/docs/api-reference.html#compress-options
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "extra-serde", derive(Serialize, Deserialize))]
@@ -265,6 +448,20 @@ pub struct CompressOptions {
     #[cfg_attr(feature = "extra-serde", serde(alias = "pure_funcs"))]
     pub pure_funcs: Vec<Box<Expr>>,
 
+    /// Drop unused calls to side-effect-free built-ins of the target
+    /// version, like `Math.max` or `Object.freeze({})`. Built-ins which
+    /// convert their arguments are only dropped for primitive literals,
+    /// unless `unsafe` is enabled.
+    ///
+    /// Requires `pristine_globals`. Defaults to false.
+    #[cfg_attr(feature = "extra-serde", serde(default))]
+    pub pure_builtins: bool,
+
+    /// Side-effect-free functions of libraries. Unlike `pure_builtins`, this
+    /// is used even if `pristine_globals` is false.
+    #[cfg_attr(feature = "extra-serde", serde(default))]
+    pub pure_libraries: PureLibraries,
+
     #[cfg_attr(feature = "extra-serde", serde(default = "true_by_default"))]
     #[cfg_attr(feature = "extra-serde", serde(alias = "reduce_funcs"))]
     pub reduce_fns: bool,
@@ -412,6 +609,8 @@ impl Default for CompressOptions {
             props: true,
             pure_getters: Default::default(),
             pure_funcs: Default::default(),
+            pure_builtins: false,
+            pure_libraries: Default::default(),
             reduce_fns: true,
             reduce_vars: false,
             sequences: 3,
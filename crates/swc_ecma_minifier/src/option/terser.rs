@@ -1,5 +1,10 @@
 //! Compatibility for terser config.
 
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use swc_atoms::JsWord;
@@ -9,7 +14,7 @@ use swc_ecma_parser::parse_file_as_expr;
 use swc_ecma_utils::drop_span;
 
 use super::{default_passes, true_by_default, CompressOptions, TopLevelOptions};
-use crate::option::PureGetterOption;
+use crate::option::{PureGetterOption, PureLibraries};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -245,11 +250,52 @@ pub struct TerserCompressorOptions {
 
     #[serde(default)]
     pub pristine_globals: Option<bool>,
+
+    #[serde(default)]
+    pub pure_builtins: Option<bool>,
+
+    /// Paths should be loaded with
+    /// [TerserCompressorOptions::load_pure_libraries] before calling
+    /// [TerserCompressorOptions::into_config].
+    #[serde(default)]
+    pub pure_libraries: Option<TerserPureLibraries>,
+}
+
+/// [PureLibraries] given inline, or as a path to a JSON file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TerserPureLibraries {
+    Inline(PureLibraries),
+    File(PathBuf),
 }
 
 impl_default!(TerserCompressorOptions);
 
 impl TerserCompressorOptions {
+    /// Reads `pure_libraries` given as a path, which is resolved against
+    /// `base`.
+    pub fn load_pure_libraries(&mut self, base: &Path) -> io::Result<()> {
+        if let Some(TerserPureLibraries::File(path)) = &self.pure_libraries {
+            let path = base.join(path);
+            let libs = fs::read_to_string(&path)
+                .and_then(|json| Ok(serde_json::from_str(&json)?))
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!(
+                            "failed to load `pure_libraries` from {}: {}",
+                            path.display(),
+                            err
+                        ),
+                    )
+                })?;
+
+            self.pure_libraries = Some(TerserPureLibraries::Inline(libs));
+        }
+
+        Ok(())
+    }
+
     pub fn into_config(self, cm: Lrc<SourceMap>) -> CompressOptions {
         CompressOptions {
             arguments: self.arguments,
@@ -404,6 +450,18 @@ impl TerserCompressorOptions {
                     })
                 })
                 .collect(),
+            pure_builtins: self.pure_builtins.unwrap_or(false),
+            pure_libraries: match self.pure_libraries {
+                Some(TerserPureLibraries::Inline(v)) => v,
+                Some(TerserPureLibraries::File(path)) => {
+                    tracing::warn!(
+                        "`pure_libraries` at {} is ignored because it's not loaded",
+                        path.display()
+                    );
+                    Default::default()
+                }
+                None => Default::default(),
+            },
         }
     }
 }
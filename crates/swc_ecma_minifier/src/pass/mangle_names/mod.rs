@@ -1,12 +1,17 @@
+use std::{borrow::Cow, sync::Arc};
+
 use rustc_hash::{FxHashMap, FxHashSet};
 use swc_atoms::JsWord;
-use swc_common::{chain, Mark};
+use swc_common::{chain, collections::AHashMap, Mark, SyntaxContext};
 use swc_ecma_ast::{Module, *};
 use swc_ecma_transforms_base::rename::{renamer, Renamer};
 use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
 
 pub(crate) use self::preserver::idents_to_preserve;
-use crate::{option::MangleOptions, util::base54::Base54Chars};
+use crate::{
+    option::{MangleCache, MangleOptions},
+    util::base54::Base54Chars,
+};
 
 mod preserver;
 mod private_name;
@@ -16,6 +21,8 @@ pub(crate) fn name_mangler(
     preserved: FxHashSet<Id>,
     chars: Base54Chars,
     top_level_mark: Mark,
+    unresolved_mark: Mark,
+    cache: Option<Arc<dyn MangleCache>>,
 ) -> impl VisitMut {
     chain!(
         LabelMangler {
@@ -31,14 +38,27 @@ pub(crate) fn name_mangler(
                 top_level_mark,
                 ignore_eval: options.eval,
             },
-            ManglingRenamer { chars, preserved }
+            ManglingRenamer {
+                chars,
+                top_level: options.top_level.unwrap_or_default(),
+                preserved,
+                cache,
+                top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+                is_module: false,
+            }
         )
     )
 }
 
 struct ManglingRenamer {
     chars: Base54Chars,
+    top_level: bool,
     preserved: FxHashSet<Id>,
+    cache: Option<Arc<dyn MangleCache>>,
+    top_level_ctxt: SyntaxContext,
+    unresolved_ctxt: SyntaxContext,
+    is_module: bool,
 }
 
 impl Renamer for ManglingRenamer {
@@ -46,6 +66,7 @@ impl Renamer for ManglingRenamer {
     const RESET_N: bool = false;
 
     fn preserved_ids_for_module(&mut self, _: &Module) -> FxHashSet<Id> {
+        self.is_module = true;
         self.preserved.clone()
     }
 
@@ -56,6 +77,53 @@ impl Renamer for ManglingRenamer {
     fn new_name_for(&self, _: &Id, n: &mut usize) -> JsWord {
         self.chars.encode(n, true)
     }
+
+    fn get_cached(&self) -> Option<Cow<AHashMap<Id, JsWord>>> {
+        // Cached names are names of the top level, which are kept as-is unless
+        // `top_level` is enabled.
+        if !self.top_level {
+            return None;
+        }
+
+        let cache = self.cache.as_ref()?.vars_cache();
+
+        // Globals declared by other scripts are renamed too. Top-level bindings
+        // of modules are not globals, so a free variable of a module may be a
+        // built-in with the same name.
+        Some(Cow::Owned(
+            cache
+                .into_iter()
+                .flat_map(|(orig, mangled)| {
+                    let unresolved = if self.is_module {
+                        None
+                    } else {
+                        Some(((orig.clone(), self.unresolved_ctxt), mangled.clone()))
+                    };
+
+                    unresolved
+                        .into_iter()
+                        .chain(Some(((orig, self.top_level_ctxt), mangled)))
+                })
+                .filter(|(id, _)| !self.preserved.contains(id))
+                .collect(),
+        ))
+    }
+
+    fn store_cache(&mut self, update: &AHashMap<Id, JsWord>) {
+        if !self.top_level {
+            return;
+        }
+
+        if let Some(cache) = &self.cache {
+            let update = update
+                .iter()
+                .filter(|(id, _)| id.1 == self.top_level_ctxt)
+                .map(|(id, mangled)| (id.0.clone(), mangled.clone()))
+                .collect();
+
+            cache.update_vars_cache(&update);
+        }
+    }
 }
 
 struct LabelMangler {
@@ -10,7 +10,7 @@ use swc_ecma_ast::{
 use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
 
 use crate::{
-    option::ManglePropertiesOptions,
+    option::{MangleCache, ManglePropertiesOptions},
     program_data::{analyze, ProgramData},
     util::base54::Base54Chars,
 };
@@ -40,6 +40,8 @@ struct ManglePropertiesState {
 
     // Cache of already mangled names
     cache: AHashMap<JsWord, JsWord>,
+    // Values of `cache`
+    mangled: AHashSet<JsWord>,
 
     // Numbers to pass to base54()
     n: usize,
@@ -85,9 +87,14 @@ impl ManglePropertiesState {
             if let Some(cached) = self.cache.get(name) {
                 Some(cached.clone())
             } else {
-                let mangled_name = self.chars.encode(&mut self.n, true);
+                let mut mangled_name = self.chars.encode(&mut self.n, true);
+                // Names from the name cache may be taken.
+                while self.mangled.contains(&mangled_name) {
+                    mangled_name = self.chars.encode(&mut self.n, true);
+                }
 
                 self.cache.insert(name.clone(), mangled_name.clone());
+                self.mangled.insert(mangled_name.clone());
                 Some(mangled_name)
             }
         } else {
@@ -100,13 +107,19 @@ pub(crate) fn mangle_properties(
     m: &mut Program,
     options: ManglePropertiesOptions,
     chars: Base54Chars,
+    name_cache: Option<&dyn MangleCache>,
 ) {
+    let cache = name_cache
+        .map(|name_cache| name_cache.props_cache())
+        .unwrap_or_default();
+
     let mut state = ManglePropertiesState {
         options,
         chars,
         names_to_mangle: Default::default(),
         unmangleable: Default::default(),
-        cache: Default::default(),
+        mangled: cache.values().cloned().collect(),
+        cache,
         n: 0,
     };
 
@@ -117,6 +130,10 @@ pub(crate) fn mangle_properties(
     });
 
     m.visit_mut_with(&mut Mangler { state: &mut state });
+
+    if let Some(name_cache) = name_cache {
+        name_cache.update_props_cache(&state.cache);
+    }
 }
 
 // Step 1 -- collect candidates to mangle
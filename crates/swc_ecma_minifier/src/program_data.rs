@@ -11,7 +11,7 @@ use swc_ecma_ast::*;
 use swc_ecma_usage_analyzer::{
     alias::{Access, AccessKind},
     analyzer::{
-        analyze_with_storage,
+        analyze_with_pure_builtins, analyze_with_storage,
         storage::{ScopeDataLike, Storage, VarDataLike},
         CalleeKind, Ctx, ScopeKind, UsageAnalyzer,
     },
@@ -19,6 +19,8 @@ use swc_ecma_usage_analyzer::{
 };
 use swc_ecma_visit::VisitWith;
 
+use crate::option::CompressOptions;
+
 pub(crate) fn analyze<N>(n: &N, marks: Option<Marks>) -> ProgramData
 where
     N: VisitWith<UsageAnalyzer<ProgramData>>,
@@ -26,6 +28,17 @@ where
     analyze_with_storage::<ProgramData, _>(n, marks)
 }
 
+/// Same as [analyze], but built-ins are respected if `options` allow it, like
+/// [crate::metadata].
+pub(crate) fn analyze_with_options<N>(n: &N, marks: Marks, options: &CompressOptions) -> ProgramData
+where
+    N: VisitWith<UsageAnalyzer<ProgramData>>,
+{
+    let pure_builtins = (options.pure_builtins && options.pristine_globals).then_some(options.ecma);
+
+    analyze_with_pure_builtins::<ProgramData, _>(n, Some(marks), pure_builtins)
+}
+
 /// Analyzed info of a whole program we are working on.
 #[derive(Debug, Default)]
 pub(crate) struct ProgramData {
@@ -0,0 +1,293 @@
+//! Size reports and budgets of minified code.
+//!
+//! Measuring compressed sizes requires the `size-report` feature.
+
+use std::io;
+#[cfg(feature = "size-report")]
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+use swc_common::{BytePos, LineCol, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_utils::find_pat_ids;
+
+/// Sizes of the output, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    pub raw: usize,
+    pub gzip: usize,
+    pub brotli: usize,
+
+    /// Raw sizes of top-level declarations, largest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub declarations: Vec<DeclarationSize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationSize {
+    /// Names declared by the declaration, or `default`.
+    pub name: String,
+    pub size: usize,
+}
+
+/// Upper limits of [SizeReport].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SizeBudget {
+    #[serde(default)]
+    pub raw: Option<usize>,
+
+    #[serde(default)]
+    pub gzip: Option<usize>,
+
+    #[serde(default)]
+    pub brotli: Option<usize>,
+}
+
+/// A segment of the output source map.
+#[derive(Debug, Clone, Copy)]
+pub struct Mapping {
+    pub dst: LineCol,
+    pub src: LineCol,
+}
+
+/// Range of a top-level declaration in the input file.
+#[derive(Debug, Clone)]
+pub struct TopLevelDecl {
+    pub name: String,
+    pub start: LineCol,
+    pub end: LineCol,
+}
+
+impl SizeReport {
+    #[cfg(feature = "size-report")]
+    pub fn new(code: &str) -> io::Result<Self> {
+        let gzip = {
+            let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+            e.write_all(code.as_bytes())?;
+            e.finish()?.len()
+        };
+
+        let brotli = {
+            let mut buf = vec![];
+            {
+                let mut e = brotli::CompressorWriter::new(&mut buf, 4096, 11, 22);
+                e.write_all(code.as_bytes())?;
+            }
+            buf.len()
+        };
+
+        Ok(SizeReport {
+            raw: code.len(),
+            gzip,
+            brotli,
+            declarations: Default::default(),
+        })
+    }
+
+    /// Always fails, as compressed sizes cannot be measured without the
+    /// `size-report` feature.
+    #[cfg(not(feature = "size-report"))]
+    pub fn new(_code: &str) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "`reportSize` and `sizeBudget` require the `size-report` feature of swc_ecma_minifier",
+        ))
+    }
+
+    /// Attributes each segment of `mappings` to the declaration which contains
+    /// its source position.
+    ///
+    /// `decls` should be sorted by position, like the result of
+    /// [top_level_decls], and source positions of `mappings` should point to
+    /// the same file.
+    pub fn with_declarations(
+        mut self,
+        code: &str,
+        mut mappings: Vec<Mapping>,
+        decls: &[TopLevelDecl],
+    ) -> Self {
+        mappings.sort_by_key(|m| (m.dst.line, m.dst.col));
+
+        let mut sizes = vec![0; decls.len()];
+        let mut cursor = Cursor::new(code);
+
+        let offsets = mappings
+            .iter()
+            .map(|m| cursor.seek(m.dst))
+            .chain(Some(code.len()))
+            .collect::<Vec<_>>();
+
+        for (m, range) in mappings.iter().zip(offsets.windows(2)) {
+            let pos = (m.src.line, m.src.col);
+            let idx = decls.partition_point(|d| (d.start.line, d.start.col) <= pos);
+            if idx == 0 {
+                continue;
+            }
+
+            let decl = &decls[idx - 1];
+            if pos < (decl.end.line, decl.end.col) {
+                sizes[idx - 1] += range[1] - range[0];
+            }
+        }
+
+        self.declarations = decls
+            .iter()
+            .zip(sizes)
+            .filter(|(_, size)| *size != 0)
+            .map(|(decl, size)| DeclarationSize {
+                name: decl.name.clone(),
+                size,
+            })
+            .collect();
+        self.declarations
+            .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        self
+    }
+}
+
+impl SizeBudget {
+    /// Returns messages for sizes exceeding the budget.
+    pub fn check(&self, report: &SizeReport) -> Vec<String> {
+        [
+            ("raw", self.raw, report.raw),
+            ("gzip", self.gzip, report.gzip),
+            ("brotli", self.brotli, report.brotli),
+        ]
+        .into_iter()
+        .filter_map(|(kind, budget, size)| {
+            let budget = budget?;
+            if size <= budget {
+                return None;
+            }
+
+            Some(format!(
+                "{} size of the output ({} bytes) exceeds the budget ({} bytes)",
+                kind, size, budget
+            ))
+        })
+        .collect()
+    }
+}
+
+/// Converts [LineCol] of the output to byte offsets. Positions should be
+/// sorted.
+struct Cursor<'a> {
+    code: &'a str,
+    pos: LineCol,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(code: &'a str) -> Self {
+        Cursor {
+            code,
+            pos: LineCol { line: 0, col: 0 },
+            offset: 0,
+        }
+    }
+
+    fn seek(&mut self, to: LineCol) -> usize {
+        for c in self.code[self.offset..].chars() {
+            if (self.pos.line, self.pos.col) >= (to.line, to.col) {
+                break;
+            }
+
+            if c == '\n' {
+                if self.pos.line == to.line {
+                    break;
+                }
+                self.pos.line += 1;
+                self.pos.col = 0;
+            } else if self.pos.line == to.line {
+                self.pos.col += c.len_utf16() as u32;
+            }
+
+            self.offset += c.len_utf8();
+        }
+
+        self.offset
+    }
+}
+
+/// Returns the ranges of top-level declarations of `program`, sorted by
+/// position.
+pub fn top_level_decls(program: &Program, cm: &SourceMap) -> Vec<TopLevelDecl> {
+    let mut decls = vec![];
+
+    match program {
+        Program::Module(m) => {
+            for item in &m.body {
+                match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(e)) => {
+                        add_decl(&mut decls, cm, &e.decl, e.span)
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                        span,
+                        ..
+                    }))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span,
+                        ..
+                    })) => add(&mut decls, cm, "default".into(), *span),
+                    ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                        add_decl(&mut decls, cm, decl, decl.span())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Program::Script(s) => {
+            for stmt in &s.body {
+                if let Stmt::Decl(decl) = stmt {
+                    add_decl(&mut decls, cm, decl, decl.span());
+                }
+            }
+        }
+    }
+
+    decls.sort_by_key(|d| (d.start.line, d.start.col));
+    decls
+}
+
+fn add_decl(decls: &mut Vec<TopLevelDecl>, cm: &SourceMap, decl: &Decl, span: Span) {
+    let name = match decl {
+        Decl::Class(c) => c.ident.sym.to_string(),
+        Decl::Fn(f) => f.ident.sym.to_string(),
+        Decl::Var(v) => {
+            let ids: Vec<Id> = find_pat_ids(&v.decls);
+            ids.iter().map(|id| &*id.0).collect::<Vec<_>>().join(", ")
+        }
+        _ => return,
+    };
+
+    add(decls, cm, name, span)
+}
+
+fn add(decls: &mut Vec<TopLevelDecl>, cm: &SourceMap, name: String, span: Span) {
+    if span.is_dummy() {
+        return;
+    }
+
+    if let (Some(start), Some(end)) = (line_col(cm, span.lo), line_col(cm, span.hi)) {
+        decls.push(TopLevelDecl { name, start, end });
+    }
+}
+
+/// Same as the positions used by source maps.
+fn line_col(cm: &SourceMap, pos: BytePos) -> Option<LineCol> {
+    let loc = cm.lookup_char_pos(pos);
+    let line_start = *loc.file.lines.get(loc.line.checked_sub(1)?)?;
+
+    let start = (line_start.0 - loc.file.start_pos.0) as usize;
+    let end = (pos.0 - loc.file.start_pos.0) as usize;
+    let col = loc.file.src.get(start..end)?.encode_utf16().count();
+
+    Some(LineCol {
+        line: (loc.line - 1) as _,
+        col: col as _,
+    })
+}
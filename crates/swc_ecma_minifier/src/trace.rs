@@ -0,0 +1,197 @@
+//! Optimization trace, which explains why the compressor kept code.
+//!
+//! Pass an [OptimizationTrace] to
+//! [ExtraOptions::optimization_trace](crate::option::ExtraOptions) to record
+//! decisions of the compressor.
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+use swc_atoms::JsWord;
+use swc_common::{errors::Handler, SourceMap, Span};
+
+/// Decisions of the compressor, in the order they are made.
+#[derive(Debug, Default)]
+pub struct OptimizationTrace {
+    /// The final outcome of each transformation. [None] means it's applied.
+    events: Mutex<IndexMap<(Span, Transform, Option<JsWord>), Option<BlockReason>>>,
+}
+
+/// A transformation applied to or blocked for a node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OptimizationEvent {
+    pub span: Span,
+    pub transform: Transform,
+    /// Name of the binding the transformation is applied to.
+    pub target: Option<JsWord>,
+    /// [None] if the transformation is applied.
+    pub blocked_by: Option<BlockReason>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    /// Inlining a variable into its usages.
+    InlineVar,
+    /// Inlining a function or class declaration into its usages.
+    InlineFn,
+    /// Inlining an immediately invoked function expression.
+    InlineIife,
+    /// Dropping an unused declaration.
+    DropUnused,
+    /// Merging an assignment into the next expression which uses it.
+    CollapseVars,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockReason {
+    SideEffects,
+    EvalScope,
+    Reassigned,
+    PropertyMutation,
+    Used,
+    UsedMultipleTimes,
+    UsedBeforeDecl,
+    UsedAsArg,
+    UsedInLoop,
+    CrossesFnBoundary,
+    Recursive,
+    TopLevel,
+    Exported,
+    NoInline,
+    KeepNames,
+    InlinePrevented,
+    DependsOnReassigned,
+    AsyncOrGenerator,
+    ComplexParams,
+    SpreadArgs,
+    ComplexBody,
+}
+
+impl BlockReason {
+    pub fn description(self) -> &'static str {
+        match self {
+            BlockReason::SideEffects => "has side effects",
+            BlockReason::EvalScope => "used in eval scope",
+            BlockReason::Reassigned => "reassigned",
+            BlockReason::PropertyMutation => "has its properties mutated",
+            BlockReason::Used => "used",
+            BlockReason::UsedMultipleTimes => "used more than once",
+            BlockReason::UsedBeforeDecl => "used before its declaration",
+            BlockReason::UsedAsArg => "passed as an argument",
+            BlockReason::UsedInLoop => "used in a loop",
+            BlockReason::CrossesFnBoundary => "used across function boundaries",
+            BlockReason::Recursive => "used recursively",
+            BlockReason::TopLevel => "declared in the top level",
+            BlockReason::Exported => "exported",
+            BlockReason::NoInline => "marked as `#__NOINLINE__`",
+            BlockReason::KeepNames => "named and names are kept",
+            BlockReason::InlinePrevented => "used in a way which prevents inlining",
+            BlockReason::DependsOnReassigned => "depends on reassigned variables",
+            BlockReason::AsyncOrGenerator => "async or a generator",
+            BlockReason::ComplexParams => "has complex parameters",
+            BlockReason::SpreadArgs => "called with spread arguments",
+            BlockReason::ComplexBody => "has a body which cannot be inlined",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonEvent<'a> {
+    transform: Transform,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<&'a str>,
+    applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<BlockReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+}
+
+impl OptimizationTrace {
+    pub(crate) fn record(&self, event: OptimizationEvent) {
+        let mut events = self.events.lock();
+
+        // The same decision is made again on each pass, and a transformation
+        // blocked on a pass may be applied on a later one.
+        let blocked_by = events
+            .entry((event.span, event.transform, event.target))
+            .or_insert(event.blocked_by);
+        if blocked_by.is_some() {
+            *blocked_by = event.blocked_by;
+        }
+    }
+
+    pub fn events(&self) -> Vec<OptimizationEvent> {
+        self.events
+            .lock()
+            .iter()
+            .map(
+                |((span, transform, target), blocked_by)| OptimizationEvent {
+                    span: *span,
+                    transform: *transform,
+                    target: target.clone(),
+                    blocked_by: *blocked_by,
+                },
+            )
+            .collect()
+    }
+
+    /// Returns the events as a JSON array, with spans resolved using `cm`.
+    pub fn to_json(&self, cm: &SourceMap) -> serde_json::Value {
+        let events = self.events();
+
+        let events = events
+            .iter()
+            .map(|event| {
+                let loc = if event.span.is_dummy() {
+                    None
+                } else {
+                    Some(cm.lookup_char_pos(event.span.lo))
+                };
+
+                JsonEvent {
+                    transform: event.transform,
+                    target: event.target.as_deref(),
+                    applied: event.blocked_by.is_none(),
+                    reason: event.blocked_by,
+                    description: event.blocked_by.map(BlockReason::description),
+                    file: loc.as_ref().map(|loc| loc.file.name.to_string()),
+                    line: loc.as_ref().map(|loc| loc.line),
+                    column: loc.as_ref().map(|loc| loc.col_display + 1),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_value(events).expect("failed to serialize optimization trace")
+    }
+
+    /// Reports the events as notes.
+    pub fn emit_diagnostics(&self, handler: &Handler) {
+        for event in self.events() {
+            let subject = match &event.target {
+                Some(target) => format!("`{}`", target),
+                None => "this".into(),
+            };
+
+            let msg = match event.blocked_by {
+                Some(reason) => format!(
+                    "{:?}: {} is kept because it is {}",
+                    event.transform,
+                    subject,
+                    reason.description()
+                ),
+                None => format!("{:?}: {} is optimized", event.transform, subject),
+            };
+
+            handler.span_note_diag(event.span, &msg).emit();
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Helpers for tests which call the minifier directly.
+
+#![allow(dead_code)]
+
+use swc_common::{errors::Handler, sync::Lrc, FileName, SourceMap};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{
+    text_writer::{omit_trailing_semi, JsWriter},
+    Emitter,
+};
+use swc_ecma_minifier::option::ExtraOptions;
+use swc_ecma_parser::{parse_file_as_module, parse_file_as_script};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_visit::VisitMutWith;
+
+/// Parses `src` as a module or a script, and applies `resolver` with the
+/// marks of `extra`.
+pub fn parse(
+    cm: &Lrc<SourceMap>,
+    handler: &Handler,
+    name: &str,
+    src: &str,
+    is_module: bool,
+    extra: &ExtraOptions,
+) -> Result<Program, ()> {
+    let fm = cm.new_source_file(FileName::Real(name.into()), src.into());
+
+    let program = if is_module {
+        parse_file_as_module(
+            &fm,
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .map(Program::Module)
+    } else {
+        parse_file_as_script(
+            &fm,
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .map(Program::Script)
+    };
+
+    let mut program = program.map_err(|err| {
+        err.into_diagnostic(handler).emit();
+    })?;
+
+    program.visit_mut_with(&mut resolver(
+        extra.unresolved_mark,
+        extra.top_level_mark,
+        false,
+    ));
+
+    Ok(program)
+}
+
+/// Prints `program` without whitespace.
+pub fn print_minified(cm: Lrc<SourceMap>, program: &Program) -> String {
+    let mut buf = vec![];
+
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config::default().with_minify(true),
+            cm: cm.clone(),
+            comments: None,
+            wr: omit_trailing_semi(JsWriter::new(cm, "\n", &mut buf, None)),
+        };
+
+        emitter.emit_program(program).unwrap();
+    }
+
+    String::from_utf8(buf).unwrap()
+}
@@ -230,6 +230,8 @@ fn run(
             &ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module();
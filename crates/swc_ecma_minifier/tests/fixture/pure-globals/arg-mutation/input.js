@@ -0,0 +1,15 @@
+export function wrapped() {
+    const o = { a: 1 };
+    Object(o).a = 2;
+    return o.a;
+}
+export function constructed() {
+    const o = { a: 1 };
+    Array(o)[0].a = 2;
+    return o.a;
+}
+export function checked() {
+    const o = { a: 1 };
+    Array.isArray(o);
+    return o.a;
+}
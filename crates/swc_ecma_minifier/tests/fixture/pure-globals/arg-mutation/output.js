@@ -0,0 +1,15 @@
+export function wrapped() {
+    const o = {
+        a: 1
+    };
+    return Object(o).a = 2, o.a;
+}
+export function constructed() {
+    const o = {
+        a: 1
+    };
+    return Array(o)[0].a = 2, o.a;
+}
+export function checked() {
+    return 1;
+}
@@ -0,0 +1,28 @@
+Math.max(1, 2);
+Math.trunc(1.5);
+Array.isArray([]);
+Object.freeze({ a: 1 });
+Object.freeze(shared);
+new Set();
+new Set(items);
+new Map();
+Symbol();
+"abc".padStart(5);
+"abc".padStart(width);
+parseInt("10", 10);
+parseInt(value, 10);
+Object.keys({ a: 1, b: 2 });
+Object.values({ get a() { console.log("getter ran"); return 1 } });
+Object.entries({ get b() { sideEffect() } });
+Object.values({ ...source });
+Number.isNaN(sideEffect());
+const unused = Math.floor(Math.random() * 10);
+const frozen = Object.freeze({ b: 2 });
+const set = new WeakSet();
+export const used = Math.sign(input);
+export function local(x) {
+    const s = new Set();
+    const o = Object.freeze({ c: 3 });
+    const n = Number.isInteger(x);
+    return Symbol();
+}
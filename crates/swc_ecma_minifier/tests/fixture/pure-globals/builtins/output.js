@@ -0,0 +1,15 @@
+Object.freeze(shared), items, "abc".padStart(width), parseInt(value, 10), Object.values({
+    get a () {
+        return console.log("getter ran"), 1;
+    }
+}), Object.entries({
+    get b () {
+        sideEffect();
+    }
+}), Object.values({
+    ...source
+}), sideEffect();
+export const used = Math.sign(input);
+export function local(x) {
+    return Symbol();
+}
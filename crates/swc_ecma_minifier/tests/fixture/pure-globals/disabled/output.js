@@ -0,0 +1,3 @@
+parseInt("10", 10), "abc".padStart(5), Object.freeze({
+    a: 1
+});
@@ -0,0 +1,12 @@
+import { map } from "lodash";
+import * as lodash from "lodash";
+import chunk from "lodash-es/chunk";
+import { track } from "analytics";
+map([1, 2], (x) => x * 2);
+lodash.filter([1], Boolean);
+chunk([1, 2, 3], 2);
+_.noop();
+_.identity(1);
+track("event");
+const unused = map([3], String);
+export const used = map([4], String);
@@ -0,0 +1,8 @@
+import { map } from "lodash";
+import * as lodash from "lodash";
+import chunk from "lodash-es/chunk";
+import { track } from "analytics";
+_.identity(1), track("event");
+export const used = map([
+    4
+], String);
@@ -0,0 +1,5 @@
+"abc".trim();
+"abc".padStart(5);
+"abc".at(0);
+Number.isNaN(1);
+Object.freeze({ a: 1 });
@@ -0,0 +1 @@
+"abc".padStart(5), "abc".at(0), Number.isNaN(1);
@@ -3,8 +3,11 @@
 use std::{
     path::{Path, PathBuf},
     process::{Command, Stdio},
+    sync::Arc,
 };
 
+use swc_atoms::JsWord;
+use swc_cached::regex::CachedRegex;
 use swc_common::{errors::Handler, sync::Lrc, FileName, Mark, SourceFile, SourceMap};
 use swc_ecma_ast::*;
 use swc_ecma_codegen::{
@@ -13,7 +16,10 @@ use swc_ecma_codegen::{
 };
 use swc_ecma_minifier::{
     optimize,
-    option::{ExtraOptions, MangleOptions, ManglePropertiesOptions, MinifyOptions},
+    option::{
+        ExtraOptions, MangleCache, MangleOptions, ManglePropertiesOptions, MinifyOptions,
+        NameCache, SimpleMangleCache,
+    },
 };
 use swc_ecma_parser::parse_file_as_module;
 use swc_ecma_transforms_base::{fixer::paren_remover, resolver};
@@ -22,6 +28,11 @@ use swc_ecma_visit::VisitMutWith;
 use testing::{assert_eq, NormalizedOutput};
 use tracing::warn;
 
+use self::common::print_minified;
+
+#[path = "common/mod.rs"]
+mod common;
+
 fn print(cm: Lrc<SourceMap>, m: &Module, minify: bool) -> String {
     let mut buf = vec![];
 
@@ -104,6 +115,8 @@ fn snapshot_compress_fixture(input: PathBuf) {
             &ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module();
@@ -167,6 +180,8 @@ fn fixture(input: PathBuf) {
             &ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module();
@@ -204,6 +219,8 @@ fn assert_mangled(src: &str, expected: &str, opts: MangleOptions) {
             &ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         )
         .expect_module();
@@ -358,3 +375,145 @@ class a {
         },
     )
 }
+
+/// Mangles `src` as a separate script which shares `cache` with other
+/// scripts.
+fn mangle_with_name_cache(src: &str, opts: MangleOptions, cache: Arc<dyn MangleCache>) -> String {
+    testing::run_test2(false, |cm, handler| {
+        let extra = ExtraOptions {
+            unresolved_mark: Mark::new(),
+            top_level_mark: Mark::new(),
+            mangle_name_cache: Some(cache),
+            optimization_trace: None,
+        };
+
+        let program = common::parse(&cm, &handler, "input.js", src, false, &extra)?;
+
+        let program = optimize(
+            program,
+            cm.clone(),
+            None,
+            None,
+            &MinifyOptions {
+                mangle: Some(opts),
+                compress: None,
+                ..Default::default()
+            },
+            &extra,
+        );
+
+        Ok(print_minified(cm, &program))
+    })
+    .unwrap()
+}
+
+#[test]
+fn name_cache() {
+    let opts = MangleOptions {
+        top_level: Some(true),
+        props: Some(ManglePropertiesOptions {
+            regex: Some(CachedRegex::new("^_").unwrap()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let cache = Arc::new(SimpleMangleCache::default());
+
+    let first = mangle_with_name_cache(
+        "var config = { _endpoint: 1, _retries: 2 };
+console.log(config._endpoint);",
+        opts.clone(),
+        cache.clone(),
+    );
+    let first_cache = cache.name_cache();
+
+    // `config` is a global declared by the first file.
+    let second = mangle_with_name_cache(
+        "var local = { _retries: 3, _timeout: 4 };
+console.log(config._retries, local._timeout);",
+        opts,
+        cache.clone(),
+    );
+    let second_cache = cache.name_cache();
+
+    let config = &first_cache.vars[&JsWord::from("config")];
+    let retries = &first_cache.props[&JsWord::from("_retries")];
+    assert_eq!(
+        first,
+        format!(
+            "var {c}={{{e}:1,{r}:2}};console.log({c}.{e});",
+            c = config,
+            e = first_cache.props[&JsWord::from("_endpoint")],
+            r = retries,
+        )
+    );
+    assert_eq!(
+        second,
+        format!(
+            "var {l}={{{r}:3,{t}:4}};console.log({c}.{r},{l}.{t});",
+            c = config,
+            l = second_cache.vars[&JsWord::from("local")],
+            r = retries,
+            t = second_cache.props[&JsWord::from("_timeout")],
+        )
+    );
+
+    // Names chosen by the second file do not collide with the cached ones.
+    assert_ne!(second_cache.vars[&JsWord::from("local")], *config);
+    assert!(!first_cache
+        .props
+        .values()
+        .any(|v| *v == second_cache.props[&JsWord::from("_timeout")]));
+
+    let mut expected = first_cache;
+    expected.vars.insert(
+        "local".into(),
+        second_cache.vars[&JsWord::from("local")].clone(),
+    );
+    expected.props.insert(
+        "_timeout".into(),
+        second_cache.props[&JsWord::from("_timeout")].clone(),
+    );
+    assert_eq!(second_cache, expected);
+}
+
+#[test]
+fn name_cache_of_terser() {
+    let json =
+        r#"{"vars":{"props":{"$config":"a","$local":"b"}},"props":{"props":{"$_endpoint":"c"}}}"#;
+
+    let cache: NameCache = serde_json::from_str(json).unwrap();
+
+    assert_eq!(cache.vars[&JsWord::from("config")], *"a");
+    assert_eq!(cache.vars[&JsWord::from("local")], *"b");
+    assert_eq!(cache.props[&JsWord::from("_endpoint")], *"c");
+    assert_eq!(serde_json::to_string(&cache).unwrap(), json);
+}
+
+#[test]
+fn name_cache_without_top_level() {
+    let mut name_cache = NameCache::default();
+    name_cache.vars.insert("config".into(), "a".into());
+    name_cache.vars.insert("helper".into(), "b".into());
+    let cache = Arc::new(SimpleMangleCache::new(name_cache.clone()));
+
+    // Globals of scripts are referenced by other code, so they are not renamed
+    // from the cache if `top_level` is disabled.
+    let output = mangle_with_name_cache(
+        "var config = 1;
+function run(value) {
+    return helper(config, value);
+}",
+        MangleOptions {
+            top_level: Some(false),
+            ..Default::default()
+        },
+        cache.clone(),
+    );
+
+    assert_eq!(
+        output,
+        "var config=1;function run(n){return helper(config,n)}"
+    );
+    assert_eq!(cache.name_cache(), name_cache);
+}
@@ -0,0 +1,234 @@
+#![deny(warnings)]
+
+use swc_common::{collections::AHashMap, Mark};
+use swc_ecma_ast::Program;
+use swc_ecma_minifier::{
+    module_graph::{optimize_module_graph, GraphModule},
+    option::{CompressOptions, ExtraOptions, MangleOptions, MinifyOptions},
+};
+use testing::assert_eq;
+
+use self::common::{parse, print_minified};
+
+#[path = "common/mod.rs"]
+mod common;
+
+/// Minifies `modules`, which are `(name, source, deps, is_entry)`.
+fn minify_graph(modules: &[(&str, &str, &[(&str, usize)], bool)], mangle: bool) -> Vec<String> {
+    testing::run_test2(false, |cm, handler| {
+        let extra = ExtraOptions {
+            unresolved_mark: Mark::new(),
+            top_level_mark: Mark::new(),
+            mangle_name_cache: None,
+            optimization_trace: None,
+        };
+
+        let mut graph = vec![];
+        for (name, src, deps, is_entry) in modules {
+            graph.push(GraphModule {
+                module: parse(&cm, &handler, name, src, true, &extra)?.expect_module(),
+                deps: deps
+                    .iter()
+                    .map(|(src, idx)| ((*src).into(), *idx))
+                    .collect::<AHashMap<_, _>>(),
+                is_entry: *is_entry,
+            });
+        }
+
+        let output = optimize_module_graph(
+            graph,
+            cm.clone(),
+            None,
+            &MinifyOptions {
+                compress: Some(CompressOptions::default()),
+                mangle: if mangle {
+                    Some(MangleOptions::default())
+                } else {
+                    None
+                },
+                ..Default::default()
+            },
+            &extra,
+        );
+
+        Ok(output
+            .into_iter()
+            .map(|m| print_minified(cm.clone(), &Program::Module(m)))
+            .collect())
+    })
+    .unwrap()
+}
+
+#[test]
+fn constant_exports() {
+    let output = minify_graph(
+        &[
+            (
+                "main.js",
+                "import { DEBUG, log } from './config';
+if (DEBUG) log('debug');
+export function main() { log('main') }",
+                &[("./config", 1)],
+                true,
+            ),
+            (
+                "config.js",
+                "export const DEBUG = false;
+export const VERBOSE = true;
+export function log(msg) { console.log(msg) }
+export function trace(msg) { console.trace(msg) }",
+                &[],
+                false,
+            ),
+        ],
+        false,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "import{log}from\"./config\";export function main(){log(\"main\")}",
+            "export function log(msg){console.log(msg)}",
+        ]
+    );
+}
+
+#[test]
+fn dead_import_drops_export() {
+    let output = minify_graph(
+        &[
+            (
+                "main.js",
+                "import { DEBUG } from './config';
+import { debug } from './debug';
+if (DEBUG) debug();
+console.log('main');",
+                &[("./config", 1), ("./debug", 2)],
+                true,
+            ),
+            ("config.js", "export const DEBUG = false;", &[], false),
+            (
+                "debug.js",
+                "export function debug() { console.log('debug') }",
+                &[],
+                false,
+            ),
+        ],
+        false,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "import\"./config\";import\"./debug\";console.log(\"main\");",
+            "",
+            "",
+        ]
+    );
+}
+
+#[test]
+fn namespace_import_keeps_exports() {
+    let output = minify_graph(
+        &[
+            (
+                "main.js",
+                "import * as utils from './utils';
+console.log(utils);",
+                &[("./utils", 1)],
+                true,
+            ),
+            (
+                "utils.js",
+                "export function a() { return 1 }
+export function b() { return 2 }",
+                &[],
+                false,
+            ),
+        ],
+        true,
+    );
+
+    assert_eq!(output[1].matches("export").count(), 2, "{}", output[1]);
+}
+
+#[test]
+fn free_globals_are_not_renamed() {
+    let output = minify_graph(
+        &[
+            (
+                "a.js",
+                "const document = { title: 1 };
+export function f() { return document.title + document.body }",
+                &[],
+                true,
+            ),
+            (
+                "b.js",
+                "export function g() { return document.title + window.x }",
+                &[],
+                true,
+            ),
+        ],
+        true,
+    );
+
+    assert_eq!(
+        output[1],
+        "export function g(){return document.title+window.x}"
+    );
+}
+
+#[test]
+fn imports_are_named_after_exports() {
+    let output = minify_graph(
+        &[
+            (
+                "main.js",
+                "import { format as formatValue } from './format';
+import { parse } from './parse';
+export function main(input) { return formatValue(parse(input)) }",
+                &[("./format", 1), ("./parse", 2)],
+                true,
+            ),
+            (
+                "format.js",
+                "function formatImpl(value) { return String(value) }
+export { formatImpl as format };",
+                &[],
+                false,
+            ),
+            (
+                "parse.js",
+                "export function parse(input) { return JSON.parse(input) }",
+                &[],
+                false,
+            ),
+        ],
+        true,
+    );
+
+    let format_local = output[1]
+        .strip_prefix("function ")
+        .and_then(|s| s.split('(').next())
+        .unwrap();
+
+    assert!(
+        output[0].starts_with(&format!(
+            "import{{format as {f}}}from\"./format\";import{{parse}}from\"./parse\";",
+            f = format_local
+        )),
+        "{}",
+        output[0]
+    );
+    assert!(
+        output[0].contains(&format!("return {}(parse(", format_local)),
+        "{}",
+        output[0]
+    );
+    assert!(
+        output[1].contains(&format!("export{{{} as format}}", format_local)),
+        "{}",
+        output[1]
+    );
+}
@@ -0,0 +1,137 @@
+#![deny(warnings)]
+
+use std::sync::Arc;
+
+use serde_json::json;
+use swc_common::Mark;
+use swc_ecma_minifier::{
+    optimize,
+    option::{CompressOptions, ExtraOptions, MinifyOptions},
+    trace::{BlockReason, OptimizationTrace, Transform},
+};
+
+use self::common::parse;
+
+#[path = "common/mod.rs"]
+mod common;
+
+fn trace(src: &str) -> (Arc<OptimizationTrace>, serde_json::Value) {
+    let trace = Arc::new(OptimizationTrace::default());
+
+    let json = testing::run_test2(false, |cm, handler| {
+        let extra = ExtraOptions {
+            unresolved_mark: Mark::new(),
+            top_level_mark: Mark::new(),
+            mangle_name_cache: None,
+            optimization_trace: Some(trace.clone()),
+        };
+
+        let program = parse(&cm, &handler, "input.js", src, true, &extra)?;
+
+        optimize(
+            program,
+            cm.clone(),
+            None,
+            None,
+            &MinifyOptions {
+                compress: Some(CompressOptions {
+                    module: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            &extra,
+        );
+
+        Ok(trace.to_json(&cm))
+    })
+    .unwrap();
+
+    (trace, json)
+}
+
+fn has_event(
+    trace: &OptimizationTrace,
+    transform: Transform,
+    target: &str,
+    blocked_by: Option<BlockReason>,
+) -> bool {
+    trace.events().iter().any(|e| {
+        e.transform == transform
+            && e.target.as_deref() == Some(target)
+            && e.blocked_by == blocked_by
+    })
+}
+
+#[test]
+fn trace_inline_var() {
+    let (trace, _) = trace(
+        "export function run() {
+    var a = 1;
+    var b = sideEffect();
+    b = sideEffect();
+    console.log(a, b, b);
+}
+",
+    );
+
+    assert!(has_event(&trace, Transform::InlineVar, "a", None));
+    assert!(has_event(
+        &trace,
+        Transform::InlineVar,
+        "b",
+        Some(BlockReason::Reassigned)
+    ));
+    assert!(has_event(
+        &trace,
+        Transform::DropUnused,
+        "run",
+        Some(BlockReason::Exported)
+    ));
+}
+
+#[test]
+fn trace_eval_json() {
+    let (_, json) = trace(
+        "export function withEval(code) {
+    var x = 1;
+    eval(code);
+    return x;
+}
+",
+    );
+
+    assert!(json.as_array().unwrap().contains(&json!({
+        "transform": "inline_var",
+        "target": "x",
+        "applied": false,
+        "reason": "eval_scope",
+        "description": "used in eval scope",
+        "file": "input.js",
+        "line": 2,
+        "column": 9,
+    })));
+}
+
+#[test]
+fn trace_final_outcome() {
+    let (trace, _) = trace(
+        "export function run() {
+    var debug = false;
+    function f() { return sideEffect(); }
+    if (debug) f();
+    return f();
+}
+",
+    );
+
+    // `f` is used twice on the first pass, and inlined once `if (debug)` is
+    // removed.
+    assert!(has_event(&trace, Transform::InlineFn, "f", None));
+    assert!(!has_event(
+        &trace,
+        Transform::InlineFn,
+        "f",
+        Some(BlockReason::UsedMultipleTimes)
+    ));
+}
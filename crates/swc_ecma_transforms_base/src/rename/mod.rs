@@ -11,7 +11,10 @@ use self::renamer_concurrent::{Send, Sync};
 #[cfg(not(feature = "concurrent-renamer"))]
 use self::renamer_single::{Send, Sync};
 use self::{
-    analyzer::{scope::RenameMap, Analyzer},
+    analyzer::{
+        scope::{FastJsWord, RenameMap},
+        Analyzer,
+    },
     collector::{collect_decls, CustomBindingCollector, IdCollector},
     eval::contains_eval,
     ops::Operator,
@@ -40,6 +43,17 @@ pub trait Renamer: Send + Sync {
 
     /// Should increment `n`.
     fn new_name_for(&self, orig: &Id, n: &mut usize) -> JsWord;
+
+    /// Names which should be used for identifiers of the top level, like names
+    /// chosen by previous runs of the renamer.
+    ///
+    /// These names are not used for other identifiers.
+    fn get_cached(&self) -> Option<Cow<AHashMap<Id, JsWord>>> {
+        None
+    }
+
+    /// Called with the names chosen for the top level.
+    fn store_cache(&mut self, _update: &AHashMap<Id, JsWord>) {}
 }
 
 pub fn rename(map: &AHashMap<Id, JsWord>) -> impl '_ + Fold + VisitMut {
@@ -153,6 +167,25 @@ where
                 .extend(self.preserved.iter().map(|v| v.0.clone()));
         }
 
+        if top_level {
+            if let Some(cached) = self.renamer.get_cached() {
+                // Names used by this program cannot be reused.
+                let cached = cached
+                    .iter()
+                    .filter(|(_, v)| !unresolved.contains(*v))
+                    .collect::<Vec<_>>();
+
+                unresolved
+                    .to_mut()
+                    .extend(cached.iter().map(|(_, v)| (*v).clone()));
+                map.extend(
+                    cached
+                        .into_iter()
+                        .map(|(id, v)| ((FastJsWord::new(id.0.clone()), id.1), v.clone())),
+                );
+            }
+        }
+
         if R::MANGLE {
             let cost = scope.rename_cost();
             scope.rename_in_mangle_mode(
@@ -244,6 +277,7 @@ where
         self.unresolved = self.get_unresolved(m, has_eval);
 
         let map = self.get_map(m, false, true, has_eval);
+        self.renamer.store_cache(&map);
 
         // If we have eval, we cannot rename a whole program at once.
         //
@@ -281,6 +315,7 @@ where
         self.unresolved = self.get_unresolved(m, has_eval);
 
         let map = self.get_map(m, false, true, has_eval);
+        self.renamer.store_cache(&map);
 
         if has_eval {
             m.visit_mut_children_with(self);
@@ -1,10 +1,25 @@
-use swc_atoms::JsWord;
-use swc_common::collections::AHashMap;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use swc_atoms::{js_word, JsWord};
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    errors::HANDLER,
+    sync::Lrc,
+    FileName, Mark, SourceMap, Span, SyntaxContext,
+};
 use swc_ecma_ast::*;
-use swc_ecma_transforms_base::enable_helper;
+use swc_ecma_loader::resolve::Resolve;
+use swc_ecma_parser::{parse_file_as_program, EsConfig, Syntax, TsConfig};
+use swc_ecma_transforms_base::{enable_helper, resolver};
+use swc_ecma_utils::find_pat_ids;
 use swc_ecma_visit::{
-    as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitWith,
+    as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitMutWith, VisitWith,
 };
+use tracing::debug;
 
 use crate::{module_decl_strip::LinkFlag, util::ImportInterop};
 
@@ -106,3 +121,658 @@ impl Visit for ImportAnalyzer {
         self.dynamic_import_found = true;
     }
 }
+
+/// Reports imports which do not match exports of the imported modules, which
+/// would otherwise fail at runtime after converting ES modules to CommonJS.
+///
+/// Only imports resolved to local files on disk are checked, and packages in
+/// `node_modules` are skipped. Exports of CommonJS modules are detected like
+/// `cjs-module-lexer`, and modules whose exports cannot be detected are not
+/// checked.
+///
+/// As the detection is heuristic, problems are reported to [HANDLER] as
+/// warnings.
+pub fn interop_diagnostics<R>(resolver: R, base: FileName) -> impl Fold + VisitMut
+where
+    R: Resolve,
+{
+    as_folder(InteropDiagnostics {
+        resolver,
+        base,
+        cache: Default::default(),
+    })
+}
+
+struct InteropDiagnostics<R>
+where
+    R: Resolve,
+{
+    resolver: R,
+    base: FileName,
+    /// [None] means the exports of the module are unknown.
+    cache: AHashMap<PathBuf, Option<Rc<ModuleExports>>>,
+}
+
+#[derive(Debug, Default)]
+struct ModuleExports {
+    /// Exported names and the files which declare them.
+    names: AHashMap<JsWord, PathBuf>,
+    /// Names exported by multiple `export *` with different origins, which
+    /// are not exported at all.
+    ambiguous: AHashSet<JsWord>,
+}
+
+impl<R> VisitMut for InteropDiagnostics<R>
+where
+    R: Resolve,
+{
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        self.visit_module(&*module);
+    }
+}
+
+impl<R> Visit for InteropDiagnostics<R>
+where
+    R: Resolve,
+{
+    noop_visit_type!();
+
+    fn visit_module(&mut self, n: &Module) {
+        let base = self.base.clone();
+
+        for item in &n.body {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if !import.type_only => {
+                    let exports = match self.exports_of(&base, &import.src.value) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    for s in &import.specifiers {
+                        match s {
+                            ImportSpecifier::Named(s) if !s.is_type_only => {
+                                let name = match &s.imported {
+                                    Some(imported) => export_name(imported),
+                                    None => &s.local.sym,
+                                };
+                                check_import(&exports, &import.src, name, s.span);
+                            }
+                            ImportSpecifier::Default(s) => {
+                                check_import(&exports, &import.src, &js_word!("default"), s.span);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    specifiers,
+                    type_only: false,
+                    ..
+                })) => {
+                    let exports = match self.exports_of(&base, &src.value) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    for s in specifiers {
+                        match s {
+                            ExportSpecifier::Named(s) if !s.is_type_only => {
+                                check_import(&exports, src, export_name(&s.orig), s.span);
+                            }
+                            ExportSpecifier::Default(s) => {
+                                check_import(&exports, src, &js_word!("default"), s.exported.span);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        self.check_export_stars(n);
+    }
+}
+
+impl<R> InteropDiagnostics<R>
+where
+    R: Resolve,
+{
+    /// Reports names exported by multiple `export *` declarations of `n`.
+    fn check_export_stars(&mut self, n: &Module) {
+        let base = self.base.clone();
+        let explicit = explicit_exports(n);
+
+        let mut seen = AHashMap::<JsWord, (PathBuf, &Str)>::default();
+        let mut reported = AHashSet::default();
+
+        for item in &n.body {
+            let src = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+                    src,
+                    type_only: false,
+                    ..
+                })) => src,
+                _ => continue,
+            };
+            let exports = match self.exports_of(&base, &src.value) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let mut names = exports
+                .names
+                .iter()
+                .filter(|(name, _)| **name != js_word!("default") && !explicit.contains(*name))
+                .collect::<Vec<_>>();
+            names.sort_by(|a, b| a.0.cmp(b.0));
+
+            for (name, origin) in names {
+                match seen.get(name) {
+                    Some((prev_origin, prev_src)) => {
+                        if prev_origin != origin && reported.insert(name.clone()) {
+                            emit_warning(
+                                src.span,
+                                &format!(
+                                    "`{}` is exported by both `{}` and `{}` through `export *`, \
+                                     so it is not exported",
+                                    name, prev_src.value, src.value
+                                ),
+                            );
+                        }
+                    }
+                    None => {
+                        seen.insert(name.clone(), (origin.clone(), &**src));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the exports of `src` imported from `base`.
+    fn exports_of(&mut self, base: &FileName, src: &str) -> Option<Rc<ModuleExports>> {
+        let path = match self.resolver.resolve(base, src) {
+            // Parsing dependencies would be too slow.
+            Ok(FileName::Real(path))
+                if path
+                    .components()
+                    .any(|c| c.as_os_str() == "node_modules") =>
+            {
+                return None
+            }
+            Ok(FileName::Real(path)) => path,
+            Ok(..) => return None,
+            Err(err) => {
+                debug!(
+                    "interop diagnostics: failed to resolve `{}`: {:?}",
+                    src, err
+                );
+                return None;
+            }
+        };
+
+        self.exports_of_file(path)
+    }
+
+    fn exports_of_file(&mut self, path: PathBuf) -> Option<Rc<ModuleExports>> {
+        if let Some(v) = self.cache.get(&path) {
+            return v.clone();
+        }
+        // Breaks cycles of `export *`.
+        self.cache.insert(path.clone(), None);
+
+        let exports = self.collect_exports(&path).map(Rc::new);
+        self.cache.insert(path, exports.clone());
+        exports
+    }
+
+    fn collect_exports(&mut self, path: &Path) -> Option<ModuleExports> {
+        let (program, unresolved_ctxt) = parse_program(path)?;
+
+        match program {
+            Program::Module(m) if m.body.iter().any(|item| item.is_module_decl()) => {
+                self.collect_esm_exports(path, &m)
+            }
+            Program::Module(Module { body, .. }) => {
+                let stmts = body
+                    .into_iter()
+                    .filter_map(|item| item.stmt())
+                    .collect::<Vec<_>>();
+                self.collect_cjs_exports(path, &stmts, unresolved_ctxt)
+            }
+            Program::Script(s) => self.collect_cjs_exports(path, &s.body, unresolved_ctxt),
+        }
+    }
+
+    fn collect_esm_exports(&mut self, path: &Path, m: &Module) -> Option<ModuleExports> {
+        let base = FileName::Real(path.to_path_buf());
+        let mut exports = ModuleExports::default();
+        let mut stars = vec![];
+
+        for item in &m.body {
+            let decl = match item {
+                ModuleItem::ModuleDecl(v) => v,
+                _ => continue,
+            };
+
+            match decl {
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    for name in decl_names(decl) {
+                        exports.names.insert(name, path.to_path_buf());
+                    }
+                }
+                ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..) => {
+                    exports
+                        .names
+                        .insert(js_word!("default"), path.to_path_buf());
+                }
+                ModuleDecl::ExportNamed(NamedExport {
+                    specifiers, src, ..
+                }) => {
+                    let target = src
+                        .as_ref()
+                        .and_then(|src| self.exports_of(&base, &src.value));
+
+                    for s in specifiers {
+                        let (orig, exported) = match s {
+                            ExportSpecifier::Named(s) => (
+                                export_name(&s.orig).clone(),
+                                export_name(s.exported.as_ref().unwrap_or(&s.orig)).clone(),
+                            ),
+                            ExportSpecifier::Namespace(s) => {
+                                exports
+                                    .names
+                                    .insert(export_name(&s.name).clone(), path.to_path_buf());
+                                continue;
+                            }
+                            ExportSpecifier::Default(s) => {
+                                (js_word!("default"), s.exported.sym.clone())
+                            }
+                        };
+
+                        let origin = target
+                            .as_ref()
+                            .and_then(|target| target.names.get(&orig))
+                            .cloned()
+                            .unwrap_or_else(|| path.to_path_buf());
+                        exports.names.insert(exported, origin);
+                    }
+                }
+                ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                    stars.push(self.exports_of(&base, &src.value));
+                }
+                // `export =` of TypeScript is CommonJS.
+                ModuleDecl::TsExportAssignment(..) => return None,
+                _ => {}
+            }
+        }
+
+        let explicit = exports.names.keys().cloned().collect::<AHashSet<_>>();
+        for star in stars {
+            // Names of unknown modules cannot be checked.
+            let star = star?;
+
+            for name in &star.ambiguous {
+                if !explicit.contains(name) {
+                    exports.ambiguous.insert(name.clone());
+                }
+            }
+
+            for (name, origin) in &star.names {
+                if *name == js_word!("default") || explicit.contains(name) {
+                    continue;
+                }
+
+                match exports.names.get(name) {
+                    Some(prev) if prev != origin => {
+                        exports.ambiguous.insert(name.clone());
+                    }
+                    Some(..) => {}
+                    None => {
+                        exports.names.insert(name.clone(), origin.clone());
+                    }
+                }
+            }
+        }
+        for name in &exports.ambiguous {
+            exports.names.remove(name);
+        }
+
+        Some(exports)
+    }
+
+    fn collect_cjs_exports(
+        &mut self,
+        path: &Path,
+        stmts: &[Stmt],
+        unresolved_ctxt: SyntaxContext,
+    ) -> Option<ModuleExports> {
+        let mut finder = CjsExportFinder {
+            unresolved_ctxt,
+            names: Default::default(),
+            reexports: Default::default(),
+            unknown: false,
+        };
+        stmts.visit_with(&mut finder);
+
+        if finder.unknown {
+            return None;
+        }
+
+        let base = FileName::Real(path.to_path_buf());
+        let mut exports = ModuleExports::default();
+        // `module.exports` is the default export.
+        exports
+            .names
+            .insert(js_word!("default"), path.to_path_buf());
+        for name in finder.names {
+            exports.names.insert(name, path.to_path_buf());
+        }
+
+        for src in finder.reexports {
+            let reexported = self.exports_of(&base, &src)?;
+            for (name, origin) in &reexported.names {
+                if *name != js_word!("default") {
+                    exports
+                        .names
+                        .entry(name.clone())
+                        .or_insert_with(|| origin.clone());
+                }
+            }
+        }
+
+        Some(exports)
+    }
+}
+
+/// Finds exports of a CommonJS module, like `cjs-module-lexer`.
+struct CjsExportFinder {
+    /// `exports`, `module` and `require` are globals only if their context is
+    /// this.
+    unresolved_ctxt: SyntaxContext,
+    names: AHashSet<JsWord>,
+    reexports: Vec<JsWord>,
+    /// True if `module.exports` is assigned something other than an object
+    /// literal or `require()`.
+    unknown: bool,
+}
+
+impl CjsExportFinder {
+    fn add_object_keys(&mut self, obj: &ObjectLit) {
+        for prop in &obj.props {
+            match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::Shorthand(i) => {
+                        self.names.insert(i.sym.clone());
+                    }
+                    Prop::KeyValue(KeyValueProp { key, .. })
+                    | Prop::Getter(GetterProp { key, .. })
+                    | Prop::Method(MethodProp { key, .. }) => match key {
+                        PropName::Ident(i) => {
+                            self.names.insert(i.sym.clone());
+                        }
+                        PropName::Str(s) => {
+                            self.names.insert(s.value.clone());
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                },
+                PropOrSpread::Spread(SpreadElement { expr, .. }) => match self.require_src(expr) {
+                    Some(src) => self.reexports.push(src.clone()),
+                    None => self.unknown = true,
+                },
+            }
+        }
+    }
+
+    /// Returns true if `e` is the global `name`, not a local binding.
+    fn is_global(&self, e: &Expr, name: &str) -> bool {
+        match e {
+            Expr::Ident(i) => &*i.sym == name && i.span.ctxt == self.unresolved_ctxt,
+            _ => false,
+        }
+    }
+
+    /// `exports` or `module.exports`
+    fn is_exports(&self, e: &Expr) -> bool {
+        match e {
+            Expr::Member(m) => self.is_module_exports(m),
+            _ => self.is_global(e, "exports"),
+        }
+    }
+
+    fn is_module_exports(&self, m: &MemberExpr) -> bool {
+        self.is_global(&m.obj, "module")
+            && prop_name(&m.prop).map_or(false, |p| &**p == "exports")
+    }
+
+    /// `require("foo")`
+    fn require_src<'a>(&self, e: &'a Expr) -> Option<&'a JsWord> {
+        match e {
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) if self.is_global(callee, "require") && args.len() == 1 => match &*args[0].expr {
+                Expr::Lit(Lit::Str(s)) => Some(&s.value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Visit for CjsExportFinder {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        n.visit_children_with(self);
+
+        let left = match &n.left {
+            PatOrExpr::Expr(e) => &**e,
+            PatOrExpr::Pat(p) => match &**p {
+                Pat::Expr(e) => &**e,
+                _ => return,
+            },
+        };
+        let left = match left {
+            Expr::Member(v) => v,
+            _ => return,
+        };
+
+        // `module.exports = ...`
+        if self.is_module_exports(left) {
+            match &*n.right {
+                Expr::Object(obj) => self.add_object_keys(obj),
+                right => match self.require_src(right) {
+                    Some(src) => self.reexports.push(src.clone()),
+                    None => self.unknown = true,
+                },
+            }
+            return;
+        }
+
+        // `exports.foo = ...` or `module.exports.foo = ...`
+        if self.is_exports(&left.obj) {
+            if let Some(name) = prop_name(&left.prop) {
+                self.names.insert(name.clone());
+            }
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        n.visit_children_with(self);
+
+        let callee = match &n.callee {
+            Callee::Expr(callee) => &**callee,
+            _ => return,
+        };
+
+        match (callee, &*n.args) {
+            // `Object.defineProperty(exports, "foo", ...)`
+            (Expr::Member(callee), [target, name, ..])
+                if self.is_global(&callee.obj, "Object")
+                    && prop_name(&callee.prop).map_or(false, |p| &**p == "defineProperty")
+                    && self.is_exports(&target.expr) =>
+            {
+                if let Expr::Lit(Lit::Str(name)) = &*name.expr {
+                    if name.value != *"__esModule" {
+                        self.names.insert(name.value.clone());
+                    }
+                }
+            }
+            // `_export(exports, { foo: ... })` of swc
+            (Expr::Ident(callee), [target, obj])
+                if &*callee.sym == "_export" && self.is_exports(&target.expr) =>
+            {
+                if let Expr::Object(obj) = &*obj.expr {
+                    self.add_object_keys(obj);
+                }
+            }
+            // `_export_star(require("foo"), exports)` of swc, `__exportStar` of tsc
+            (Expr::Ident(callee), [src, target])
+                if matches!(&*callee.sym, "_export_star" | "__exportStar")
+                    && self.is_exports(&target.expr) =>
+            {
+                if let Some(src) = self.require_src(&src.expr) {
+                    self.reexports.push(src.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn prop_name(prop: &MemberProp) -> Option<&JsWord> {
+    match prop {
+        MemberProp::Ident(i) => Some(&i.sym),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(s)) => Some(&s.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn export_name(n: &ModuleExportName) -> &JsWord {
+    match n {
+        ModuleExportName::Ident(i) => &i.sym,
+        ModuleExportName::Str(s) => &s.value,
+    }
+}
+
+fn decl_names(decl: &Decl) -> Vec<JsWord> {
+    match decl {
+        Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+            vec![ident.sym.clone()]
+        }
+        Decl::TsEnum(e) => vec![e.id.sym.clone()],
+        Decl::TsInterface(i) => vec![i.id.sym.clone()],
+        Decl::TsTypeAlias(a) => vec![a.id.sym.clone()],
+        Decl::TsModule(m) => match &m.id {
+            TsModuleName::Ident(ident) => vec![ident.sym.clone()],
+            TsModuleName::Str(..) => vec![],
+        },
+        Decl::Var(v) => find_pat_ids::<_, Ident>(&v.decls)
+            .into_iter()
+            .map(|i| i.sym)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Names exported without `export *`.
+fn explicit_exports(m: &Module) -> AHashSet<JsWord> {
+    let mut names = AHashSet::default();
+
+    for item in &m.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                names.extend(decl_names(decl));
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { specifiers, .. })) => {
+                names.extend(specifiers.iter().map(|s| match s {
+                    ExportSpecifier::Named(s) => {
+                        export_name(s.exported.as_ref().unwrap_or(&s.orig)).clone()
+                    }
+                    ExportSpecifier::Namespace(s) => export_name(&s.name).clone(),
+                    ExportSpecifier::Default(s) => s.exported.sym.clone(),
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+fn check_import(exports: &ModuleExports, src: &Str, name: &JsWord, span: Span) {
+    if exports.names.contains_key(name) {
+        return;
+    }
+
+    let msg = if exports.ambiguous.contains(name) {
+        format!(
+            "`{}` is exported by multiple `export *` of `{}`, so it is not exported",
+            name, src.value
+        )
+    } else {
+        format!("`{}` does not export `{}`", src.value, name)
+    };
+
+    emit_warning(span, &msg);
+}
+
+fn emit_warning(span: Span, msg: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_warn(span, msg).emit());
+    }
+}
+
+/// Parses a file and resolves its identifiers.
+///
+/// Returns the program and the context of unresolved (global) identifiers.
+fn parse_program(path: &Path) -> Option<(Program, SyntaxContext)> {
+    let ext = path.extension()?.to_str()?;
+    let syntax = match ext {
+        "js" | "jsx" | "mjs" | "cjs" => Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+        "ts" | "mts" | "cts" => Syntax::Typescript(Default::default()),
+        "tsx" => Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => return None,
+    };
+
+    let src = fs::read_to_string(path).ok()?;
+    let cm = Lrc::new(SourceMap::default());
+    let fm = cm.new_source_file(FileName::Real(path.to_path_buf()), src);
+
+    let mut program = parse_file_as_program(&fm, syntax, EsVersion::latest(), None, &mut vec![])
+        .map_err(|err| {
+            debug!(
+                "interop diagnostics: failed to parse {}: {:?}",
+                path.display(),
+                err
+            )
+        })
+        .ok()?;
+
+    let unresolved_mark = Mark::new();
+    program.visit_mut_with(&mut resolver(
+        unresolved_mark,
+        Mark::new(),
+        syntax.typescript(),
+    ));
+
+    Some((program, SyntaxContext::empty().apply_mark(unresolved_mark)))
+}
@@ -26,4 +26,11 @@ pub mod umd;
 pub struct EsModuleConfig {
     #[serde(default)]
     pub resolve_fully: bool,
+
+    /// Rewrites imports of local files to the files emitted for them, like
+    /// `./util` to `./util.js`, so the output can be loaded by node.js.
+    ///
+    /// See [path::Config::fully_specified].
+    #[serde(default)]
+    pub fully_specified: bool,
 }
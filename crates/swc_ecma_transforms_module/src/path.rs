@@ -93,6 +93,12 @@ where
 pub struct Config {
     pub base_dir: Option<PathBuf>,
     pub resolve_fully: bool,
+    /// Rewrites specifiers of local files to the files emitted for them, as
+    /// required by the ES module loader of node.js.
+    ///
+    /// e.g. `./util` becomes `./util.js`, `./dir` becomes `./dir/index.js` and
+    /// `./mod.mts` becomes `./mod.mjs`.
+    pub fully_specified: bool,
 }
 
 impl<R> NodeImportResolver<R>
@@ -187,11 +193,25 @@ where
             target_path.set_extension("");
         }
 
-        if cfg!(target_os = "windows") {
-            target_path.display().to_string().replace('\\', "/").into()
-        } else {
-            target_path.display().to_string().into()
+        to_specifier_string(&target_path)
+    }
+
+    /// Creates a specifier for the file emitted for `target_path`.
+    fn to_fully_specified(&self, mut target_path: PathBuf) -> JsWord {
+        debug!(
+            "Creating a fully specified specifier for `{}`",
+            target_path.display()
+        );
+
+        if let Some(ext) = target_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(emitted_extension)
+        {
+            target_path.set_extension(ext);
         }
+
+        to_specifier_string(&target_path)
     }
 
     fn try_resolve_import(&self, base: &FileName, module_specifier: &str) -> Result<JsWord, Error> {
@@ -209,7 +229,14 @@ where
             None
         };
 
-        let orig_filename = module_specifier.split('/').last();
+        // The file name of an address from an import map is used as-is, like
+        // `./src/utils.js` for `@app/utils`.
+        let mapped = self.resolver.map_specifier(base, module_specifier);
+        let orig_filename = mapped
+            .as_deref()
+            .unwrap_or(module_specifier)
+            .split('/')
+            .last();
 
         let target = self.resolver.resolve(base, module_specifier);
         let target = match target {
@@ -231,6 +258,8 @@ where
                     return Ok(self.to_specifier(v, orig_filename));
                 }
             }
+            // e.g. urls from import maps
+            FileName::Custom(s) if s.contains("://") => return Ok(s.into()),
             FileName::Custom(s) => return Ok(self.to_specifier(s.into(), orig_filename)),
             _ => {
                 unreachable!(
@@ -307,6 +336,10 @@ where
             Cow::Owned(format!("./{}", s))
         };
 
+        if self.config.fully_specified {
+            return Ok(self.to_fully_specified(s.into_owned().into()));
+        }
+
         Ok(self.to_specifier(s.into_owned().into(), orig_filename))
     }
 }
@@ -341,6 +374,24 @@ impl_ref!(P, &'_ P);
 impl_ref!(P, Box<P>);
 impl_ref!(P, Arc<P>);
 
+fn to_specifier_string(path: &Path) -> JsWord {
+    if cfg!(target_os = "windows") {
+        path.display().to_string().replace('\\', "/").into()
+    } else {
+        path.display().to_string().into()
+    }
+}
+
+/// Returns the extension of the file emitted for a source file with `ext`.
+fn emitted_extension(ext: &str) -> Option<&'static str> {
+    match ext {
+        "ts" | "tsx" | "jsx" => Some("js"),
+        "mts" => Some("mjs"),
+        "cts" => Some("cjs"),
+        _ => None,
+    }
+}
+
 fn absolute_path(base_dir: Option<&Path>, path: &Path) -> io::Result<PathBuf> {
     let absolute_path = if path.is_absolute() {
         path.to_path_buf()
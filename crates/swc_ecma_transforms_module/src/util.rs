@@ -43,6 +43,13 @@ pub struct Config {
 
     #[serde(default)]
     pub resolve_fully: bool,
+
+    /// Reports imports of local files which do not match their exports, like
+    /// named imports which a CommonJS module never defines.
+    ///
+    /// See [crate::import_analysis::interop_diagnostics].
+    #[serde(default)]
+    pub interop_diagnostics: bool,
 }
 
 impl Default for Config {
@@ -58,6 +65,7 @@ impl Default for Config {
             ignore_dynamic: false,
             preserve_import_meta: false,
             resolve_fully: false,
+            interop_diagnostics: false,
         }
     }
 }
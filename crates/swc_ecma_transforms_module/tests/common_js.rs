@@ -1,12 +1,29 @@
-use std::{fs::File, path::PathBuf, rc::Rc};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
-use swc_common::{chain, comments::SingleThreadedComments, Mark};
+use swc_common::{
+    chain,
+    comments::SingleThreadedComments,
+    errors::{Handler, HANDLER},
+    FileName, Mark,
+};
+use swc_ecma_loader::{resolvers::node::NodeModulesResolver, TargetEnv};
 use swc_ecma_parser::{Syntax, TsConfig};
 use swc_ecma_transforms_base::{feature::FeatureFlag, resolver};
 use swc_ecma_transforms_compat::es2015::for_of;
-use swc_ecma_transforms_module::common_js::{self, common_js};
+use swc_ecma_transforms_module::{
+    common_js::{self, common_js},
+    import_analysis::interop_diagnostics,
+};
 use swc_ecma_transforms_testing::{test, test_fixture, FixtureTestConfig};
+use swc_ecma_ast::Module;
 use swc_ecma_visit::Fold;
+use testing::NormalizedOutput;
 
 fn syntax() -> Syntax {
     Default::default()
@@ -66,6 +83,80 @@ fn esm_to_cjs(input: PathBuf) {
     );
 }
 
+#[testing::fixture("tests/interop-diagnostics/**/input.js")]
+fn esm_to_cjs_interop_diagnostics(input: PathBuf) {
+    let output = input.with_file_name("output.cjs");
+    let base = FileName::Real(input.canonicalize().unwrap());
+    let warnings = Buffer::default();
+
+    test_fixture(
+        syntax(),
+        &|tester| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, false),
+                CaptureWarnings {
+                    pass: interop_diagnostics(
+                        NodeModulesResolver::new(TargetEnv::Node, Default::default(), true),
+                        base.clone(),
+                    ),
+                    handler: Handler::with_emitter_writer(
+                        Box::new(warnings.clone()),
+                        Some(tester.cm.clone()),
+                    ),
+                },
+                common_js(
+                    unresolved_mark,
+                    Default::default(),
+                    FeatureFlag::all(),
+                    Some(tester.comments.clone())
+                ),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            sourcemap: false,
+            allow_error: false,
+        },
+    );
+
+    let warnings = String::from_utf8(warnings.0.lock().unwrap().clone()).unwrap();
+    NormalizedOutput::from(warnings)
+        .compare_to_file(input.with_file_name("output.stderr"))
+        .unwrap();
+}
+
+/// The handler of tests does not emit warnings.
+struct CaptureWarnings<P> {
+    pass: P,
+    handler: Handler,
+}
+
+impl<P> Fold for CaptureWarnings<P>
+where
+    P: Fold,
+{
+    fn fold_module(&mut self, n: Module) -> Module {
+        HANDLER.set(&self.handler, || self.pass.fold_module(n))
+    }
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 test!(
     syntax(),
     |tester| chain!(
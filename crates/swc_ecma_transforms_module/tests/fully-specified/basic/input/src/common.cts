@@ -0,0 +1 @@
+export const cts = 1;
@@ -0,0 +1 @@
+export const tsx = 1;
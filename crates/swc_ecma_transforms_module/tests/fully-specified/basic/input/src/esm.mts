@@ -0,0 +1 @@
+export const mts = 1;
@@ -0,0 +1,17 @@
+import { util } from "./util";
+import { helper } from "./util.js";
+import { dir } from "./dir";
+import { nested } from "./nested/";
+import { tsx } from "./component";
+import { mts } from "./esm.mjs";
+import { cts } from "./common.cjs";
+import { plain } from "./lib/plain";
+import json from "./data.json";
+import "./missing";
+export * from "../shared";
+export { reexported } from "./lib";
+
+const lazy = import("./util");
+const dynamicDir = import("./dir");
+
+console.log(util, helper, dir, nested, tsx, mts, cts, plain, json, lazy, dynamicDir);
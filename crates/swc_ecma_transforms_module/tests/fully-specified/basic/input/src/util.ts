@@ -0,0 +1,2 @@
+export const util = 1;
+export const helper = 2;
@@ -0,0 +1,15 @@
+import { util } from "./util.js";
+import { helper } from "./util.js";
+import { dir } from "./dir/index.js";
+import { nested } from "./nested/index.js";
+import { tsx } from "./component.js";
+import { mts } from "./esm.mjs";
+import { cts } from "./common.cjs";
+import { plain } from "./lib/plain.js";
+import json from "./data.json";
+import "./missing";
+export * from "../shared.js";
+export { reexported } from "./lib/index.js";
+const lazy = import("./util.js");
+const dynamicDir = import("./dir/index.js");
+console.log(util, helper, dir, nested, tsx, mts, cts, plain, json, lazy, dynamicDir);
@@ -0,0 +1,6 @@
+import React from "react";
+import debounce from "lodash/debounce.js";
+import { format } from "@app/utils";
+import { local } from "./local.js";
+
+console.log(React, debounce, format, local);
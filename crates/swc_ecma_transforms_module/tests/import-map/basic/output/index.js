@@ -0,0 +1,5 @@
+import React from "https://esm.sh/react@18.2.0";
+import debounce from "../vendor/lodash/debounce.js";
+import { format } from "./utils.js";
+import { local } from "./local.js";
+console.log(React, debounce, format, local);
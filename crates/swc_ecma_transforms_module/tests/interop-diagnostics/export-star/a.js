@@ -0,0 +1,3 @@
+export const shared = "a";
+export const onlyA = "a";
+export const conflict = "a";
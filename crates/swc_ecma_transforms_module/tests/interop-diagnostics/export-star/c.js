@@ -0,0 +1,5 @@
+module.exports = {
+    conflict: "c",
+    onlyA: "c",
+    onlyC: "c"
+};
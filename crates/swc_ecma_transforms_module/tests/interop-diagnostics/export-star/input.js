@@ -0,0 +1,8 @@
+import { shared, onlyA } from "./reexport";
+
+export * from "./a";
+export * from "./b";
+export { shared } from "./a";
+export * from "./c";
+
+console.log(shared, onlyA);
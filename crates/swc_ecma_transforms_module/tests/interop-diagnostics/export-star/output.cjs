@@ -0,0 +1,15 @@
+"use strict";
+Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+Object.defineProperty(exports, "shared", {
+    enumerable: true,
+    get: function() {
+        return _a.shared;
+    }
+});
+const _reexport = require("./reexport");
+const _a = _export_star(require("./a"), exports);
+_export_star(require("./b"), exports);
+_export_star(require("./c"), exports);
+console.log(_reexport.shared, _reexport.onlyA);
@@ -0,0 +1,8 @@
+"use strict";
+exports.b = 1;
+Object.defineProperty(exports, "c", {
+    enumerable: true,
+    get: function() {
+        return 2;
+    }
+});
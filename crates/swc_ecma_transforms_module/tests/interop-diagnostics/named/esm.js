@@ -0,0 +1,3 @@
+export const a = 1;
+export function typos() {}
+export default class Foo {}
@@ -0,0 +1,8 @@
+import esm, { a, missing as renamed } from "./esm";
+import { b, c, notExported } from "./cjs.cjs";
+import { x } from "./unknown.cjs";
+import { typo } from "./esm";
+import { real, fake, alsoFake } from "./shadowed.cjs";
+export { a as reexported, nope } from "./esm";
+
+console.log(esm, a, renamed, b, c, notExported, x, typo, real, fake, alsoFake);
@@ -0,0 +1,23 @@
+"use strict";
+Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+function _export(target, all) {
+    for(var name in all)Object.defineProperty(target, name, {
+        enumerable: true,
+        get: all[name]
+    });
+}
+_export(exports, {
+    nope: function() {
+        return _esm.nope;
+    },
+    reexported: function() {
+        return _esm.a;
+    }
+});
+const _esm = /*#__PURE__*/ _interop_require_wildcard(require("./esm"));
+const _cjscjs = require("./cjs.cjs");
+const _unknowncjs = require("./unknown.cjs");
+const _shadowedcjs = require("./shadowed.cjs");
+console.log(_esm.default, _esm.a, _esm.missing, _cjscjs.b, _cjscjs.c, _cjscjs.notExported, _unknowncjs.x, _esm.typo, _shadowedcjs.real, _shadowedcjs.fake, _shadowedcjs.alsoFake);
@@ -0,0 +1,7 @@
+"use strict";
+exports.real = 1;
+
+function define(exports, module) {
+    exports.fake = 1;
+    module.exports.alsoFake = 1;
+}
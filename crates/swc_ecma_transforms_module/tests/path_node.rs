@@ -3,7 +3,9 @@ use std::path::{Path, PathBuf};
 use indexmap::IndexMap;
 use serde::Deserialize;
 use swc_common::FileName;
-use swc_ecma_loader::resolvers::{node::NodeModulesResolver, tsc::TsConfigResolver};
+use swc_ecma_loader::resolvers::{
+    import_map::ImportMapResolver, node::NodeModulesResolver, tsc::TsConfigResolver,
+};
 use swc_ecma_parser::Syntax;
 use swc_ecma_transforms_module::{
     path::{ImportResolver, NodeImportResolver},
@@ -100,6 +102,7 @@ fn paths_resolver(base_dir: &Path, rules: Vec<(String, Vec<String>)>) -> JscPath
         swc_ecma_transforms_module::path::Config {
             base_dir: Some(base_dir),
             resolve_fully: false,
+            fully_specified: false,
         },
     )
 }
@@ -142,3 +145,67 @@ fn fixture(input_dir: PathBuf) {
         Default::default(),
     );
 }
+
+#[testing::fixture("tests/import-map/**/input")]
+fn import_map(input_dir: PathBuf) {
+    let input_dir = input_dir.canonicalize().unwrap();
+    let output_dir = input_dir.parent().unwrap().join("output");
+    let index_path = input_dir.join("src").join("index.js");
+
+    test_fixture(
+        Syntax::default(),
+        &|_| {
+            let resolver = NodeImportResolver::with_config(
+                ImportMapResolver::from_file(
+                    NodeModulesResolver::new(
+                        swc_ecma_loader::TargetEnv::Browser,
+                        Default::default(),
+                        true,
+                    ),
+                    &input_dir.join("import-map.json"),
+                )
+                .unwrap(),
+                swc_ecma_transforms_module::path::Config {
+                    base_dir: Some(input_dir.clone()),
+                    resolve_fully: true,
+                    fully_specified: false,
+                },
+            );
+
+            import_rewriter(FileName::Real(index_path.clone()), resolver)
+        },
+        &index_path,
+        &output_dir.join("index.js"),
+        Default::default(),
+    );
+}
+
+#[testing::fixture("tests/fully-specified/**/input")]
+fn fully_specified(input_dir: PathBuf) {
+    let input_dir = input_dir.canonicalize().unwrap();
+    let output_dir = input_dir.parent().unwrap().join("output");
+    let index_path = input_dir.join("src").join("index.ts");
+
+    test_fixture(
+        Syntax::default(),
+        &|_| {
+            let resolver = NodeImportResolver::with_config(
+                NodeModulesResolver::without_node_modules(
+                    swc_ecma_loader::TargetEnv::Node,
+                    Default::default(),
+                    true,
+                ),
+                swc_ecma_transforms_module::path::Config {
+                    base_dir: Some(input_dir.clone()),
+                    resolve_fully: false,
+                    fully_specified: true,
+                },
+            );
+
+            import_rewriter(FileName::Real(index_path.clone()), resolver)
+        },
+        &index_path,
+        &output_dir.join("index.js"),
+        Default::default(),
+    );
+}
@@ -9,6 +9,7 @@ use self::storage::{Storage, *};
 use crate::{
     alias::{collect_infects_from, AliasConfig},
     marks::Marks,
+    pure_globals::is_builtin_without_arg_mutation,
     util::can_end_conditionally,
 };
 
@@ -20,6 +21,22 @@ pub mod storage;
 ///
 /// If `marks` is [None], markers are ignored.
 pub fn analyze_with_storage<S, N>(n: &N, marks: Option<Marks>) -> S
+where
+    S: Storage,
+    N: VisitWith<UsageAnalyzer<S>>,
+{
+    analyze_with_pure_builtins(n, marks, None)
+}
+
+/// Same as [analyze_with_storage], but built-ins which do not mutate their
+/// arguments are recognized if `pure_builtins` is the target version.
+///
+/// This should be [None] unless globals are known to be unmodified.
+pub fn analyze_with_pure_builtins<S, N>(
+    n: &N,
+    marks: Option<Marks>,
+    pure_builtins: Option<EsVersion>,
+) -> S
 where
     S: Storage,
     N: VisitWith<UsageAnalyzer<S>>,
@@ -36,6 +53,7 @@ where
                 .apply_mark(marks.map(|m| m.unresolved_mark).unwrap_or_else(Mark::new)),
             is_unresolved_ref_safe: false,
         },
+        pure_builtins,
         used_recursively: AHashMap::default(),
     };
     n.visit_with(&mut v);
@@ -70,6 +88,7 @@ where
     scope: S::ScopeData,
     ctx: Ctx,
     expr_ctx: ExprCtx,
+    pure_builtins: Option<EsVersion>,
     used_recursively: AHashMap<Id, RecursiveUsage>,
 }
 
@@ -89,6 +108,7 @@ where
                 ..self.ctx
             },
             expr_ctx: self.expr_ctx.clone(),
+            pure_builtins: self.pure_builtins,
             scope: Default::default(),
             used_recursively: self.used_recursively.clone(),
         };
@@ -106,6 +126,22 @@ where
         ret
     }
 
+    /// Used for calls, but not for `new`.
+    fn callee_kind(&self, callee: &Expr) -> CalleeKind {
+        match self.pure_builtins {
+            Some(target)
+                if is_builtin_without_arg_mutation(
+                    callee,
+                    target,
+                    self.expr_ctx.unresolved_ctxt,
+                ) =>
+            {
+                CalleeKind::NoMutate
+            }
+            _ => CalleeKind::from_expr(callee, &self.expr_ctx),
+        }
+    }
+
     fn visit_pat_id(&mut self, i: &Ident) {
         let Ctx {
             in_left_of_for_loop,
@@ -402,7 +438,7 @@ where
             let ctx = Ctx {
                 inline_prevented,
                 in_call_arg_of: match &n.callee {
-                    Callee::Expr(e) => Some(CalleeKind::from_expr(e, &self.expr_ctx)),
+                    Callee::Expr(e) => Some(self.callee_kind(e)),
                     _ => Some(CalleeKind::Unknown),
                 },
                 is_delete_arg: false,
@@ -3,4 +3,5 @@
 pub mod alias;
 pub mod analyzer;
 pub mod marks;
+pub mod pure_globals;
 pub mod util;
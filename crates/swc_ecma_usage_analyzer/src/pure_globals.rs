@@ -0,0 +1,273 @@
+//! Side-effect-free functions and constructors of the standard library.
+//!
+//! Entries are keyed by the version which introduced them, so a call is only
+//! treated as pure if the built-in exists natively in the target environment.
+//! Otherwise it may be provided by a polyfill, which we know nothing about.
+
+use swc_common::SyntaxContext;
+use swc_ecma_ast::*;
+
+/// Arguments a pure built-in can be called with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PureArgs {
+    /// Any arguments. Side effects of the arguments themselves should be
+    /// preserved by the caller.
+    Any,
+    /// Arguments are converted to primitives, which calls methods like
+    /// `valueOf`, `toString` or `Symbol.split` of objects. Like terser, such
+    /// calls are only pure for primitive literals unless `unsafe` is enabled.
+    Coerced,
+    /// No arguments, like `new Set()`.
+    Empty,
+    /// The built-in mutates or reads properties of its arguments, so all of
+    /// them should be fresh literals without accessors, like
+    /// `Object.freeze({})`.
+    Fresh,
+}
+
+struct Entry {
+    path: &'static str,
+    since: EsVersion,
+    args: PureArgs,
+}
+
+macro_rules! entries {
+    ($($since:ident, $args:ident: [$($path:literal),* $(,)?];)*) => {
+        &[$($(Entry {
+            path: $path,
+            since: EsVersion::$since,
+            args: PureArgs::$args,
+        },)*)*]
+    };
+}
+
+static CALLS: &[Entry] = entries!(
+    Es3, Any: ["Boolean"];
+    Es3, Coerced: [
+        "isFinite",
+        "isNaN",
+        "parseFloat",
+        "parseInt",
+        "String.fromCharCode",
+        "Math.abs",
+        "Math.acos",
+        "Math.asin",
+        "Math.atan",
+        "Math.atan2",
+        "Math.ceil",
+        "Math.cos",
+        "Math.exp",
+        "Math.floor",
+        "Math.log",
+        "Math.max",
+        "Math.min",
+        "Math.pow",
+        "Math.random",
+        "Math.round",
+        "Math.sin",
+        "Math.sqrt",
+        "Math.tan",
+    ];
+    Es5, Any: ["Array.isArray", "Date.now"];
+    Es5, Fresh: [
+        "Object.freeze",
+        "Object.getOwnPropertyNames",
+        "Object.keys",
+        "Object.preventExtensions",
+        "Object.seal",
+    ];
+    Es2015, Any: [
+        "Object.is",
+        "Number.isFinite",
+        "Number.isInteger",
+        "Number.isNaN",
+        "Number.isSafeInteger",
+    ];
+    Es2015, Coerced: [
+        "Symbol",
+        "Math.acosh",
+        "Math.asinh",
+        "Math.atanh",
+        "Math.cbrt",
+        "Math.clz32",
+        "Math.cosh",
+        "Math.expm1",
+        "Math.fround",
+        "Math.hypot",
+        "Math.imul",
+        "Math.log10",
+        "Math.log1p",
+        "Math.log2",
+        "Math.sign",
+        "Math.sinh",
+        "Math.tanh",
+        "Math.trunc",
+    ];
+    Es2017, Fresh: ["Object.entries", "Object.values"];
+);
+
+static NEWS: &[Entry] = entries!(
+    Es3, Coerced: [
+        "Error",
+        "EvalError",
+        "RangeError",
+        "ReferenceError",
+        "SyntaxError",
+        "TypeError",
+        "URIError",
+    ];
+    Es3, Empty: ["Array", "Date", "Object"];
+    Es2015, Empty: ["Map", "Set", "WeakMap", "WeakSet"];
+);
+
+/// Methods of `String.prototype`, which are pure if called on a string
+/// literal.
+static STRING_METHODS: &[Entry] = entries!(
+    Es3, Coerced: [
+        "charAt",
+        "charCodeAt",
+        "concat",
+        "indexOf",
+        "lastIndexOf",
+        "localeCompare",
+        "slice",
+        "split",
+        "substr",
+        "substring",
+        "toLocaleLowerCase",
+        "toLocaleUpperCase",
+        "toLowerCase",
+        "toString",
+        "toUpperCase",
+        "valueOf",
+    ];
+    Es5, Coerced: ["trim"];
+    Es2015, Coerced: ["codePointAt", "endsWith", "includes", "startsWith"];
+    Es2017, Coerced: ["padEnd", "padStart"];
+    Es2019, Coerced: ["trimEnd", "trimStart"];
+    Es2022, Coerced: ["at"];
+);
+
+/// Returns true if calling `callee` with `args` is side-effect-free when
+/// targeting `target`, ignoring side effects of `args` themselves.
+///
+/// `unresolved_ctxt` is the syntax context of references to global
+/// variables. If `unsafe_coercion` is true, [PureArgs::Coerced] built-ins
+/// are pure for any arguments.
+pub fn is_pure_builtin_call(
+    callee: &Expr,
+    args: &[ExprOrSpread],
+    target: EsVersion,
+    unresolved_ctxt: SyntaxContext,
+    unsafe_coercion: bool,
+) -> bool {
+    let entry = match callee {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if matches!(&**obj, Expr::Lit(Lit::Str(..))) => find(STRING_METHODS, &prop.sym, target),
+        _ => global_path(callee, unresolved_ctxt).and_then(|path| find(CALLS, &path, target)),
+    };
+
+    match entry {
+        Some(args_kind) => are_args_allowed(args_kind, args, unsafe_coercion),
+        None => false,
+    }
+}
+
+/// Returns true if `new callee(...args)` is side-effect-free when targeting
+/// `target`, ignoring side effects of `args` themselves.
+pub fn is_pure_builtin_new(
+    callee: &Expr,
+    args: Option<&[ExprOrSpread]>,
+    target: EsVersion,
+    unresolved_ctxt: SyntaxContext,
+    unsafe_coercion: bool,
+) -> bool {
+    match global_path(callee, unresolved_ctxt).and_then(|path| find(NEWS, &path, target)) {
+        Some(args_kind) => are_args_allowed(args_kind, args.unwrap_or_default(), unsafe_coercion),
+        None => false,
+    }
+}
+
+/// Returns true if calling `callee` does not mutate the arguments when
+/// targeting `target`.
+///
+/// Only built-ins which accept [PureArgs::Any] are included, as conversions
+/// can call arbitrary methods and other built-ins read or iterate their
+/// arguments. Constructors are excluded because `new` is required for them.
+pub fn is_builtin_without_arg_mutation(
+    callee: &Expr,
+    target: EsVersion,
+    unresolved_ctxt: SyntaxContext,
+) -> bool {
+    let args = global_path(callee, unresolved_ctxt).and_then(|path| find(CALLS, &path, target));
+
+    matches!(args, Some(PureArgs::Any))
+}
+
+/// Returns the dotted path of `e`, like `Math.max`, if it's a property of a
+/// global variable.
+pub fn global_path(e: &Expr, unresolved_ctxt: SyntaxContext) -> Option<String> {
+    match e {
+        Expr::Ident(i) if i.span.ctxt == unresolved_ctxt => Some(i.sym.to_string()),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            let mut path = global_path(obj, unresolved_ctxt)?;
+            path.push('.');
+            path.push_str(&prop.sym);
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+fn find(table: &[Entry], path: &str, target: EsVersion) -> Option<PureArgs> {
+    table
+        .iter()
+        .find(|e| e.path == path && e.since <= target)
+        .map(|e| e.args)
+}
+
+fn are_args_allowed(kind: PureArgs, args: &[ExprOrSpread], unsafe_coercion: bool) -> bool {
+    // Spreading invokes an iterator.
+    if args.iter().any(|arg| arg.spread.is_some()) {
+        return false;
+    }
+
+    match kind {
+        PureArgs::Any => true,
+        PureArgs::Coerced => unsafe_coercion || args.iter().all(|arg| is_primitive_lit(&arg.expr)),
+        PureArgs::Empty => args.is_empty(),
+        PureArgs::Fresh => args.iter().all(|arg| is_fresh_without_accessors(&arg.expr)),
+    }
+}
+
+fn is_primitive_lit(e: &Expr) -> bool {
+    match e {
+        Expr::Lit(lit) => !matches!(lit, Lit::Regex(..) | Lit::JSXText(..)),
+        Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+        _ => false,
+    }
+}
+
+/// `Object.values` and `Object.entries` invoke getters, and spreading an
+/// object invokes getters of the spread value.
+fn is_fresh_without_accessors(e: &Expr) -> bool {
+    match e {
+        Expr::Object(obj) => obj.props.iter().all(|prop| match prop {
+            PropOrSpread::Prop(prop) => !matches!(&**prop, Prop::Getter(..) | Prop::Setter(..)),
+            PropOrSpread::Spread(..) => false,
+        }),
+        Expr::Array(arr) => arr
+            .elems
+            .iter()
+            .all(|elem| elem.as_ref().map_or(true, |elem| elem.spread.is_none())),
+        Expr::Fn(..) | Expr::Arrow(..) => true,
+        _ => false,
+    }
+}
@@ -2047,6 +2047,8 @@ impl Minifier<'_> {
             &swc_ecma_minifier::option::ExtraOptions {
                 unresolved_mark,
                 top_level_mark,
+                mangle_name_cache: None,
+                optimization_trace: None,
             },
         );
 
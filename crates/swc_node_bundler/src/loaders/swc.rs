@@ -126,14 +126,17 @@ impl SwcLoader {
             }
         }
 
-        let fm = self
-            .compiler
-            .cm
-            .load_file(match name {
-                FileName::Real(v) => v,
-                _ => bail!("swc-loader only accepts path. Got `{}`", name),
-            })
-            .with_context(|| format!("failed to load file `{}`", name))?;
+        let path = match name {
+            FileName::Real(v) => v,
+            _ => bail!("swc-loader only accepts path. Got `{}`", name),
+        };
+        // Packages of Yarn Plug'n'Play may be in zip archives.
+        #[cfg(feature = "pnp")]
+        let fm = swc_ecma_loader::resolvers::pnp::read_to_string(path)
+            .map(|src| self.compiler.cm.new_source_file(name.clone(), src));
+        #[cfg(not(feature = "pnp"))]
+        let fm = self.compiler.cm.load_file(path);
+        let fm = fm.with_context(|| format!("failed to load file `{}`", name))?;
 
         if let FileName::Real(path) = name {
             if let Some(ext) = path.extension() {
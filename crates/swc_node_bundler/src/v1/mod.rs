@@ -64,6 +64,14 @@ impl Config {
             .as_ref()
             .and_then(|options| options.codegen_target())
     }
+
+    /// Returns true if `optimization.splitChunks` is enabled.
+    pub fn split_chunks(&self) -> bool {
+        self.optimization
+            .as_ref()
+            .and_then(|optimization| optimization.split_chunks)
+            .unwrap_or(false)
+    }
 }
 
 #[derive(StringEnum)]
@@ -11,6 +11,10 @@ pub struct OptimizationConfig {
     #[serde(skip)]
     pub minimizer: Option<JsCallback<String, String>>,
 
+    /// Emit modules loaded by `import()` as separate chunks, and move modules
+    /// shared by multiple chunks into common chunks.
+    ///
+    /// `null` is the same as `false`.
     #[serde(default)]
-    pub split_chunks: Option<()>,
+    pub split_chunks: Option<bool>,
 }
@@ -102,10 +102,9 @@ fn pass(input_dir: PathBuf) {
                         .code;
 
                     let name = match bundled.kind {
-                        BundleKind::Named { name } | BundleKind::Lib { name } => {
-                            PathBuf::from(name)
-                        }
-                        BundleKind::Dynamic => format!("dynamic.{}.js", bundled.id).into(),
+                        BundleKind::Named { name }
+                        | BundleKind::Lib { name }
+                        | BundleKind::Dynamic { name } => PathBuf::from(name),
                     };
 
                     let output_path = entry
@@ -77,6 +77,21 @@ impl PluginModuleCacheInner {
         is_in_cache
     }
 
+    /// Returns the hash of the plugin module bytes for the corresponding key.
+    ///
+    /// The hash is only available if the module was stored via
+    /// `store_bytes_from_path` with FileSystemCache enabled, and changes
+    /// whenever the plugin binary changes.
+    pub fn get_module_hash(&self, key: &str) -> Option<String> {
+        #[cfg(all(not(target_arch = "wasm32"), feature = "filesystem_cache"))]
+        return self
+            .fs_cache_hash_store
+            .get(key)
+            .map(|hash| hash.to_string());
+
+        None
+    }
+
     /// Insert raw plugin module bytes into cache does not have compiled
     /// wasmer::Module. The bytes stored in this type of cache will return
     /// RawPluginModuleBytes. It is strongly recommend to avoid using this
@@ -38,6 +38,40 @@ export interface JsMinifyOptions {
     outputPath?: string;
 
     inlineSourcesContent?: boolean;
+
+    /**
+     * Names chosen by the mangler for previous files, in the format of
+     * `nameCache` of terser. Original names are prefixed with `$`. The
+     * updated cache is returned as `nameCache` of `output` of the result.
+     */
+    nameCache?: {
+        vars?: {
+            props?: Record<string, string>;
+        };
+        props?: {
+            props?: Record<string, string>;
+        };
+    };
+
+    /**
+     * Reports raw, gzip and brotli sizes of the output as `output` of the result.
+     */
+    reportSize?: boolean;
+
+    /**
+     * Fails if sizes of the output in bytes exceed these.
+     */
+    sizeBudget?: {
+        raw?: number;
+        gzip?: number;
+        brotli?: number;
+    };
+
+    /**
+     * Records why the compressor kept code, as `optimizationTrace` of `output`
+     * of the result.
+     */
+    optimizationTrace?: boolean;
 }
 
 /**
@@ -254,6 +288,25 @@ export interface TerserCompressOptions {
 
     pure_funcs?: string[];
 
+    /**
+     * Drop unused calls to side-effect-free built-ins of the target version,
+     * like `Math.max` or `Object.freeze({})`. Built-ins which convert their
+     * arguments are only dropped for primitive literals, unless `unsafe` is
+     * enabled.
+     *
+     * Defaults to false.
+     */
+    pure_builtins?: boolean;
+
+    /**
+     * Side-effect-free functions of libraries.
+     *
+     * A string is a path to a JSON file with the same shape, resolved against
+     * the directory of `.swcrc`, or the current directory for `minify()`.
+     * Paths are not supported by wasm bindings.
+     */
+    pure_libraries?: PureLibraries | string;
+
     reduce_funcs?: boolean;
 
     reduce_vars?: boolean;
@@ -299,6 +352,19 @@ export interface TerserCompressOptions {
     module?: boolean;
 }
 
+export interface PureLibraries {
+    /**
+     * Dotted paths of global functions, like `_.map`.
+     */
+    globals?: string[];
+
+    /**
+     * Module specifiers. All functions imported from matching modules are
+     * pure. A specifier ending with `/*` matches all subpaths of the package.
+     */
+    modules?: string[];
+}
+
 export interface TerserMangleOptions {
     props?: TerserManglePropertiesOptions;
 
@@ -635,6 +701,13 @@ export interface JscConfig {
         [from: string]: string[];
     };
 
+    /**
+     * Conditions of the `exports` and `imports` fields of package.json, like
+     * `development`. `import` or `require` is added depending on the kind of
+     * the request, and `default` always matches.
+     */
+    conditions?: string[];
+
     minify?: JsMinifyOptions;
 
     preserveAllComments?: boolean;
@@ -1083,6 +1156,11 @@ export interface Output {
      * Sourcemap (**not** base64 encoded)
      */
     map?: string;
+    /**
+     * Extra data as JSON, e.g. `size` if `reportSize` of minify options is
+     * set.
+     */
+    output?: string;
 }
 
 export interface MatchPattern {}